@@ -0,0 +1,88 @@
+//! Separable DoG convolution against the per-cell reference
+//!
+//! Run with `cargo bench --bench ganglion_convolution`. Times
+//! `GanglionLayer::process_image` and the equivalent per-cell
+//! `GanglionCell::compute_response` on midget layers of growing size, and
+//! fails if the two disagree or the separable path is not faster.
+
+use neuron::ganglion::{GanglionCell, GanglionLayer};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Timed runs per path; the fastest is reported
+const RUNS: usize = 3;
+
+/// Largest allowed difference between the two paths (signed response)
+const TOLERANCE: f32 = 0.05;
+
+/// Textured test image: a diagonal ramp with a superimposed checkerboard
+fn test_image(size: usize) -> Vec<Vec<f32>> {
+    (0..size)
+        .map(|y| {
+            (0..size)
+                .map(|x| {
+                    let ramp = (x + y) as f32 / (2 * size) as f32;
+                    let check = if (x / 16 + y / 16) % 2 == 0 { 0.25 } else { 0.0 };
+                    ramp * 0.75 + check
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Fastest of several runs of a closure
+fn fastest(mut run: impl FnMut()) -> Duration {
+    (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            run();
+            start.elapsed()
+        })
+        .min()
+        .unwrap_or_default()
+}
+
+fn main() {
+    for size in [256, 512, 1024] {
+        let image = test_image(size);
+        let mut layer = GanglionLayer::midget(size, size);
+        let mut references: Vec<GanglionCell> = layer
+            .cells()
+            .iter()
+            .map(|cell| {
+                let (x, y) = cell.position();
+                let mut reference =
+                    GanglionCell::new(cell.id(), cell.cell_type(), x, y, cell.center_radius(), cell.surround_radius());
+                reference.set_profile(cell.profile());
+                reference
+            })
+            .collect();
+
+        // Compare the first responses, before contrast gain control adapts the layer
+        layer.process_image(&image);
+        for reference in &mut references {
+            reference.compute_response(&image);
+        }
+        let max_difference = layer
+            .cells()
+            .iter()
+            .zip(&references)
+            .map(|(cell, reference)| (cell.signed_response() - reference.signed_response()).abs())
+            .fold(0.0, f32::max);
+
+        let separable = fastest(|| layer.process_image(black_box(&image)));
+        let per_cell = fastest(|| {
+            for reference in &mut references {
+                reference.compute_response(black_box(&image));
+            }
+        });
+        let speed_up = per_cell.as_secs_f64() / separable.as_secs_f64().max(f64::EPSILON);
+
+        println!(
+            "{size:>5}²  {:>7} cells  separable {separable:>10.2?}  per-cell {per_cell:>10.2?}  speed-up {speed_up:>5.1}×  max difference {max_difference:.6}",
+            layer.cells().len()
+        );
+        assert!(max_difference < TOLERANCE, "paths disagree by {max_difference} at {size}²");
+        assert!(separable < per_cell, "separable convolution is not faster at {size}²");
+    }
+}
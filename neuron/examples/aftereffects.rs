@@ -0,0 +1,62 @@
+use neuron::experiments::{
+    color_afterimage, contrast_adaptation, edge_energy, mean_blue_yellow, mean_red_green, tilt_aftereffect,
+};
+use std::env;
+use std::path::Path;
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let output_dir = args.get(1).map(String::as_str).unwrap_or("aftereffects");
+    let size = 48;
+    let frames = 60;
+
+    if let Err(e) = std::fs::create_dir_all(output_dir) {
+        eprintln!("❌ Cannot create {}: {}", output_dir, e);
+        std::process::exit(1);
+    }
+    let output = |name: &str| Path::new(output_dir).join(name).to_string_lossy().into_owned();
+
+    println!("🎨 Colour afterimages ({} frames of adaptation, then grey)", frames);
+    for (name, color) in [("red", [0.9, 0.1, 0.1]), ("green", [0.1, 0.9, 0.1]), ("blue", [0.1, 0.1, 0.9])] {
+        let result = color_afterimage(size, size, color, frames).run().expect("valid protocol");
+        println!(
+            "  {:<6} red-green {:>+8.2}  blue-yellow {:>+8.2}",
+            name,
+            result.aftereffect(mean_red_green),
+            result.aftereffect(mean_blue_yellow)
+        );
+        let path = output(&format!("afterimage_{}.png", name));
+        match result.save_comparison(|response| &response.red_green_map, &path) {
+            Ok(()) => println!("         saved {}", path),
+            Err(e) => eprintln!("❌ {}", e),
+        }
+    }
+
+    println!("\n📐 Tilt aftereffect (test grating at 45°)");
+    for adapt_degrees in [0.0, 90.0] {
+        let result = tilt_aftereffect(size, size, adapt_degrees, 45.0, frames).run().expect("valid protocol");
+        println!(
+            "  adapter {:>4.0}°: readout shift {:>+6.2}°, repulsion {:>+6.2}°",
+            adapt_degrees,
+            result.orientation_shift(),
+            result.tilt_repulsion()
+        );
+    }
+
+    println!("\n🌗 Contrast adaptation (full-contrast adapter)");
+    for test_contrast in [0.1, 0.3, 0.6] {
+        let result = contrast_adaptation(size, size, 1.0, test_contrast, frames).run().expect("valid protocol");
+        println!(
+            "  test contrast {:.1}: edge response {:>+6.1}%",
+            test_contrast,
+            100.0 * result.relative_aftereffect(edge_energy)
+        );
+        if test_contrast == 0.3 {
+            let path = output("contrast_adaptation.png");
+            match result.save_comparison(|response| &response.edge_map, &path) {
+                Ok(()) => println!("         saved {}", path),
+                Err(e) => eprintln!("❌ {}", e),
+            }
+        }
+    }
+}
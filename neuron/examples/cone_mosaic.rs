@@ -0,0 +1,52 @@
+//! Example: Generate and export cone mosaics
+//!
+//! Saves the original diagonal layout next to realistic hexagonal mosaics
+//! (random and clumped L/M assignment, S-cone-free foveola) for inspection.
+
+use neuron::color_vision::ConePopulation;
+use neuron::image_utils::visualize_cone_mosaic;
+use neuron::mosaic::{ConeMosaic, MosaicParameters};
+use neuron::ConeType;
+
+fn main() {
+    println!("╔═══════════════════════════════════════════════════╗");
+    println!("║   Cone Mosaic Generator                          ║");
+    println!("╚═══════════════════════════════════════════════════╝\n");
+
+    std::fs::create_dir_all("images/output").expect("Failed to create images/output directory");
+
+    let size = 80;
+    let scale = 8;
+    let population = ConePopulation::normal();
+
+    let mosaics = [
+        ("diagonal", ConeMosaic::diagonal(size, size)),
+        (
+            "random",
+            ConeMosaic::generate(size, size, &population, &MosaicParameters::new(1).with_clumping(0.0)),
+        ),
+        (
+            "clumped",
+            ConeMosaic::generate(size, size, &population, &MosaicParameters::new(1).with_clumping(0.6)),
+        ),
+        (
+            "foveola",
+            ConeMosaic::generate(size, size, &population, &MosaicParameters::new(1).with_foveola(15.0)),
+        ),
+    ];
+
+    for (name, mosaic) in &mosaics {
+        let output_path = format!("images/output/mosaic_{}.png", name);
+        match visualize_cone_mosaic(mosaic, scale, &output_path) {
+            Ok(()) => println!(
+                "🔬 {:<9} L {:>4.1}%  M {:>4.1}%  S {:>4.1}%  → {}",
+                name,
+                mosaic.fraction(ConeType::L) * 100.0,
+                mosaic.fraction(ConeType::M) * 100.0,
+                mosaic.fraction(ConeType::S) * 100.0,
+                output_path
+            ),
+            Err(e) => eprintln!("   Warning: Could not save mosaic: {}", e),
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use neuron::visual_pathway::test_patterns::checkerboard;
+use neuron::{Eye, EyeMovement, FixationalEyeMovements, SaccadeController, VisualPathway};
+
+fn main() {
+    let scene = checkerboard(96, 96, 4);
+    let frames = 45;
+    let centre = (48.0, 48.0);
+
+    let eyes = [
+        ("stabilized", Eye::still(centre)),
+        ("drift only", Eye::new(centre, FixationalEyeMovements::new().with_microsaccades(0.0, 0.0), 1)),
+        ("fixational", Eye::new(centre, FixationalEyeMovements::new(), 1)),
+        (
+            "scan path",
+            Eye::new(centre, FixationalEyeMovements::new(), 1).with_saccades(
+                SaccadeController::new(centre)
+                    .with_fixation_duration(400.0)
+                    .with_scan_path(&[(30.0, 30.0), (66.0, 40.0), (48.0, 66.0)]),
+            ),
+        ),
+    ];
+
+    println!("👁️  Magnocellular activity while viewing a static checkerboard");
+    for (name, mut eye) in eyes {
+        let mut pathway = VisualPathway::new(32, 32);
+        let responses = pathway.process_through_eye(&scene, &mut eye, frames);
+
+        let activity: Vec<f32> = responses
+            .iter()
+            .map(|(_, response)| response.lgn.magnocellular.iter().flatten().sum())
+            .collect();
+        let microsaccades = responses.iter().filter(|(gaze, _)| gaze.movement == EyeMovement::Microsaccade).count();
+        let saccade_frames = responses.iter().filter(|(gaze, _)| gaze.movement == EyeMovement::Saccade).count();
+        let (last_gaze, _) = responses.last().expect("at least one frame");
+
+        println!(
+            "  {:<10}  onset {:>8.1}  late {:>8.1}  microsaccades {:>2}  saccade frames {:>2}  final gaze ({:.1}, {:.1})",
+            name,
+            activity.iter().take(5).sum::<f32>() / 5.0,
+            activity.iter().skip(frames - 10).sum::<f32>() / 10.0,
+            microsaccades,
+            saccade_frames,
+            last_gaze.x,
+            last_gaze.y
+        );
+    }
+}
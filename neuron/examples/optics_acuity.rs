@@ -0,0 +1,66 @@
+use neuron::visual_pathway::test_patterns::sine_grating;
+use neuron::{EyeOptics, VisualPathway};
+
+fn main() {
+    let size = 48;
+    let frequencies = [5.0, 10.0, 20.0, 30.0, 40.0, 60.0];
+
+    println!("🔍 Optical modulation transfer (555 nm)");
+    print!("  pupil  ");
+    for cpd in frequencies {
+        print!("{:>7.0}cpd", cpd);
+    }
+    println!();
+    for pupil in [2.0, 3.0, 6.0] {
+        let optics = EyeOptics::new(pupil);
+        print!("  {:.0} mm   ", pupil);
+        for cpd in frequencies {
+            print!("{:>10.3}", optics.modulation_transfer(555.0, cpd));
+        }
+        println!();
+    }
+
+    println!("\n🌈 Longitudinal chromatic aberration (3 mm pupil)");
+    let optics = EyeOptics::standard();
+    for wavelength in [450.0, 500.0, 555.0, 600.0, 650.0] {
+        println!(
+            "  {:>3.0} nm: defocus {:>+5.2} D, PSF σ {:>5.2} arcmin, 20 cpd contrast {:.3}",
+            wavelength,
+            optics.chromatic_defocus(wavelength),
+            optics.psf_sigma(wavelength),
+            optics.modulation_transfer(wavelength, 20.0)
+        );
+    }
+
+    println!("\n👁️  Cone grating response with and without optics (20% contrast)");
+    for period in [16.0, 8.0, 6.0, 4.0, 3.0] {
+        let image = sine_grating(size, size, period, 0.0, 0.0, 0.2);
+        let sharp = grating_amplitude(&mut VisualPathway::new(size, size), &image, period);
+        let blurred = grating_amplitude(
+            &mut VisualPathway::new(size, size).with_optics(EyeOptics::standard()),
+            &image,
+            period,
+        );
+        let cpd = optics.pixels_per_degree() / period;
+        println!(
+            "  {:>4.1} cpd: sharp {:.4}, through optics {:.4} ({:>5.1}%)",
+            cpd,
+            sharp,
+            blurred,
+            100.0 * blurred / sharp.max(f32::EPSILON)
+        );
+    }
+}
+
+/// Amplitude of a horizontal grating in the cone image
+fn grating_amplitude(pathway: &mut VisualPathway, image: &[Vec<f32>], period: f32) -> f32 {
+    let response = pathway.process_grayscale_image(image);
+    let (mut re, mut im) = (0.0f32, 0.0f32);
+    for (y, row) in response.cone_activations.iter().enumerate() {
+        let mean = row.iter().sum::<f32>() / row.len() as f32;
+        let angle = 2.0 * std::f32::consts::PI * y as f32 / period;
+        re += mean * angle.cos();
+        im += mean * angle.sin();
+    }
+    re.hypot(im) * 2.0 / image.len() as f32
+}
@@ -0,0 +1,70 @@
+//! Example: Process a colour photograph through the visual system
+//!
+//! The image keeps its colour: sRGB pixels are converted to LMS cone
+//! excitations so that L, M and S cones each see their own signal.
+
+use neuron::image_utils::{ascii_visualization, load_and_resize_color, save_grayscale_image};
+use neuron::visual_pathway::VisualPathway;
+use std::env;
+
+fn main() {
+    println!("╔═══════════════════════════════════════════════════╗");
+    println!("║   Colour Image Visual Processing Demo            ║");
+    println!("╚═══════════════════════════════════════════════════╝\n");
+
+    let args: Vec<String> = env::args().collect();
+
+    if args.len() < 2 {
+        println!("Usage: {} <image_path>", args[0]);
+        println!("\nExample:");
+        println!("  cargo run --example process_color_image --release -- images/input/photo.jpg");
+        return;
+    }
+
+    let image_path = &args[1];
+    println!("📷 Loading image: {}\n", image_path);
+
+    std::fs::create_dir_all("images/output").expect("Failed to create images/output directory");
+
+    let size = 64;
+
+    let image = match load_and_resize_color(image_path, size, size) {
+        Ok(img) => {
+            println!("✓ Colour image loaded and resized to {}x{}", size, size);
+            img
+        }
+        Err(e) => {
+            eprintln!("❌ Error loading image: {}", e);
+            return;
+        }
+    };
+
+    let mut pathway = VisualPathway::new(size as usize, size as usize);
+
+    println!("⚡ Processing image through visual pathway (LMS cone excitations)...\n");
+    let response = pathway.process_color_image(&image);
+
+    println!("1️⃣  Photoreceptor Layer (L, M and S cones)");
+    let total_activation: f32 = response.cone_activations.iter().flatten().sum();
+    println!(
+        "   Average cone activation: {:.1}%",
+        total_activation / (size * size) as f32 * 100.0
+    );
+
+    println!("\n2️⃣  Ganglion Cell Layer (Edge Detection)");
+    let output_path = "images/output/color_edges.png";
+    if let Err(e) = save_grayscale_image(&response.edge_map, output_path) {
+        eprintln!("   Warning: Could not save edge map: {}", e);
+    } else {
+        println!("   ✓ Edge map saved to '{}'", output_path);
+    }
+
+    println!("\n3️⃣  V1 Primary Visual Cortex (Orientation Detection)");
+    println!("   ├─ Horizontal edges: {:.2}", response.features.horizontal_strength);
+    println!("   ├─ Vertical edges:   {:.2}", response.features.vertical_strength);
+    println!("   └─ Diagonal edges:   {:.2}", response.features.diagonal_strength);
+    println!("   Dominant orientation: {}", response.features.dominant_orientation());
+
+    println!("\n📊 Edge Map Preview (ASCII):");
+    println!("{}", ascii_visualization(&response.edge_map, 60));
+}
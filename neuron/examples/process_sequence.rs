@@ -0,0 +1,52 @@
+use neuron::image_utils::{load_grayscale_gif, load_grayscale_sequence};
+use neuron::visual_pathway::VisualPathway;
+use std::env;
+use std::path::Path;
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() < 2 {
+        eprintln!("Usage: {} <frames_dir | movie.gif> [frame_interval_ms]", args[0]);
+        eprintln!("  frame_interval_ms: Time between frames (default: GIF delay, or 33.3)");
+        std::process::exit(1);
+    }
+
+    let input = Path::new(&args[1]);
+    let is_gif = input
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("gif"));
+    let loaded = if is_gif {
+        load_grayscale_gif(input).map(|(frames, interval)| (frames, Some(interval)))
+    } else {
+        load_grayscale_sequence(input).map(|frames| (frames, None))
+    };
+    let (frames, gif_interval) = match loaded {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            eprintln!("❌ Error loading frames: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let (width, height) = (frames[0][0].len(), frames[0].len());
+    let mut pathway = VisualPathway::new(width, height);
+    if let Some(interval) = args.get(2).and_then(|s| s.parse().ok()).or(gif_interval) {
+        pathway.set_frame_interval(interval);
+    }
+
+    println!("🎞️  {} frames of {}×{} at {:.1} ms", frames.len(), width, height, pathway.frame_interval_ms());
+    println!("\n{:>8}  {:>10}  {:>10}  {:>10}  {:>10}", "t (ms)", "edges", "horiz", "vert", "diag");
+
+    for (index, response) in pathway.process_sequence(&frames).iter().enumerate() {
+        let edge_energy: f32 = response.edge_map.iter().flatten().sum();
+        let features = &response.features;
+        println!(
+            "{:>8.1}  {:>10.1}  {:>10.2}  {:>10.2}  {:>10.2}",
+            index as f32 * pathway.frame_interval_ms(),
+            edge_energy,
+            features.horizontal_strength,
+            features.vertical_strength,
+            features.diagonal_strength
+        );
+    }
+}
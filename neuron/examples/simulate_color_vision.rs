@@ -0,0 +1,64 @@
+//! Example: Simulate colour vision deficiencies
+//!
+//! Renders how a colour image appears to protanopes, deuteranopes,
+//! tritanopes and an anomalous trichromat, and compares the edges each
+//! observer's visual pathway detects.
+
+use neuron::color_vision::{simulate_percept, ColorVisionDeficiency, ConePopulation};
+use neuron::image_utils::{load_and_resize_color, save_color_image};
+use neuron::visual_pathway::VisualPathway;
+use std::env;
+
+fn main() {
+    println!("╔═══════════════════════════════════════════════════╗");
+    println!("║   Colour Vision Deficiency Simulation            ║");
+    println!("╚═══════════════════════════════════════════════════╝\n");
+
+    let args: Vec<String> = env::args().collect();
+
+    if args.len() < 2 {
+        println!("Usage: {} <image_path>", args[0]);
+        println!("\nExample:");
+        println!("  cargo run --example simulate_color_vision --release -- images/input/photo.jpg");
+        return;
+    }
+
+    let image_path = &args[1];
+    println!("📷 Loading image: {}\n", image_path);
+
+    std::fs::create_dir_all("images/output").expect("Failed to create images/output directory");
+
+    let size = 128;
+
+    let image = match load_and_resize_color(image_path, size, size) {
+        Ok(img) => img,
+        Err(e) => {
+            eprintln!("❌ Error loading image: {}", e);
+            return;
+        }
+    };
+
+    let observers = [
+        ("normal", ColorVisionDeficiency::Normal),
+        ("protanopia", ColorVisionDeficiency::Protanopia),
+        ("deuteranopia", ColorVisionDeficiency::Deuteranopia),
+        ("tritanopia", ColorVisionDeficiency::Tritanopia),
+        ("protanomaly", ColorVisionDeficiency::Protanomaly { l_peak: 545.0 }),
+    ];
+
+    for (name, deficiency) in observers {
+        let population = ConePopulation::from_deficiency(deficiency);
+
+        let percept = simulate_percept(&image, &population);
+        let output_path = format!("images/output/percept_{}.png", name);
+        if let Err(e) = save_color_image(&percept, &output_path) {
+            eprintln!("   Warning: Could not save percept: {}", e);
+        }
+
+        let mut pathway = VisualPathway::with_cone_population(size as usize, size as usize, population);
+        let response = pathway.process_color_image(&image);
+        let edge_total: f32 = response.edge_map.iter().flatten().sum();
+
+        println!("👁  {:<13} edge total {:>9.1}  → {}", name, edge_total, output_path);
+    }
+}
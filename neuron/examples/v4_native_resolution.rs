@@ -1,3 +1,4 @@
+use neuron::foveation::Foveation;
 use neuron::image_utils::load_grayscale_image;
 use neuron::visual_pathway::VisualPathway;
 use std::env;
@@ -5,7 +6,7 @@ use std::env;
 fn main() {
     let args: Vec<String> = env::args().collect();
     if args.len() < 2 {
-        eprintln!("Usage: {} <image_path>", args[0]);
+        eprintln!("Usage: {} <image_path> [--foveated]", args[0]);
         std::process::exit(1);
     }
 
@@ -26,7 +27,14 @@ fn main() {
     println!("   Resolution: {}×{} pixels ({} total pixels)\n", width, height, width * height);
 
     // Create visual pathway with NATIVE dimensions
-    let mut pathway = VisualPathway::new(width, height);
+    // --foveated: fixate the image centre and sample the periphery sparsely
+    let mut pathway = if args.iter().any(|arg| arg == "--foveated") {
+        let pathway = VisualPathway::foveated(width, height, Foveation::centered(width, height));
+        println!("   Foveated retina: {} cones instead of {}\n", pathway.cone_count(), width * height);
+        pathway
+    } else {
+        VisualPathway::new(width, height)
+    };
 
     println!("🧠 Processing through visual pathway...");
     let response = pathway.process_grayscale_image(&image);
@@ -0,0 +1,390 @@
+//! Amacrine cells - Lateral inhibition and motion computation in the inner retina
+//!
+//! Starburst amacrine cells (SACs) are the core of retinal direction
+//! selectivity. Each dendrite of a SAC releases GABA mainly when a
+//! stimulus moves from the soma out towards the dendrite tip
+//! (centrifugal motion). Direction-selective ganglion cells receive this
+//! inhibition from dendrites pointing in their null direction, so motion
+//! in the null direction is vetoed while motion in the preferred
+//! direction passes.
+
+use crate::temporal::DEFAULT_FRAME_INTERVAL_MS;
+use std::collections::VecDeque;
+
+/// Time for the soma signal to reach the dendrite tips (ms)
+const SOMA_TO_TIP_DELAY_MS: f32 = 33.0;
+
+/// Direction of motion in image coordinates (y grows downwards)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MotionDirection {
+    /// Towards increasing x (0°)
+    Rightward,
+    /// Towards decreasing y (90°)
+    Upward,
+    /// Towards decreasing x (180°)
+    Leftward,
+    /// Towards increasing y (270°)
+    Downward,
+}
+
+impl MotionDirection {
+    /// All cardinal directions, counter-clockwise from rightward
+    pub const ALL: [MotionDirection; 4] = [
+        MotionDirection::Rightward,
+        MotionDirection::Upward,
+        MotionDirection::Leftward,
+        MotionDirection::Downward,
+    ];
+
+    /// Returns the one-pixel step in this direction as (dx, dy)
+    pub fn offset(&self) -> (i32, i32) {
+        match self {
+            Self::Rightward => (1, 0),
+            Self::Upward => (0, -1),
+            Self::Leftward => (-1, 0),
+            Self::Downward => (0, 1),
+        }
+    }
+
+    /// Returns the opposite direction
+    pub fn opposite(&self) -> Self {
+        match self {
+            Self::Rightward => Self::Leftward,
+            Self::Upward => Self::Downward,
+            Self::Leftward => Self::Rightward,
+            Self::Downward => Self::Upward,
+        }
+    }
+
+    /// Returns the direction in degrees, counter-clockwise from rightward
+    pub fn degrees(&self) -> f32 {
+        self.index() as f32 * 90.0
+    }
+
+    /// Returns the position of this direction in `ALL`
+    pub fn index(&self) -> usize {
+        match self {
+            Self::Rightward => 0,
+            Self::Upward => 1,
+            Self::Leftward => 2,
+            Self::Downward => 3,
+        }
+    }
+}
+
+/// Polarity of a starburst amacrine cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StarburstType {
+    /// ON starburst: driven by ON bipolar cells
+    On,
+    /// OFF starburst: driven by OFF bipolar cells
+    Off,
+}
+
+/// Starburst amacrine cell with one dendrite per cardinal direction
+#[derive(Debug)]
+pub struct StarburstAmacrineCell {
+    id: usize,
+    cell_type: StarburstType,
+
+    // Position of the soma and extent of the dendrites
+    x: usize,
+    y: usize,
+    dendrite_length: usize,
+
+    // Physiological state
+    soma_input: f32,
+    soma_history: VecDeque<f32>, // Soma input of past updates, oldest first
+    delay_updates: usize,        // Updates spanning the soma-to-tip delay
+    dendrite_outputs: [f32; 4], // GABA release of each dendrite, indexed by MotionDirection
+}
+
+impl StarburstAmacrineCell {
+    /// Creates a new starburst amacrine cell
+    ///
+    /// # Arguments
+    /// * `id` - Unique identifier
+    /// * `cell_type` - ON or OFF starburst
+    /// * `x`, `y` - Position of the soma in visual field
+    /// * `dendrite_length` - Distance from soma to dendrite tips (pixels)
+    pub fn new(id: usize, cell_type: StarburstType, x: usize, y: usize, dendrite_length: usize) -> Self {
+        Self {
+            id,
+            cell_type,
+            x,
+            y,
+            dendrite_length,
+            soma_input: 0.0,
+            soma_history: VecDeque::new(),
+            delay_updates: delay_updates(DEFAULT_FRAME_INTERVAL_MS),
+            dendrite_outputs: [0.0; 4],
+        }
+    }
+
+    /// Changes the time represented by one call of `update` (ms)
+    ///
+    /// The soma-to-tip delay stays the same in time, so it spans more
+    /// updates at shorter intervals.
+    pub fn set_frame_interval_ms(&mut self, frame_interval_ms: f32) {
+        self.delay_updates = delay_updates(frame_interval_ms);
+    }
+
+    /// Returns the cell ID
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /// Returns the cell type
+    pub fn cell_type(&self) -> StarburstType {
+        self.cell_type
+    }
+
+    /// Returns the position of the soma
+    pub fn position(&self) -> (usize, usize) {
+        (self.x, self.y)
+    }
+
+    /// Updates the cell with one frame of bipolar input
+    ///
+    /// A dendrite releases GABA when its tip is excited now and the soma
+    /// was excited one soma-to-tip delay earlier (the previous frame at
+    /// 30 Hz): the signature of a stimulus moving outwards along that
+    /// dendrite.
+    ///
+    /// # Arguments
+    /// * `bipolar` - Transient bipolar cell output of the cell's polarity
+    pub fn update(&mut self, bipolar: &[Vec<f32>]) {
+        self.soma_history.push_back(self.soma_input);
+        while self.soma_history.len() > self.delay_updates {
+            self.soma_history.pop_front();
+        }
+        let delayed_soma_input = if self.soma_history.len() == self.delay_updates {
+            self.soma_history.front().copied().unwrap_or(0.0)
+        } else {
+            0.0
+        };
+        self.soma_input = sample(bipolar, self.x as i32, self.y as i32);
+
+        for direction in MotionDirection::ALL {
+            let (dx, dy) = direction.offset();
+            let reach = self.dendrite_length as i32;
+            let tip = sample(bipolar, self.x as i32 + dx * reach, self.y as i32 + dy * reach);
+            self.dendrite_outputs[direction.index()] = tip.min(delayed_soma_input);
+        }
+    }
+
+    /// Returns the GABA release of the dendrite pointing in a direction
+    pub fn dendrite_output(&self, direction: MotionDirection) -> f32 {
+        self.dendrite_outputs[direction.index()]
+    }
+
+    /// Returns the cell to its unstimulated state
+    pub fn reset(&mut self) {
+        self.soma_input = 0.0;
+        self.soma_history.clear();
+        self.dendrite_outputs = [0.0; 4];
+    }
+}
+
+/// Number of updates spanning the soma-to-tip delay (at least one)
+fn delay_updates(frame_interval_ms: f32) -> usize {
+    ((SOMA_TO_TIP_DELAY_MS / frame_interval_ms.max(f32::EPSILON)).round() as usize).max(1)
+}
+
+/// Returns the value of a map at a point, or 0.0 outside it
+fn sample(map: &[Vec<f32>], x: i32, y: i32) -> f32 {
+    if x < 0 || y < 0 {
+        return 0.0;
+    }
+    map.get(y as usize)
+        .and_then(|row| row.get(x as usize))
+        .copied()
+        .unwrap_or(0.0)
+}
+
+/// Layer of ON and OFF starburst amacrine cells covering a visual field
+pub struct AmacrineLayer {
+    on_starburst: Vec<StarburstAmacrineCell>,
+    off_starburst: Vec<StarburstAmacrineCell>,
+    spacing: usize,
+    dendrite_length: usize,
+    columns: usize,
+    rows: usize,
+}
+
+impl AmacrineLayer {
+    /// Creates a new amacrine layer
+    ///
+    /// # Arguments
+    /// * `width`, `height` - Dimensions of visual field
+    /// * `spacing` - Distance between starburst somata
+    /// * `dendrite_length` - Distance from soma to dendrite tips
+    pub fn new(width: usize, height: usize, spacing: usize, dendrite_length: usize) -> Self {
+        let spacing = spacing.max(1);
+        let columns = width.div_ceil(spacing);
+        let rows = height.div_ceil(spacing);
+        let mut on_starburst = Vec::with_capacity(columns * rows);
+        let mut off_starburst = Vec::with_capacity(columns * rows);
+
+        for row in 0..rows {
+            for column in 0..columns {
+                let id = row * columns + column;
+                let (x, y) = (column * spacing, row * spacing);
+                on_starburst.push(StarburstAmacrineCell::new(id, StarburstType::On, x, y, dendrite_length));
+                off_starburst.push(StarburstAmacrineCell::new(id, StarburstType::Off, x, y, dendrite_length));
+            }
+        }
+
+        Self {
+            on_starburst,
+            off_starburst,
+            spacing,
+            dendrite_length,
+            columns,
+            rows,
+        }
+    }
+
+    /// Processes one frame of transient bipolar output
+    ///
+    /// # Arguments
+    /// * `on_bipolar` - ON transient bipolar output at each pixel
+    /// * `off_bipolar` - OFF transient bipolar output at each pixel
+    pub fn process(&mut self, on_bipolar: &[Vec<f32>], off_bipolar: &[Vec<f32>]) {
+        for cell in &mut self.on_starburst {
+            cell.update(on_bipolar);
+        }
+        for cell in &mut self.off_starburst {
+            cell.update(off_bipolar);
+        }
+    }
+
+    /// Returns the starburst inhibition onto a direction-selective cell
+    ///
+    /// The cell at (x, y) is contacted by the dendrite pointing in its
+    /// null direction, which belongs to the starburst cell one dendrite
+    /// length away in the preferred direction.
+    ///
+    /// # Arguments
+    /// * `x`, `y` - Position of the direction-selective ganglion cell
+    /// * `preferred` - Preferred direction of the ganglion cell
+    /// * `cell_type` - Polarity of the starburst cells providing inhibition
+    pub fn inhibition_onto(&self, x: usize, y: usize, preferred: MotionDirection, cell_type: StarburstType) -> f32 {
+        let (dx, dy) = preferred.offset();
+        let reach = self.dendrite_length as i32;
+        let soma_x = x as i32 + dx * reach;
+        let soma_y = y as i32 + dy * reach;
+        if soma_x < 0 || soma_y < 0 {
+            return 0.0;
+        }
+
+        let column = (soma_x as usize + self.spacing / 2) / self.spacing;
+        let row = (soma_y as usize + self.spacing / 2) / self.spacing;
+        if column >= self.columns || row >= self.rows {
+            return 0.0;
+        }
+
+        let cells = match cell_type {
+            StarburstType::On => &self.on_starburst,
+            StarburstType::Off => &self.off_starburst,
+        };
+        cells[row * self.columns + column].dendrite_output(preferred.opposite())
+    }
+
+    /// Returns the starburst cells of one polarity
+    pub fn starburst_cells(&self, cell_type: StarburstType) -> &[StarburstAmacrineCell] {
+        match cell_type {
+            StarburstType::On => &self.on_starburst,
+            StarburstType::Off => &self.off_starburst,
+        }
+    }
+
+    /// Changes the time represented by one call of `process` (ms)
+    pub fn set_frame_interval_ms(&mut self, frame_interval_ms: f32) {
+        for cell in self.on_starburst.iter_mut().chain(&mut self.off_starburst) {
+            cell.set_frame_interval_ms(frame_interval_ms);
+        }
+    }
+
+    /// Returns every cell to its unstimulated state
+    pub fn reset(&mut self) {
+        for cell in self.on_starburst.iter_mut().chain(&mut self.off_starburst) {
+            cell.reset();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A single bright pixel at column `x` of a 1-row map
+    fn spot(width: usize, x: usize) -> Vec<Vec<f32>> {
+        let mut map = vec![vec![0.0; width]; 1];
+        map[0][x] = 1.0;
+        map
+    }
+
+    #[test]
+    fn test_direction_geometry() {
+        assert_eq!(MotionDirection::Upward.opposite(), MotionDirection::Downward);
+        assert_eq!(MotionDirection::Leftward.degrees(), 180.0);
+        for direction in MotionDirection::ALL {
+            let (dx, dy) = direction.offset();
+            let (ox, oy) = direction.opposite().offset();
+            assert_eq!((dx + ox, dy + oy), (0, 0));
+        }
+    }
+
+    #[test]
+    fn test_starburst_dendrite_prefers_centrifugal_motion() {
+        // Spot moves outwards along the rightward dendrite (soma at 3, tip at 5)
+        let mut outward = StarburstAmacrineCell::new(0, StarburstType::On, 3, 0, 2);
+        outward.update(&spot(10, 3));
+        outward.update(&spot(10, 5));
+
+        // Spot moves inwards along the same dendrite
+        let mut inward = StarburstAmacrineCell::new(1, StarburstType::On, 3, 0, 2);
+        inward.update(&spot(10, 5));
+        inward.update(&spot(10, 3));
+
+        assert!(outward.dendrite_output(MotionDirection::Rightward) > 0.5);
+        assert_eq!(inward.dendrite_output(MotionDirection::Rightward), 0.0);
+        assert_eq!(outward.dendrite_output(MotionDirection::Leftward), 0.0);
+    }
+
+    #[test]
+    fn test_soma_delay_spans_updates_at_high_frame_rates() {
+        // At 120 Hz the soma signal needs four updates to reach the tip
+        let mut cell = StarburstAmacrineCell::new(0, StarburstType::On, 3, 0, 2);
+        cell.set_frame_interval_ms(DEFAULT_FRAME_INTERVAL_MS / 4.0);
+        cell.update(&spot(10, 3));
+        for _ in 0..3 {
+            cell.update(&spot(10, 5));
+            assert_eq!(cell.dendrite_output(MotionDirection::Rightward), 0.0);
+        }
+        cell.update(&spot(10, 5));
+
+        assert!(cell.dendrite_output(MotionDirection::Rightward) > 0.5);
+    }
+
+    #[test]
+    fn test_null_direction_motion_inhibits() {
+        let dark = vec![vec![0.0; 12]; 1];
+
+        // Leftward motion towards x = 4 is the null direction of a rightward cell
+        let mut layer = AmacrineLayer::new(12, 1, 1, 2);
+        layer.process(&spot(12, 6), &dark);
+        layer.process(&spot(12, 4), &dark);
+        let null = layer.inhibition_onto(4, 0, MotionDirection::Rightward, StarburstType::On);
+
+        let mut layer = AmacrineLayer::new(12, 1, 1, 2);
+        layer.process(&spot(12, 2), &dark);
+        layer.process(&spot(12, 4), &dark);
+        let preferred = layer.inhibition_onto(4, 0, MotionDirection::Rightward, StarburstType::On);
+
+        assert!(null > 0.5);
+        assert_eq!(preferred, 0.0);
+        assert_eq!(layer.inhibition_onto(4, 0, MotionDirection::Rightward, StarburstType::Off), 0.0);
+    }
+}
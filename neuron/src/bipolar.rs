@@ -0,0 +1,376 @@
+//! Bipolar cells - The through pathway from cones to ganglion cells
+//!
+//! Cones release glutamate in the dark and less of it in light. Bipolar
+//! cells read this signal with one of two receptor types, which splits
+//! the image into parallel ON and OFF channels:
+//! - ON bipolar cells express mGluR6, a metabotropic receptor that closes
+//!   a cation channel when glutamate binds: they are sign-inverting and
+//!   depolarize to light.
+//! - OFF bipolar cells express ionotropic (AMPA/kainate) receptors: they
+//!   are sign-conserving and depolarize to darkness.
+//!
+//! Each polarity comes in a sustained subtype, which follows its input,
+//! and a transient subtype, which adapts and signals changes.
+
+use crate::cone::Cone;
+use crate::temporal::{relaxation_rate, DEFAULT_FRAME_INTERVAL_MS};
+
+/// Time constant with which a transient cell adapts to its input (ms)
+const TRANSIENT_ADAPTATION_TIME_CONSTANT_MS: f32 = 48.0;
+
+/// Glutamate receptor on the bipolar cell dendrites
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GlutamateReceptor {
+    /// Metabotropic mGluR6 (ON cells): glutamate hyperpolarizes, sign-inverting
+    MGluR6,
+    /// Ionotropic AMPA/kainate (OFF cells): glutamate depolarizes, sign-conserving
+    IGluR,
+}
+
+/// Type of bipolar cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BipolarType {
+    /// ON sustained: depolarizes to light for as long as it lasts
+    OnSustained,
+    /// ON transient: depolarizes briefly at light onset
+    OnTransient,
+    /// OFF sustained: depolarizes to darkness for as long as it lasts
+    OffSustained,
+    /// OFF transient: depolarizes briefly at light offset
+    OffTransient,
+}
+
+impl BipolarType {
+    /// All bipolar cell types
+    pub const ALL: [BipolarType; 4] = [
+        BipolarType::OnSustained,
+        BipolarType::OnTransient,
+        BipolarType::OffSustained,
+        BipolarType::OffTransient,
+    ];
+
+    /// Returns the glutamate receptor of this type
+    pub fn receptor(&self) -> GlutamateReceptor {
+        if self.is_on() {
+            GlutamateReceptor::MGluR6
+        } else {
+            GlutamateReceptor::IGluR
+        }
+    }
+
+    /// Returns whether this is an ON (light-depolarized) type
+    pub fn is_on(&self) -> bool {
+        matches!(self, Self::OnSustained | Self::OnTransient)
+    }
+
+    /// Returns whether this is a transient (change-signalling) type
+    pub fn is_transient(&self) -> bool {
+        matches!(self, Self::OnTransient | Self::OffTransient)
+    }
+}
+
+/// Bipolar cell driven by the glutamate release of its cones
+#[derive(Debug)]
+pub struct BipolarCell {
+    id: usize,
+    cell_type: BipolarType,
+
+    // Anatomical components
+    dendrites: Vec<f32>, // Glutamate received from each connected cone
+
+    // Physiological state
+    drive: f32,    // Receptor-mediated depolarization (0.0 to 1.0)
+    baseline: f32, // Adapted drive (transient cells only)
+    output: f32,   // Signal passed to ganglion cells (0.0 to 1.0)
+    frame_interval_ms: f32, // Time represented by one update
+}
+
+impl BipolarCell {
+    /// Creates a new bipolar cell
+    ///
+    /// # Arguments
+    /// * `id` - Unique identifier
+    /// * `cell_type` - ON or OFF, sustained or transient
+    pub fn new(id: usize, cell_type: BipolarType) -> Self {
+        Self {
+            id,
+            cell_type,
+            dendrites: Vec::new(),
+            drive: 0.0,
+            baseline: 0.0,
+            output: 0.0,
+            frame_interval_ms: DEFAULT_FRAME_INTERVAL_MS,
+        }
+    }
+
+    /// Returns the cell ID
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /// Returns the cell type
+    pub fn cell_type(&self) -> BipolarType {
+        self.cell_type
+    }
+
+    /// Changes the time represented by one call of `integrate` (ms)
+    pub fn set_frame_interval_ms(&mut self, frame_interval_ms: f32) {
+        self.frame_interval_ms = frame_interval_ms.max(f32::EPSILON);
+    }
+
+    /// Receives glutamate from a cone (1.0 = dark release, 0.0 = bright light)
+    pub fn receive_glutamate(&mut self, glutamate: f32) {
+        self.dendrites.push(glutamate.clamp(0.0, 1.0));
+    }
+
+    /// Integrates the received glutamate and updates the output
+    ///
+    /// mGluR6 inverts the sign of the cone signal and iGluRs conserve it.
+    /// Transient cells report the drive minus an adapting baseline, so a
+    /// constant input fades away over about 50 ms, however often the cell
+    /// is updated. Without new input the cell keeps its previous drive.
+    pub fn integrate(&mut self) {
+        if !self.dendrites.is_empty() {
+            let glutamate = self.dendrites.iter().sum::<f32>() / self.dendrites.len() as f32;
+            self.drive = match self.cell_type.receptor() {
+                GlutamateReceptor::MGluR6 => 1.0 - glutamate,
+                GlutamateReceptor::IGluR => glutamate,
+            };
+            self.dendrites.clear();
+        }
+
+        self.output = if self.cell_type.is_transient() {
+            let change = (self.drive - self.baseline).max(0.0);
+            self.baseline += (self.drive - self.baseline)
+                * relaxation_rate(TRANSIENT_ADAPTATION_TIME_CONSTANT_MS, self.frame_interval_ms);
+            change
+        } else {
+            self.drive
+        };
+    }
+
+    /// Returns the receptor-mediated depolarization (0.0 to 1.0)
+    pub fn drive(&self) -> f32 {
+        self.drive
+    }
+
+    /// Returns the signal passed to ganglion cells (0.0 to 1.0)
+    pub fn output(&self) -> f32 {
+        self.output
+    }
+
+    /// Returns the cell to its unstimulated, unadapted state
+    pub fn reset(&mut self) {
+        self.dendrites.clear();
+        self.drive = 0.0;
+        self.baseline = 0.0;
+        self.output = 0.0;
+    }
+}
+
+/// Bipolar cells of the given types under every cone
+///
+/// Cell IDs are assigned per cone: the cells under cone `i` have IDs
+/// `i * types + 0 .. i * types + types - 1`, in the order the types were
+/// given.
+pub struct BipolarLayer {
+    cells: Vec<BipolarCell>,
+    types: Vec<BipolarType>,
+    cone_count: usize,
+}
+
+impl BipolarLayer {
+    /// Creates a new bipolar layer
+    ///
+    /// # Arguments
+    /// * `cone_count` - Number of cones feeding the layer
+    /// * `types` - Bipolar cell types present under each cone
+    pub fn new(cone_count: usize, types: &[BipolarType]) -> Self {
+        let mut cells = Vec::with_capacity(cone_count * types.len());
+        for cone_index in 0..cone_count {
+            for (offset, &cell_type) in types.iter().enumerate() {
+                cells.push(BipolarCell::new(cone_index * types.len() + offset, cell_type));
+            }
+        }
+
+        Self {
+            cells,
+            types: types.to_vec(),
+            cone_count,
+        }
+    }
+
+    /// Returns the ID of the cell of a given type under a cone
+    pub fn cell_id(&self, cone_index: usize, cell_type: BipolarType) -> Option<usize> {
+        let offset = self.types.iter().position(|&t| t == cell_type)?;
+        (cone_index < self.cone_count).then_some(cone_index * self.types.len() + offset)
+    }
+
+    /// Adds this layer's cells to each cone's connection list
+    ///
+    /// # Arguments
+    /// * `cones` - Cones in the same order as the layer's cone indices
+    pub fn connect_cones(&self, cones: &mut [Cone]) {
+        for (cone_index, cone) in cones.iter_mut().enumerate().take(self.cone_count) {
+            for offset in 0..self.types.len() {
+                cone.connect_to_neuron(cone_index * self.types.len() + offset);
+            }
+        }
+    }
+
+    /// Delivers glutamate transmissions to their target cells
+    ///
+    /// # Arguments
+    /// * `transmissions` - (bipolar cell ID, glutamate) pairs, as returned
+    ///   by `Cone::transmit_glutamate`
+    pub fn receive(&mut self, transmissions: &[(usize, f32)]) {
+        for &(id, glutamate) in transmissions {
+            if let Some(cell) = self.cells.get_mut(id) {
+                cell.receive_glutamate(glutamate);
+            }
+        }
+    }
+
+    /// Changes the time represented by one call of `process` (ms)
+    pub fn set_frame_interval_ms(&mut self, frame_interval_ms: f32) {
+        for cell in &mut self.cells {
+            cell.set_frame_interval_ms(frame_interval_ms);
+        }
+    }
+
+    /// Integrates the input of every cell
+    pub fn process(&mut self) {
+        for cell in &mut self.cells {
+            cell.integrate();
+        }
+    }
+
+    /// Returns the output of one cell type under each cone (by cone index)
+    pub fn outputs(&self, cell_type: BipolarType) -> Vec<f32> {
+        (0..self.cone_count)
+            .map(|cone_index| {
+                self.cell_id(cone_index, cell_type)
+                    .map_or(0.0, |id| self.cells[id].output())
+            })
+            .collect()
+    }
+
+    /// Returns the bipolar cell types present in the layer
+    pub fn types(&self) -> &[BipolarType] {
+        &self.types
+    }
+
+    /// Returns all cells
+    pub fn cells(&self) -> &[BipolarCell] {
+        &self.cells
+    }
+
+    /// Returns cells of a specific type
+    pub fn cells_by_type(&self, cell_type: BipolarType) -> Vec<&BipolarCell> {
+        self.cells
+            .iter()
+            .filter(|c| c.cell_type() == cell_type)
+            .collect()
+    }
+
+    /// Returns every cell to its unstimulated, unadapted state
+    pub fn reset(&mut self) {
+        for cell in &mut self.cells {
+            cell.reset();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::photopigment::{ConeType, LightStimulus};
+
+    fn stimulate(cell: &mut BipolarCell, glutamate: f32) -> f32 {
+        cell.receive_glutamate(glutamate);
+        cell.integrate();
+        cell.output()
+    }
+
+    #[test]
+    fn test_receptor_sign() {
+        let mut on = BipolarCell::new(0, BipolarType::OnSustained);
+        let mut off = BipolarCell::new(1, BipolarType::OffSustained);
+        assert_eq!(BipolarType::OnSustained.receptor(), GlutamateReceptor::MGluR6);
+        assert_eq!(BipolarType::OffTransient.receptor(), GlutamateReceptor::IGluR);
+
+        // Light: little glutamate depolarizes ON cells only
+        assert!(stimulate(&mut on, 0.1) > 0.8);
+        assert!(stimulate(&mut off, 0.1) < 0.2);
+
+        // Dark: high glutamate depolarizes OFF cells only
+        assert!(stimulate(&mut on, 0.9) < 0.2);
+        assert!(stimulate(&mut off, 0.9) > 0.8);
+    }
+
+    #[test]
+    fn test_transient_response_fades() {
+        let mut sustained = BipolarCell::new(0, BipolarType::OnSustained);
+        let mut transient = BipolarCell::new(1, BipolarType::OnTransient);
+
+        let onset = stimulate(&mut transient, 0.0);
+        let mut later = onset;
+        for _ in 0..5 {
+            stimulate(&mut sustained, 0.0);
+            later = stimulate(&mut transient, 0.0);
+        }
+
+        assert!(onset > 0.9);
+        assert!(later < onset * 0.1);
+        assert!((sustained.output() - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_transient_decay_time_is_independent_of_frame_interval() {
+        // Output 2 frames after light onset at 30 Hz and 8 frames at 120 Hz
+        let mut slow = BipolarCell::new(0, BipolarType::OnTransient);
+        let mut fast = BipolarCell::new(1, BipolarType::OnTransient);
+        fast.set_frame_interval_ms(DEFAULT_FRAME_INTERVAL_MS / 4.0);
+        for _ in 0..3 {
+            stimulate(&mut slow, 0.0);
+        }
+        for _ in 0..9 {
+            stimulate(&mut fast, 0.0);
+        }
+
+        assert!(slow.output() > 0.1 && slow.output() < 0.5);
+        assert!((slow.output() - fast.output()).abs() < 0.02);
+    }
+
+    #[test]
+    fn test_off_transient_signals_light_offset() {
+        let mut cell = BipolarCell::new(0, BipolarType::OffTransient);
+        for _ in 0..10 {
+            stimulate(&mut cell, 0.0); // steady light
+        }
+        assert!(cell.output() < 0.01);
+
+        assert!(stimulate(&mut cell, 1.0) > 0.9); // light off
+    }
+
+    #[test]
+    fn test_layer_receives_through_cone_connections() {
+        let mut cones = vec![Cone::new(0, ConeType::L), Cone::new(1, ConeType::L)];
+        let mut layer = BipolarLayer::new(2, &[BipolarType::OnSustained, BipolarType::OffSustained]);
+        layer.connect_cones(&mut cones);
+        assert_eq!(cones[1].transmit_glutamate().len(), 2);
+
+        cones[0].phototransduction(LightStimulus::red(100.0));
+        for cone in &cones {
+            layer.receive(&cone.transmit_glutamate());
+        }
+        layer.process();
+
+        let on = layer.outputs(BipolarType::OnSustained);
+        let off = layer.outputs(BipolarType::OffSustained);
+        assert!(on[0] > on[1]);
+        assert!(off[0] < off[1]);
+        assert_eq!(layer.cell_id(1, BipolarType::OffSustained), Some(3));
+        assert_eq!(layer.cell_id(1, BipolarType::OnTransient), None);
+    }
+}
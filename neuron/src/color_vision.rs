@@ -0,0 +1,353 @@
+//! Colour vision deficiencies: configurable cone populations and percept simulation
+
+use crate::image_utils::{linear_rgb_to_lms, linear_to_srgb, srgb_to_linear, LINEAR_RGB_TO_LMS};
+use crate::photopigment::ConeType;
+
+/// Common forms of colour vision deficiency
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColorVisionDeficiency {
+    /// Normal trichromacy
+    Normal,
+    /// Dichromacy without L-cones (red-blind)
+    Protanopia,
+    /// Dichromacy without M-cones (green-blind)
+    Deuteranopia,
+    /// Dichromacy without S-cones (blue-blind)
+    Tritanopia,
+    /// Anomalous trichromacy: L-pigment shifted towards M (peak in nm)
+    Protanomaly { l_peak: f32 },
+    /// Anomalous trichromacy: M-pigment shifted towards L (peak in nm)
+    Deuteranomaly { m_peak: f32 },
+    /// Anomalous trichromacy: S-pigment shifted towards M (peak in nm)
+    Tritanomaly { s_peak: f32 },
+}
+
+/// Composition of the cone mosaic: which classes exist, in what ratio, with which opsins
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConePopulation {
+    /// Relative number of L-cones
+    pub l_ratio: f32,
+    /// Relative number of M-cones
+    pub m_ratio: f32,
+    /// Relative number of S-cones
+    pub s_ratio: f32,
+    /// Peak wavelength of the L-cone opsin (nm)
+    pub l_peak: f32,
+    /// Peak wavelength of the M-cone opsin (nm)
+    pub m_peak: f32,
+    /// Peak wavelength of the S-cone opsin (nm)
+    pub s_peak: f32,
+}
+
+impl ConePopulation {
+    /// Normal trichromat with the retinal abundance of each class (62:33:5)
+    pub fn normal() -> Self {
+        Self {
+            l_ratio: ConeType::L.abundance(),
+            m_ratio: ConeType::M.abundance(),
+            s_ratio: ConeType::S.abundance(),
+            l_peak: ConeType::L.peak_wavelength(),
+            m_peak: ConeType::M.peak_wavelength(),
+            s_peak: ConeType::S.peak_wavelength(),
+        }
+    }
+
+    /// Cone population of an observer with the given deficiency
+    ///
+    /// Dichromats keep the total number of cones: the missing class is
+    /// replaced by the remaining long/medium wavelength class.
+    pub fn from_deficiency(deficiency: ColorVisionDeficiency) -> Self {
+        let normal = Self::normal();
+        match deficiency {
+            ColorVisionDeficiency::Normal => normal,
+            ColorVisionDeficiency::Protanopia => normal.with_ratio(0.0, 95.0, 5.0),
+            ColorVisionDeficiency::Deuteranopia => normal.with_ratio(95.0, 0.0, 5.0),
+            ColorVisionDeficiency::Tritanopia => normal.without(ConeType::S),
+            ColorVisionDeficiency::Protanomaly { l_peak } => normal.with_peak(ConeType::L, l_peak),
+            ColorVisionDeficiency::Deuteranomaly { m_peak } => normal.with_peak(ConeType::M, m_peak),
+            ColorVisionDeficiency::Tritanomaly { s_peak } => normal.with_peak(ConeType::S, s_peak),
+        }
+    }
+
+    /// Returns a copy with a custom L:M:S ratio (0.0 removes a class)
+    pub fn with_ratio(mut self, l_ratio: f32, m_ratio: f32, s_ratio: f32) -> Self {
+        self.l_ratio = l_ratio.max(0.0);
+        self.m_ratio = m_ratio.max(0.0);
+        self.s_ratio = s_ratio.max(0.0);
+        self
+    }
+
+    /// Returns a copy with the opsin of one class shifted to a new peak
+    pub fn with_peak(mut self, cone_type: ConeType, peak: f32) -> Self {
+        match cone_type {
+            ConeType::L => self.l_peak = peak,
+            ConeType::M => self.m_peak = peak,
+            ConeType::S => self.s_peak = peak,
+        }
+        self
+    }
+
+    /// Returns a copy without any cone of the given class
+    pub fn without(self, cone_type: ConeType) -> Self {
+        match cone_type {
+            ConeType::L => self.with_ratio(0.0, self.m_ratio, self.s_ratio),
+            ConeType::M => self.with_ratio(self.l_ratio, 0.0, self.s_ratio),
+            ConeType::S => self.with_ratio(self.l_ratio, self.m_ratio, 0.0),
+        }
+    }
+
+    /// Returns the opsin peak wavelength of a cone class
+    pub fn peak(&self, cone_type: ConeType) -> f32 {
+        match cone_type {
+            ConeType::L => self.l_peak,
+            ConeType::M => self.m_peak,
+            ConeType::S => self.s_peak,
+        }
+    }
+
+    /// Returns the fraction of cones belonging to a class (0.0 to 1.0)
+    pub fn fraction(&self, cone_type: ConeType) -> f32 {
+        let total = self.l_ratio + self.m_ratio + self.s_ratio;
+        if total <= 0.0 {
+            return 0.0;
+        }
+        let ratio = match cone_type {
+            ConeType::L => self.l_ratio,
+            ConeType::M => self.m_ratio,
+            ConeType::S => self.s_ratio,
+        };
+        ratio / total
+    }
+
+    /// Returns whether a cone class is present in the mosaic
+    pub fn has(&self, cone_type: ConeType) -> bool {
+        self.fraction(cone_type) > 0.0
+    }
+
+    /// Returns the linear RGB → excitation weights of a cone class
+    ///
+    /// Shifted opsins are interpolated between the normal L, M and S rows of
+    /// `LINEAR_RGB_TO_LMS` according to their peak wavelength.
+    pub fn rgb_weights(&self, cone_type: ConeType) -> [f32; 3] {
+        excitation_weights(self.peak(cone_type))
+    }
+}
+
+impl Default for ConePopulation {
+    fn default() -> Self {
+        Self::normal()
+    }
+}
+
+/// Returns linear RGB → excitation weights for an opsin with the given peak
+///
+/// Piecewise-linear interpolation between the normal S (420 nm),
+/// M (530 nm) and L (560 nm) rows of the RGB → LMS matrix.
+pub fn excitation_weights(peak: f32) -> [f32; 3] {
+    let [l_row, m_row, s_row] = LINEAR_RGB_TO_LMS;
+    let s_peak = ConeType::S.peak_wavelength();
+    let m_peak = ConeType::M.peak_wavelength();
+    let l_peak = ConeType::L.peak_wavelength();
+
+    let (from, to, t) = if peak <= m_peak {
+        (s_row, m_row, ((peak - s_peak) / (m_peak - s_peak)).clamp(0.0, 1.0))
+    } else {
+        (m_row, l_row, ((peak - m_peak) / (l_peak - m_peak)).clamp(0.0, 1.0))
+    };
+
+    [0, 1, 2].map(|i| from[i] + (to[i] - from[i]) * t)
+}
+
+/// Simulates how an sRGB image appears to an observer with a given cone population
+///
+/// Cone excitations are computed with the observer's (possibly shifted)
+/// opsins. A missing cone class is reconstructed from the remaining two
+/// following Viénot, Brettel & Mollon (1999): the projection preserves
+/// white and one hue that dichromats see normally (blue for protans and
+/// deutans, red for tritans). The result is converted back to sRGB as a
+/// normal observer would see it.
+///
+/// # Arguments
+/// * `image` - 2D array of sRGB-encoded [R, G, B] values (0.0 to 1.0)
+/// * `population` - Cone population of the simulated observer
+pub fn simulate_percept(image: &[Vec<[f32; 3]>], population: &ConePopulation) -> Vec<Vec<[f32; 3]>> {
+    let weights = [
+        population.rgb_weights(ConeType::L),
+        population.rgb_weights(ConeType::M),
+        population.rgb_weights(ConeType::S),
+    ];
+    let lms_to_rgb = invert_matrix(LINEAR_RGB_TO_LMS);
+    let projection = dichromat_projection(population);
+
+    image
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|pixel| {
+                    let linear = pixel.map(srgb_to_linear);
+                    let lms = weights.map(|w| w[0] * linear[0] + w[1] * linear[1] + w[2] * linear[2]);
+                    let lms = projection.map_or(lms, |(missing, a, b)| {
+                        let mut projected = lms;
+                        let (first, second) = other_channels(missing);
+                        projected[missing] = a * lms[first] + b * lms[second];
+                        projected
+                    });
+                    let rgb = multiply(&lms_to_rgb, lms);
+                    rgb.map(linear_to_srgb)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Returns (missing channel, a, b) such that missing = a * first + b * second
+fn dichromat_projection(population: &ConePopulation) -> Option<(usize, f32, f32)> {
+    let white = linear_rgb_to_lms([1.0, 1.0, 1.0]);
+    let blue = linear_rgb_to_lms([0.0, 0.0, 1.0]);
+    let red = linear_rgb_to_lms([1.0, 0.0, 0.0]);
+
+    let (missing, anchor) = if !population.has(ConeType::L) {
+        (0, blue)
+    } else if !population.has(ConeType::M) {
+        (1, blue)
+    } else if !population.has(ConeType::S) {
+        (2, red)
+    } else {
+        return None;
+    };
+
+    // Solve a * first + b * second = missing for white and the anchor hue
+    let (first, second) = other_channels(missing);
+    let det = white[first] * anchor[second] - white[second] * anchor[first];
+    if det.abs() < f32::EPSILON {
+        return None;
+    }
+    let a = (white[missing] * anchor[second] - white[second] * anchor[missing]) / det;
+    let b = (white[first] * anchor[missing] - white[missing] * anchor[first]) / det;
+    Some((missing, a, b))
+}
+
+fn other_channels(channel: usize) -> (usize, usize) {
+    match channel {
+        0 => (1, 2),
+        1 => (0, 2),
+        _ => (0, 1),
+    }
+}
+
+fn multiply(matrix: &[[f32; 3]; 3], vector: [f32; 3]) -> [f32; 3] {
+    matrix.map(|row| row[0] * vector[0] + row[1] * vector[1] + row[2] * vector[2])
+}
+
+fn invert_matrix(m: [[f32; 3]; 3]) -> [[f32; 3]; 3] {
+    let det = m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+        - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+        + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0]);
+    let inv_det = 1.0 / det;
+
+    [
+        [
+            (m[1][1] * m[2][2] - m[1][2] * m[2][1]) * inv_det,
+            (m[0][2] * m[2][1] - m[0][1] * m[2][2]) * inv_det,
+            (m[0][1] * m[1][2] - m[0][2] * m[1][1]) * inv_det,
+        ],
+        [
+            (m[1][2] * m[2][0] - m[1][0] * m[2][2]) * inv_det,
+            (m[0][0] * m[2][2] - m[0][2] * m[2][0]) * inv_det,
+            (m[0][2] * m[1][0] - m[0][0] * m[1][2]) * inv_det,
+        ],
+        [
+            (m[1][0] * m[2][1] - m[1][1] * m[2][0]) * inv_det,
+            (m[0][1] * m[2][0] - m[0][0] * m[2][1]) * inv_det,
+            (m[0][0] * m[1][1] - m[0][1] * m[1][0]) * inv_det,
+        ],
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn distance(a: [f32; 3], b: [f32; 3]) -> f32 {
+        ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)).sqrt()
+    }
+
+    #[test]
+    fn test_normal_population_matches_abundance() {
+        let population = ConePopulation::normal();
+        assert!((population.fraction(ConeType::L) - 0.62).abs() < 1e-6);
+        assert!((population.fraction(ConeType::S) - 0.05).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_dichromat_populations() {
+        let protanope = ConePopulation::from_deficiency(ColorVisionDeficiency::Protanopia);
+        let tritanope = ConePopulation::from_deficiency(ColorVisionDeficiency::Tritanopia);
+
+        assert!(!protanope.has(ConeType::L));
+        assert!(protanope.has(ConeType::M) && protanope.has(ConeType::S));
+        assert!(!tritanope.has(ConeType::S));
+    }
+
+    #[test]
+    fn test_custom_ratio() {
+        let population = ConePopulation::normal().with_ratio(2.0, 1.0, 0.0);
+        assert!((population.fraction(ConeType::L) - 2.0 / 3.0).abs() < 1e-6);
+        assert!(!population.has(ConeType::S));
+    }
+
+    #[test]
+    fn test_anomalous_weights_interpolate() {
+        let normal_l = excitation_weights(ConeType::L.peak_wavelength());
+        let normal_m = excitation_weights(ConeType::M.peak_wavelength());
+        let anomalous_l = excitation_weights(545.0);
+
+        assert_eq!(normal_l, LINEAR_RGB_TO_LMS[0]);
+        // An L-cone at 545 nm sits halfway between normal L and M
+        assert!((anomalous_l[0] - (normal_l[0] + normal_m[0]) / 2.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_normal_percept_is_identity() {
+        let image = vec![vec![[0.8, 0.2, 0.1], [0.1, 0.5, 0.9]]];
+        let percept = simulate_percept(&image, &ConePopulation::normal());
+
+        for (original, simulated) in image[0].iter().zip(&percept[0]) {
+            assert!(distance(*original, *simulated) < 0.01);
+        }
+    }
+
+    #[test]
+    fn test_dichromat_confuses_red_and_green() {
+        let red = [0.8, 0.3, 0.1];
+        let green = [0.45, 0.55, 0.1];
+        let image = vec![vec![red, green, [1.0, 1.0, 1.0]]];
+
+        let normal = simulate_percept(&image, &ConePopulation::normal());
+        for deficiency in [ColorVisionDeficiency::Protanopia, ColorVisionDeficiency::Deuteranopia] {
+            let percept = simulate_percept(&image, &ConePopulation::from_deficiency(deficiency));
+
+            assert!(distance(percept[0][0], percept[0][1]) < distance(normal[0][0], normal[0][1]));
+            // White is preserved by the projection
+            assert!(distance(percept[0][2], [1.0, 1.0, 1.0]) < 0.02);
+        }
+    }
+
+    #[test]
+    fn test_anomalous_trichromat_is_milder_than_dichromat() {
+        let image = vec![vec![[0.8, 0.3, 0.1], [0.45, 0.55, 0.1]]];
+        let deuteranomalous = simulate_percept(
+            &image,
+            &ConePopulation::from_deficiency(ColorVisionDeficiency::Deuteranomaly { m_peak: 545.0 }),
+        );
+        let deuteranope = simulate_percept(
+            &image,
+            &ConePopulation::from_deficiency(ColorVisionDeficiency::Deuteranopia),
+        );
+        let normal = simulate_percept(&image, &ConePopulation::normal());
+
+        let anomalous_gap = distance(deuteranomalous[0][0], deuteranomalous[0][1]);
+        assert!(anomalous_gap < distance(normal[0][0], normal[0][1]));
+        assert!(anomalous_gap > distance(deuteranope[0][0], deuteranope[0][1]));
+    }
+}
@@ -1,6 +1,8 @@
 //! Cone photoreceptor implementation with realistic phototransduction
 
-use crate::photopigment::{ConeType, LightStimulus};
+use crate::photopigment::{ConeType, LightSource, PreReceptoralFilter, SpectralSensitivity};
+use crate::pigment::PigmentKinetics;
+use crate::temporal::{relaxation_rate, DEFAULT_FRAME_INTERVAL_MS};
 
 // Physiological constants for cones
 const DARK_POTENTIAL: f32 = -40.0; // mV - depolarized in darkness
@@ -10,11 +12,27 @@ const LIGHT_GLUTAMATE_RELEASE: f32 = 10.0; // Low glutamate in light
 const CGMP_DARK_LEVEL: f32 = 100.0; // High cGMP in darkness
 const CGMP_LIGHT_LEVEL: f32 = 10.0; // Low cGMP in light
 
+/// Background that halves the cascade gain (the cone's "dark light").
+/// Dimmer backgrounds leave the cone dark adapted; brighter ones lower
+/// the gain in proportion (Weber's law).
+const DARK_LIGHT_INTENSITY: f32 = 10.0;
+
+/// Time constant of the cGMP cascade (ms)
+const CGMP_TIME_CONSTANT_MS: f32 = 93.0;
+
+/// Time constant with which the adapting background follows the light (ms)
+const BACKGROUND_ADAPTATION_TIME_CONSTANT_MS: f32 = 116.0;
+
+/// ATP used per second of phototransduction, and regenerated per second of recovery
+const ATP_CONSUMPTION_PER_S: f32 = 3.0;
+const ATP_RECOVERY_PER_S: f32 = 30.0;
+
 /// Represents a cone photoreceptor cell in the retina
 #[derive(Debug)]
 pub struct Cone {
     id: usize,
     cone_type: ConeType,
+    sensitivity: SpectralSensitivity, // Corneal spectral sensitivity
     
     // Anatomical segments
     outer_segment_pigment: f32, // Photopigment concentration
@@ -26,7 +44,13 @@ pub struct Cone {
     glutamate_release: f32,
     
     // Adaptation state
-    adaptation_level: f32, // Light adaptation (0.0 = dark adapted, 1.0 = light adapted)
+    background_intensity: Option<f32>, // Absorbed intensity the gain is adapted to (None before any light)
+    
+    // Lateral feedback from horizontal cells (mV of depolarization at the synapse)
+    feedback_potential: f32,
+
+    // Time represented by one update (ms)
+    frame_interval_ms: f32,
     
     // Connection to downstream neurons
     connected_neurons: Vec<usize>, // Bipolar cell IDs
@@ -39,19 +63,53 @@ impl Cone {
     /// * `id` - Unique identifier for this cone
     /// * `cone_type` - Type of cone (S, M, or L)
     pub fn new(id: usize, cone_type: ConeType) -> Self {
+        Self::with_prefilter(id, cone_type, PreReceptoralFilter::standard())
+    }
+
+    /// Creates a new cone seen through a specific lens and macular pigment
+    ///
+    /// # Arguments
+    /// * `id` - Unique identifier for this cone
+    /// * `cone_type` - Type of cone (S, M, or L)
+    /// * `filter` - Pre-receptoral filtering (`PreReceptoralFilter::none()` for bare pigment)
+    pub fn with_prefilter(id: usize, cone_type: ConeType, filter: PreReceptoralFilter) -> Self {
+        Self::with_sensitivity(id, cone_type, cone_type.fundamental(filter))
+    }
+
+    /// Creates a new cone with a precomputed spectral sensitivity
+    ///
+    /// Useful when building large mosaics, so the sensitivity curve is
+    /// computed once per cone class rather than once per cone.
+    pub fn with_sensitivity(id: usize, cone_type: ConeType, sensitivity: SpectralSensitivity) -> Self {
         Self {
             id,
             cone_type,
+            sensitivity,
             outer_segment_pigment: 100.0,
             inner_segment_atp: 100.0,
             membrane_potential: DARK_POTENTIAL,
             cgmp_level: CGMP_DARK_LEVEL,
             glutamate_release: DARK_GLUTAMATE_RELEASE,
-            adaptation_level: 0.0,
+            background_intensity: None,
+            feedback_potential: 0.0,
+            frame_interval_ms: DEFAULT_FRAME_INTERVAL_MS,
             connected_neurons: Vec::new(),
         }
     }
 
+    /// Returns the time represented by one phototransduction update (ms)
+    pub fn frame_interval_ms(&self) -> f32 {
+        self.frame_interval_ms
+    }
+
+    /// Changes the time represented by one phototransduction update (ms)
+    ///
+    /// The cGMP cascade, light adaptation and pigment bleaching all
+    /// advance by this much per call of `transduce_on_background`.
+    pub fn set_frame_interval_ms(&mut self, frame_interval_ms: f32) {
+        self.frame_interval_ms = frame_interval_ms.max(f32::EPSILON);
+    }
+
     /// Returns the cone's ID
     pub fn id(&self) -> usize {
         self.id
@@ -62,6 +120,16 @@ impl Cone {
         self.cone_type
     }
 
+    /// Returns the peak wavelength of the cone's opsin (nm)
+    pub fn peak_wavelength(&self) -> f32 {
+        self.sensitivity.lambda_max()
+    }
+
+    /// Returns the cone's sensitivity at a given wavelength (1.0 at its peak)
+    pub fn spectral_sensitivity(&self, wavelength: f32) -> f32 {
+        self.sensitivity.at(wavelength)
+    }
+
     /// Returns the current membrane potential
     pub fn membrane_potential(&self) -> f32 {
         self.membrane_potential
@@ -96,21 +164,61 @@ impl Cone {
     /// 7. Less glutamate released
     ///
     /// # Arguments
-    /// * `light` - The light stimulus
-    pub fn phototransduction(&mut self, light: LightStimulus) {
+    /// * `light` - The light stimulus (single wavelength or full spectrum)
+    pub fn phototransduction<L: LightSource>(&mut self, light: L) {
         // Calculate effective light intensity based on spectral sensitivity
-        let sensitivity = self.cone_type.spectral_sensitivity(light.wavelength);
-        let effective_intensity = light.intensity * sensitivity;
-        
-        // Apply adaptation: cones adapt to ambient light levels
-        let adapted_intensity = effective_intensity * (1.0 - self.adaptation_level * 0.7);
+        let sensitivity = self.sensitivity;
+        let effective_intensity = light.absorbed_intensity(|wavelength| sensitivity.at(wavelength));
+
+        self.transduce(effective_intensity);
+    }
+
+    /// Runs the phototransduction cascade from an already-computed excitation
+    ///
+    /// Used when the light absorbed by this cone class is known directly,
+    /// e.g. LMS cone excitations computed from a colour image.
+    ///
+    /// # Arguments
+    /// * `effective_intensity` - Light absorbed by the photopigment
+    pub fn transduce(&mut self, effective_intensity: f32) {
+        self.transduce_on_background(effective_intensity, effective_intensity);
+    }
+
+    /// Runs the phototransduction cascade while adapting to a given background
+    ///
+    /// Light adaptation is divisive (Weber's law): the cascade gain is
+    /// inversely proportional to the background the cone is adapted to,
+    /// so a given contrast produces the same response at any mean
+    /// luminance. The background is tracked gradually; `transduce` adapts
+    /// to the cone's own input, while a retina can pass a pooled mean.
+    ///
+    /// A cone that has not seen light before starts in steady state with
+    /// the first call (adapted to its background, cGMP settled), so a
+    /// single image gives the same response at any mean luminance.
+    ///
+    /// # Arguments
+    /// * `effective_intensity` - Light absorbed by the photopigment
+    /// * `background_intensity` - Light level to adapt to (same units)
+    pub fn transduce_on_background(&mut self, effective_intensity: f32, background_intensity: f32) {
+        // Only unbleached pigment catches photons
+        let absorbed_intensity = effective_intensity * self.pigment_fraction();
+        let absorbed_background = background_intensity.max(0.0) * self.pigment_fraction();
+        let first_frame = self.background_intensity.is_none();
+        let adapted_background = *self.background_intensity.get_or_insert(absorbed_background);
+
+        // Apply adaptation: divisive gain set by the adapting background
+        let adapted_intensity = absorbed_intensity * self.weber_gain();
         
         // Phototransduction cascade
         // More light → less cGMP
         let target_cgmp = CGMP_DARK_LEVEL - (adapted_intensity / 10.0).clamp(0.0, 90.0);
         
         // cGMP changes gradually (not instantaneous)
-        let cgmp_change_rate = 0.3;
+        let cgmp_change_rate = if first_frame {
+            1.0
+        } else {
+            relaxation_rate(CGMP_TIME_CONSTANT_MS, self.frame_interval_ms)
+        };
         self.cgmp_level += (target_cgmp - self.cgmp_level) * cgmp_change_rate;
         self.cgmp_level = self.cgmp_level.clamp(CGMP_LIGHT_LEVEL, CGMP_DARK_LEVEL);
         
@@ -119,26 +227,116 @@ impl Cone {
         self.membrane_potential = LIGHT_POTENTIAL + (DARK_POTENTIAL - LIGHT_POTENTIAL) * channel_opening;
         
         // Glutamate release is proportional to depolarization
-        let depolarization_factor = (self.membrane_potential - LIGHT_POTENTIAL) / (DARK_POTENTIAL - LIGHT_POTENTIAL);
-        self.glutamate_release = LIGHT_GLUTAMATE_RELEASE 
-            + (DARK_GLUTAMATE_RELEASE - LIGHT_GLUTAMATE_RELEASE) * depolarization_factor;
+        self.update_glutamate_release();
         
-        // Light adaptation: gradually adapt to sustained light
-        let adaptation_rate = 0.01;
-        let target_adaptation = (effective_intensity / 100.0).clamp(0.0, 1.0);
-        self.adaptation_level += (target_adaptation - self.adaptation_level) * adaptation_rate;
+        // Weber adaptation: the background tracks the (absorbed) adapting light
+        self.background_intensity = Some(
+            adapted_background
+                + (absorbed_background - adapted_background)
+                    * relaxation_rate(BACKGROUND_ADAPTATION_TIME_CONSTANT_MS, self.frame_interval_ms),
+        );
         
+        // Absorbed photons bleach pigment while regeneration continues
+        let step_s = self.frame_interval_ms / 1000.0;
+        self.advance_pigment(effective_intensity, step_s);
+
         // Energy consumption (ATP usage)
-        self.inner_segment_atp = (self.inner_segment_atp - 0.1).max(20.0);
+        self.inner_segment_atp = (self.inner_segment_atp - ATP_CONSUMPTION_PER_S * step_s).max(20.0);
+    }
+
+    /// Applies lateral feedback from horizontal cells
+    ///
+    /// Light in the surround hyperpolarizes horizontal cells, which shifts
+    /// the calcium current at the cone terminal and raises glutamate
+    /// release. The feedback therefore opposes the cone's own light
+    /// response, forming the antagonistic surround.
+    ///
+    /// # Arguments
+    /// * `feedback` - Horizontal cell signal as a fraction of the cone's
+    ///   full voltage range (0.0 = no feedback)
+    pub fn apply_horizontal_feedback(&mut self, feedback: f32) {
+        self.feedback_potential = feedback.max(0.0) * (DARK_POTENTIAL - LIGHT_POTENTIAL);
+        self.update_glutamate_release();
+    }
+
+    /// Returns the current horizontal cell feedback (mV of depolarization)
+    pub fn feedback_potential(&self) -> f32 {
+        self.feedback_potential
+    }
+
+    /// Returns how far light has hyperpolarized the cone (0.0 = dark, 1.0 = fully)
+    ///
+    /// Unlike `response_level` this is not amplified, so it stays graded
+    /// at daylight intensities; horizontal cells pool this signal.
+    pub fn hyperpolarization(&self) -> f32 {
+        (DARK_POTENTIAL - self.membrane_potential) / (DARK_POTENTIAL - LIGHT_POTENTIAL)
+    }
+
+    /// Returns the signal passed to bipolar cells (0.0 = dark, 1.0 = bright light)
+    ///
+    /// Derived from glutamate release, so it includes horizontal cell
+    /// feedback. Amplified like `response_level`, which it equals when
+    /// there is no feedback.
+    pub fn synaptic_output(&self) -> f32 {
+        let base_response = (DARK_GLUTAMATE_RELEASE - self.glutamate_release)
+            / (DARK_GLUTAMATE_RELEASE - LIGHT_GLUTAMATE_RELEASE);
+        (base_response * 50.0).clamp(0.0, 1.0)
+    }
+
+    /// Glutamate release follows the terminal potential (soma plus feedback)
+    fn update_glutamate_release(&mut self) {
+        let terminal_potential = (self.membrane_potential + self.feedback_potential).min(DARK_POTENTIAL);
+        let depolarization_factor = (terminal_potential - LIGHT_POTENTIAL) / (DARK_POTENTIAL - LIGHT_POTENTIAL);
+        self.glutamate_release = LIGHT_GLUTAMATE_RELEASE 
+            + (DARK_GLUTAMATE_RELEASE - LIGHT_GLUTAMATE_RELEASE) * depolarization_factor;
     }
 
-    /// Regenerates photopigment and ATP (recovery in darkness)
+    /// Regenerates photopigment and ATP (one frame interval of recovery in darkness)
     pub fn metabolic_recovery(&mut self) {
         // Regenerate photopigment
-        self.outer_segment_pigment = (self.outer_segment_pigment + 0.5).min(100.0);
+        let step_s = self.frame_interval_ms / 1000.0;
+        self.advance_pigment(0.0, step_s);
         
         // Regenerate ATP
-        self.inner_segment_atp = (self.inner_segment_atp + 1.0).min(100.0);
+        self.inner_segment_atp = (self.inner_segment_atp + ATP_RECOVERY_PER_S * step_s).min(100.0);
+    }
+
+    /// Returns the fraction of unbleached photopigment (1.0 = fully dark adapted)
+    ///
+    /// Sensitivity scales with this fraction: bleached pigment catches no photons.
+    pub fn pigment_fraction(&self) -> f32 {
+        self.outer_segment_pigment / 100.0
+    }
+
+    /// Exposes the cone to a steady light, bleaching its pigment
+    ///
+    /// Only the pigment is updated, so long bleaches can be applied in
+    /// one call.
+    ///
+    /// # Arguments
+    /// * `effective_intensity` - Light absorbed by the photopigment
+    /// * `duration_s` - Exposure time (s)
+    pub fn bleach(&mut self, effective_intensity: f32, duration_s: f32) {
+        self.advance_pigment(effective_intensity, duration_s);
+    }
+
+    /// Regenerates pigment during a period of darkness
+    ///
+    /// # Arguments
+    /// * `duration_s` - Time in darkness (s)
+    pub fn dark_adapt(&mut self, duration_s: f32) {
+        self.advance_pigment(0.0, duration_s);
+    }
+
+    /// Returns the intensity needed to detect a flash at the current pigment level
+    pub fn detection_threshold(&self) -> f32 {
+        PigmentKinetics::cone().threshold(self.pigment_fraction())
+    }
+
+    /// Updates the pigment level under a constant intensity
+    fn advance_pigment(&mut self, intensity: f32, duration_s: f32) {
+        let fraction = PigmentKinetics::cone().advance(self.pigment_fraction(), intensity, duration_s);
+        self.outer_segment_pigment = fraction * 100.0;
     }
 
     /// Returns signals to be transmitted to connected neurons
@@ -159,14 +357,41 @@ impl Cone {
             .collect()
     }
 
-    /// Returns the adaptation level
+    /// Returns the glutamate sent to each connected bipolar cell
+    ///
+    /// # Returns
+    /// Vector of (neuron_id, glutamate) tuples, with glutamate normalized
+    /// to the cone's synaptic range (1.0 = dark release, 0.0 = bright light)
+    pub fn transmit_glutamate(&self) -> Vec<(usize, f32)> {
+        let glutamate = 1.0 - self.synaptic_output();
+        self.connected_neurons
+            .iter()
+            .map(|&neuron_id| (neuron_id, glutamate))
+            .collect()
+    }
+
+    /// Returns the adaptation level (0.0 = dark adapted, 1.0 = light adapted)
+    ///
+    /// This is the fraction of the dark-adapted gain that Weber
+    /// adaptation has removed: 0.5 once the background reaches the
+    /// intensity at which adaptation sets in.
     pub fn adaptation_level(&self) -> f32 {
-        self.adaptation_level
+        1.0 - self.weber_gain()
+    }
+
+    /// Returns the absorbed intensity the cone is currently adapted to
+    pub fn background_intensity(&self) -> f32 {
+        self.background_intensity.unwrap_or(0.0)
+    }
+
+    /// Returns the current cascade gain (1.0 when dark adapted)
+    pub fn weber_gain(&self) -> f32 {
+        1.0 / (1.0 + self.background_intensity() / DARK_LIGHT_INTENSITY)
     }
 
     /// Returns whether the cone is in a light-adapted state
     pub fn is_light_adapted(&self) -> bool {
-        self.adaptation_level > 0.5
+        self.adaptation_level() > 0.5
     }
 
     /// Returns the cone's response level (0.0 = dark, 1.0 = bright light)
@@ -186,6 +411,8 @@ impl Cone {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::photopigment::LightStimulus;
+    use crate::spectrum::SpectralPowerDistribution;
 
     #[test]
     fn test_cone_creation() {
@@ -235,6 +462,20 @@ mod tests {
         assert!(s_cone.response_level() > l_cone.response_level());
     }
 
+    #[test]
+    fn test_broadband_light() {
+        let mut s_cone = Cone::new(0, ConeType::S);
+        let mut s_cone_narrow = Cone::new(1, ConeType::S);
+
+        // Daylight contains short wavelengths that a 555 nm stand-in lacks
+        for _ in 0..10 {
+            s_cone.phototransduction(SpectralPowerDistribution::d65(100.0));
+            s_cone_narrow.phototransduction(LightStimulus::white_light(100.0));
+        }
+
+        assert!(s_cone.response_level() > s_cone_narrow.response_level());
+    }
+
     #[test]
     fn test_light_adaptation() {
         let mut cone = Cone::new(0, ConeType::M);
@@ -248,6 +489,20 @@ mod tests {
         assert!(cone.adaptation_level() > 0.5);
     }
 
+    #[test]
+    fn test_horizontal_feedback_raises_glutamate() {
+        let mut cone = Cone::new(0, ConeType::L);
+        for _ in 0..20 {
+            cone.phototransduction(LightStimulus::new(560.0, 20.0));
+        }
+        let glutamate = cone.glutamate_release();
+        assert!((cone.synaptic_output() - cone.response_level()).abs() < 1e-5);
+
+        cone.apply_horizontal_feedback(cone.hyperpolarization() * 0.8);
+        assert!(cone.glutamate_release() > glutamate);
+        assert!(cone.synaptic_output() < cone.response_level());
+    }
+
     #[test]
     fn test_neuron_connection() {
         let mut cone = Cone::new(0, ConeType::S);
@@ -276,4 +531,61 @@ mod tests {
         
         assert!(cone.energy_level() > depleted_atp);
     }
+
+    #[test]
+    fn test_bleached_cone_is_less_sensitive() {
+        let mut fresh = Cone::new(0, ConeType::L);
+        let mut bleached = Cone::new(1, ConeType::L);
+        bleached.bleach(50_000.0, 60.0);
+        assert!(bleached.pigment_fraction() < 0.1);
+        assert!(bleached.detection_threshold() > 100.0 * fresh.detection_threshold());
+
+        for _ in 0..20 {
+            fresh.transduce(200.0);
+            bleached.transduce(200.0);
+        }
+        assert!(bleached.response_level() < fresh.response_level());
+
+        // Pigment regenerates in darkness
+        let before = bleached.pigment_fraction();
+        bleached.dark_adapt(120.0);
+        assert!(bleached.pigment_fraction() > before + 0.5);
+    }
+
+    #[test]
+    fn test_weber_adaptation() {
+        // The same 50% increment after adapting to backgrounds 100x apart
+        let increment_response = |background: f32| {
+            let mut cone = Cone::new(0, ConeType::M);
+            for _ in 0..100 {
+                cone.transduce(background);
+            }
+            let adapted = cone.hyperpolarization();
+            for _ in 0..20 {
+                cone.transduce_on_background(background * 1.5, background);
+            }
+            (adapted, cone.hyperpolarization() - adapted)
+        };
+
+        let (dim_level, dim) = increment_response(100.0);
+        let (bright_level, bright) = increment_response(10_000.0);
+        assert!((dim_level - bright_level).abs() < 0.01);
+        assert!(dim > 0.0 && (bright / dim - 1.0).abs() < 0.1);
+        assert!(Cone::new(1, ConeType::L).weber_gain() == 1.0);
+    }
+
+    #[test]
+    fn test_first_frame_sets_the_adapting_background() {
+        // A single frame is already seen at the gain of its own background
+        let first_response = |background: f32| {
+            let mut cone = Cone::new(0, ConeType::M);
+            cone.transduce_on_background(background * 1.5, background);
+            (cone.hyperpolarization(), cone.adaptation_level())
+        };
+
+        let (dim, dim_adaptation) = first_response(100.0);
+        let (bright, bright_adaptation) = first_response(10_000.0);
+        assert!(dim > 0.0 && (bright / dim - 1.0).abs() < 0.1);
+        assert!(dim_adaptation > 0.5 && bright_adaptation > dim_adaptation);
+    }
 }
@@ -0,0 +1,184 @@
+//! Separable Gaussian convolution for receptive field computations
+//!
+//! A 2D Gaussian is the product of two 1D Gaussians, so blurring an image
+//! costs two 1D passes instead of one 2D pass. Near the border only the
+//! pixels inside the image are weighted and the result is renormalized,
+//! so a uniform image stays uniform right up to its edges.
+
+/// Number of standard deviations covered by a kernel on each side
+const KERNEL_EXTENT: f32 = 3.0;
+
+/// A sampled, normalized 1D Gaussian
+#[derive(Debug, Clone, PartialEq)]
+pub struct GaussianKernel {
+    sigma: f32,
+    weights: Vec<f32>,
+}
+
+impl GaussianKernel {
+    /// Creates a new kernel covering ±3σ
+    ///
+    /// # Arguments
+    /// * `sigma` - Standard deviation (pixels); values near zero give a
+    ///   single-tap identity kernel
+    pub fn new(sigma: f32) -> Self {
+        let sigma = sigma.max(0.0);
+        let radius = (KERNEL_EXTENT * sigma).ceil() as i32;
+        let mut weights: Vec<f32> = (-radius..=radius)
+            .map(|offset| {
+                if sigma > f32::EPSILON {
+                    (-(offset * offset) as f32 / (2.0 * sigma * sigma)).exp()
+                } else {
+                    1.0
+                }
+            })
+            .collect();
+        let total: f32 = weights.iter().sum();
+        for weight in &mut weights {
+            *weight /= total;
+        }
+
+        Self { sigma, weights }
+    }
+
+    /// Returns the standard deviation (pixels)
+    pub fn sigma(&self) -> f32 {
+        self.sigma
+    }
+
+    /// Returns the number of taps on each side of the center
+    pub fn radius(&self) -> usize {
+        self.weights.len() / 2
+    }
+
+    /// Returns the weight at an offset from the center (0.0 outside the kernel)
+    pub fn weight(&self, offset: i32) -> f32 {
+        let index = offset + self.radius() as i32;
+        if index < 0 {
+            return 0.0;
+        }
+        self.weights.get(index as usize).copied().unwrap_or(0.0)
+    }
+
+    /// Returns the weighted mean of one line of values around `center`
+    fn apply(&self, length: usize, center: usize, value_at: impl Fn(usize) -> f32) -> f32 {
+        let radius = self.radius();
+        let start = center.saturating_sub(radius);
+        let end = (center + radius + 1).min(length);
+
+        let mut sum = 0.0;
+        let mut weight_total = 0.0;
+        for position in start..end {
+            let weight = self.weights[position + radius - center];
+            sum += weight * value_at(position);
+            weight_total += weight;
+        }
+
+        if weight_total > 0.0 {
+            sum / weight_total
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Blurs a whole image with a Gaussian (two 1D passes)
+///
+/// # Arguments
+/// * `image` - 2D array of values
+/// * `kernel` - Gaussian applied along both axes
+pub fn gaussian_blur(image: &[Vec<f32>], kernel: &GaussianKernel) -> Vec<Vec<f32>> {
+    let height = image.len();
+    let width = if height > 0 { image[0].len() } else { 0 };
+
+    let horizontal: Vec<Vec<f32>> = image
+        .iter()
+        .map(|row| (0..width).map(|x| kernel.apply(width, x, |px| row[px])).collect())
+        .collect();
+
+    (0..height)
+        .map(|y| {
+            (0..width)
+                .map(|x| kernel.apply(height, y, |py| horizontal[py][x]))
+                .collect()
+        })
+        .collect()
+}
+
+/// Evaluates a Gaussian blur only at the given points
+///
+/// The horizontal pass runs on every row but only at the columns that
+/// contain points; the vertical pass runs only at the points. For
+/// receptive fields sampled on a sparse grid this is much cheaper than
+/// blurring the whole image.
+///
+/// # Arguments
+/// * `image` - 2D array of values
+/// * `kernel` - Gaussian applied along both axes
+/// * `points` - (x, y) positions to evaluate; points outside the image give 0.0
+pub fn gaussian_blur_at(image: &[Vec<f32>], kernel: &GaussianKernel, points: &[(usize, usize)]) -> Vec<f32> {
+    let height = image.len();
+    let width = if height > 0 { image[0].len() } else { 0 };
+
+    let mut columns: Vec<usize> = points.iter().map(|&(x, _)| x).filter(|&x| x < width).collect();
+    columns.sort_unstable();
+    columns.dedup();
+
+    // horizontal[y][i] is the row-blurred value at (columns[i], y)
+    let horizontal: Vec<Vec<f32>> = image
+        .iter()
+        .map(|row| columns.iter().map(|&x| kernel.apply(width, x, |px| row[px])).collect())
+        .collect();
+
+    points
+        .iter()
+        .map(|&(x, y)| {
+            if x >= width || y >= height {
+                return 0.0;
+            }
+            let column = columns.binary_search(&x).unwrap_or(0);
+            kernel.apply(height, y, |py| horizontal[py][column])
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kernel_is_normalized() {
+        let kernel = GaussianKernel::new(2.0);
+        let total: f32 = (-10..=10).map(|offset| kernel.weight(offset)).sum();
+
+        assert_eq!(kernel.radius(), 6);
+        assert!((total - 1.0).abs() < 1e-5);
+        assert!(kernel.weight(0) > kernel.weight(2));
+        assert_eq!(kernel.weight(7), 0.0);
+        assert_eq!(GaussianKernel::new(0.0).radius(), 0);
+    }
+
+    #[test]
+    fn test_uniform_image_stays_uniform() {
+        let image = vec![vec![0.7; 12]; 9];
+        let blurred = gaussian_blur(&image, &GaussianKernel::new(1.5));
+
+        assert!(blurred.iter().flatten().all(|&v| (v - 0.7).abs() < 1e-5));
+    }
+
+    #[test]
+    fn test_sampled_blur_matches_full_blur() {
+        let image: Vec<Vec<f32>> = (0..20)
+            .map(|y| (0..17).map(|x| ((x * 7 + y * 3) % 5) as f32).collect())
+            .collect();
+        let kernel = GaussianKernel::new(1.2);
+        let full = gaussian_blur(&image, &kernel);
+        let points = [(0, 0), (4, 8), (16, 19), (4, 3), (30, 2)];
+        let sampled = gaussian_blur_at(&image, &kernel, &points);
+
+        for (&(x, y), value) in points.iter().zip(&sampled).take(4) {
+            assert!((full[y][x] - value).abs() < 1e-5);
+        }
+        assert_eq!(sampled[4], 0.0);
+    }
+}
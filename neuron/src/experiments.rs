@@ -0,0 +1,532 @@
+//! Experiments - Adaptation protocols and perceptual aftereffects
+//!
+//! Sustained stimulation leaves the visual pathway adapted: cones scale
+//! their gain to the mean light level of their class, ganglion cells to
+//! the local contrast and V1 neurons fatigue with their own activity. An
+//! adaptation protocol shows a pathway an adapting stimulus for a number
+//! of frames, then switches to a test stimulus and records the responses.
+//! The same test is shown to a control pathway that spent as long looking
+//! at a uniform field of the test's mean colour, so any difference between
+//! the two is an aftereffect of the adapter.
+//!
+//! Presets reproduce three classic aftereffects:
+//! - Colour afterimages: after staring at a coloured field, a grey field
+//!   looks tinted in the complementary colour
+//! - The tilt aftereffect: after viewing a tilted grating, V1 reads a test
+//!   grating as tilted further away from the adapter
+//! - Contrast adaptation: after viewing a high-contrast grating, a test
+//!   grating evokes a weaker response
+//!
+//! Gratings used as adapters drift, so every location sees the same mean
+//! light and the adapter leaves no lasting afterimage of its own stripes.
+
+use crate::image_utils::visualize_signed_maps;
+use crate::v1_cortex::{Orientation, OrientationEstimate};
+use crate::visual_pathway::{test_patterns, VisualPathway, VisualResponse};
+
+/// Grating period used by the presets (pixels)
+const PRESET_GRATING_PERIOD: f32 = 8.0;
+
+/// Phase step between frames of a drifting adapter (pixels)
+const DRIFT_STEP: f32 = 2.0;
+
+/// Test frames recorded by the colour afterimage preset; the afterimage
+/// builds up over the first few frames of the neutral field
+const AFTERIMAGE_TEST_FRAMES: usize = 10;
+
+/// Test frames recorded by the tilt aftereffect preset; the last adapter
+/// frames leave a brief afterimage of their stripes on the first one
+const TILT_TEST_FRAMES: usize = 5;
+
+/// An image shown to the pathway for one frame
+#[derive(Debug, Clone, PartialEq)]
+pub enum Stimulus {
+    /// Intensity image (0.0 = black, 1.0 = white) under daylight
+    Grayscale(Vec<Vec<f32>>),
+    /// sRGB image ([R, G, B] from 0.0 to 1.0)
+    Color(Vec<Vec<[f32; 3]>>),
+}
+
+impl Stimulus {
+    /// Returns the (width, height) of the image
+    pub fn dimensions(&self) -> (usize, usize) {
+        match self {
+            Stimulus::Grayscale(image) => (image.first().map_or(0, Vec::len), image.len()),
+            Stimulus::Color(image) => (image.first().map_or(0, Vec::len), image.len()),
+        }
+    }
+
+    /// Returns a uniform field of the same size at the image's mean value
+    pub fn mean_field(&self) -> Stimulus {
+        let (width, height) = self.dimensions();
+        let pixels = (width * height).max(1) as f32;
+        match self {
+            Stimulus::Grayscale(image) => {
+                let mean = image.iter().flatten().sum::<f32>() / pixels;
+                Stimulus::Grayscale(vec![vec![mean; width]; height])
+            }
+            Stimulus::Color(image) => {
+                let mut mean = [0.0; 3];
+                for pixel in image.iter().flatten() {
+                    for (channel, &value) in mean.iter_mut().zip(pixel) {
+                        *channel += value / pixels;
+                    }
+                }
+                Stimulus::Color(vec![vec![mean; width]; height])
+            }
+        }
+    }
+
+    /// Processes the image as the next frame of a pathway
+    fn present(&self, pathway: &mut VisualPathway) -> VisualResponse {
+        match self {
+            Stimulus::Grayscale(image) => pathway.process_grayscale_image(image),
+            Stimulus::Color(image) => pathway.process_color_image(image),
+        }
+    }
+}
+
+/// The recorded response to one frame of a protocol
+#[derive(Debug)]
+pub struct FrameRecord {
+    /// Response of the whole pathway
+    pub response: VisualResponse,
+    /// Summed V1 activation for each preferred orientation
+    pub orientation_tuning: Vec<(Orientation, f32)>,
+}
+
+impl FrameRecord {
+    /// Returns the orientation read out from V1 by a population vector
+    ///
+    /// Each orientation channel votes with its activation on a doubled-angle
+    /// circle, so 0° and 180° count as the same orientation.
+    ///
+    /// # Returns
+    /// Orientation in degrees (0 to 180), or `None` if V1 is silent
+    pub fn decoded_orientation(&self) -> Option<f32> {
+        OrientationEstimate::from_tuning(&self.orientation_tuning).map(|estimate| estimate.orientation.degrees())
+    }
+}
+
+/// An adapt-then-test protocol
+#[derive(Debug, Clone)]
+pub struct AdaptationProtocol {
+    adapter: Vec<Stimulus>,
+    adapt_frames: usize,
+    test: Stimulus,
+    test_frames: usize,
+}
+
+impl AdaptationProtocol {
+    /// Creates a new protocol with one test frame
+    ///
+    /// # Arguments
+    /// * `adapter` - Frames of the adapting stimulus, repeated in order
+    ///   (a single frame for a static adapter)
+    /// * `adapt_frames` - Number of frames of adaptation
+    /// * `test` - Test stimulus shown after adaptation
+    pub fn new(adapter: Vec<Stimulus>, adapt_frames: usize, test: Stimulus) -> Self {
+        Self {
+            adapter,
+            adapt_frames,
+            test,
+            test_frames: 1,
+        }
+    }
+
+    /// Returns a copy that shows the test for several frames
+    pub fn with_test_frames(mut self, test_frames: usize) -> Self {
+        self.test_frames = test_frames.max(1);
+        self
+    }
+
+    /// Returns the number of adaptation frames
+    pub fn adapt_frames(&self) -> usize {
+        self.adapt_frames
+    }
+
+    /// Returns the number of test frames
+    pub fn test_frames(&self) -> usize {
+        self.test_frames
+    }
+
+    /// Runs the protocol on new pathways sized to the stimuli
+    pub fn run(&self) -> Result<AdaptationResult, String> {
+        let (width, height) = self.test.dimensions();
+        self.run_with(|| VisualPathway::new(width, height))
+    }
+
+    /// Runs the protocol on pathways made by a constructor
+    ///
+    /// The constructor is called twice: once for the adapted pathway and
+    /// once for the control. Both have V1 adaptation enabled (see
+    /// `VisualPathway::with_v1_adaptation`), so V1 carries the adapter's
+    /// aftereffect into the test.
+    ///
+    /// # Arguments
+    /// * `new_pathway` - Creates a fresh, unadapted pathway
+    ///
+    /// # Returns
+    /// The recorded responses, or an error if the stimuli are empty or
+    /// differ in size
+    pub fn run_with<F: FnMut() -> VisualPathway>(&self, mut new_pathway: F) -> Result<AdaptationResult, String> {
+        let dimensions = self.test.dimensions();
+        if dimensions.0 == 0 || dimensions.1 == 0 {
+            return Err("Empty test stimulus".to_string());
+        }
+        if self.adapter.is_empty() {
+            return Err("No adapter frames".to_string());
+        }
+        if let Some(frame) = self.adapter.iter().find(|frame| frame.dimensions() != dimensions) {
+            return Err(format!(
+                "Adapter is {:?} but the test is {:?}",
+                frame.dimensions(),
+                dimensions
+            ));
+        }
+
+        let mut pathway = new_pathway().with_v1_adaptation(true);
+        let adaptation = (0..self.adapt_frames)
+            .map(|frame| record(&self.adapter[frame % self.adapter.len()], &mut pathway))
+            .collect();
+        let test = (0..self.test_frames).map(|_| record(&self.test, &mut pathway)).collect();
+
+        let mut control_pathway = new_pathway().with_v1_adaptation(true);
+        let neutral = self.test.mean_field();
+        for _ in 0..self.adapt_frames {
+            neutral.present(&mut control_pathway);
+        }
+        let control = (0..self.test_frames)
+            .map(|_| record(&self.test, &mut control_pathway))
+            .collect();
+
+        Ok(AdaptationResult {
+            adaptation,
+            test,
+            control,
+        })
+    }
+}
+
+/// Presents one frame and records the pathway's response
+fn record(stimulus: &Stimulus, pathway: &mut VisualPathway) -> FrameRecord {
+    let response = stimulus.present(pathway);
+    FrameRecord {
+        response,
+        orientation_tuning: pathway.v1_cortex().orientation_tuning(),
+    }
+}
+
+/// Responses recorded by an adaptation protocol
+#[derive(Debug)]
+pub struct AdaptationResult {
+    /// Responses to the adapter, frame by frame
+    pub adaptation: Vec<FrameRecord>,
+    /// Responses to the test after adaptation
+    pub test: Vec<FrameRecord>,
+    /// Responses to the test after viewing the test's mean field instead
+    pub control: Vec<FrameRecord>,
+}
+
+impl AdaptationResult {
+    /// Returns the aftereffect on a measure of the response
+    ///
+    /// # Arguments
+    /// * `measure` - Reduces a response to one number
+    ///
+    /// # Returns
+    /// Mean over the test frames of the adapted measure minus the control
+    pub fn aftereffect<F: Fn(&VisualResponse) -> f32>(&self, measure: F) -> f32 {
+        let mean = |records: &[FrameRecord]| {
+            records.iter().map(|record| measure(&record.response)).sum::<f32>() / records.len().max(1) as f32
+        };
+        mean(&self.test) - mean(&self.control)
+    }
+
+    /// Returns the aftereffect as a fraction of the control measure
+    pub fn relative_aftereffect<F: Fn(&VisualResponse) -> f32>(&self, measure: F) -> f32 {
+        let control = self.control.iter().map(|record| measure(&record.response)).sum::<f32>()
+            / self.control.len().max(1) as f32;
+        self.aftereffect(measure) / control.abs().max(f32::EPSILON)
+    }
+
+    /// Returns how far adaptation rotated the orientation V1 reads from the test
+    ///
+    /// The orientation is read from the tuning summed over the test frames.
+    ///
+    /// # Returns
+    /// Signed difference in degrees (-90 to 90) between the adapted and
+    /// control readouts, or 0.0 if V1 is silent
+    pub fn orientation_shift(&self) -> f32 {
+        let adapted = pooled_orientation(&self.test);
+        let control = pooled_orientation(&self.control);
+        match (adapted, control) {
+            (Some(adapted), Some(control)) => orientation_difference(adapted, control),
+            _ => 0.0,
+        }
+    }
+
+    /// Returns the tilt aftereffect: the orientation shift away from the adapter
+    ///
+    /// The adapter's orientation is read from V1's response to its first
+    /// (unadapted) frame, so the sign does not depend on how V1 labels
+    /// orientations. Positive values mean the test was repelled from the
+    /// adapter, negative values that it was attracted.
+    pub fn tilt_repulsion(&self) -> f32 {
+        let adapter = self.adaptation.first().and_then(FrameRecord::decoded_orientation);
+        let control = pooled_orientation(&self.control);
+        match (adapter, control) {
+            (Some(adapter), Some(control)) => {
+                self.orientation_shift() * orientation_difference(control, adapter).signum()
+            }
+            _ => 0.0,
+        }
+    }
+
+    /// Renders control, adapted and difference maps side by side
+    ///
+    /// Each map is averaged over the test frames.
+    ///
+    /// # Arguments
+    /// * `map` - Selects the map to render from a response
+    /// * `output_path` - Where to save the image
+    pub fn save_comparison<F>(&self, map: F, output_path: &str) -> Result<(), String>
+    where
+        F: Fn(&VisualResponse) -> &Vec<Vec<f32>>,
+    {
+        let control = mean_map(&self.control, &map)?;
+        let adapted = mean_map(&self.test, &map)?;
+        let difference = adapted
+            .iter()
+            .zip(&control)
+            .map(|(adapted_row, control_row)| adapted_row.iter().zip(control_row).map(|(a, c)| a - c).collect())
+            .collect();
+        visualize_signed_maps(&[control, adapted, difference], output_path)
+    }
+}
+
+/// Averages a map over the frames of a record
+fn mean_map<F>(records: &[FrameRecord], map: &F) -> Result<Vec<Vec<f32>>, String>
+where
+    F: Fn(&VisualResponse) -> &Vec<Vec<f32>>,
+{
+    let first = records.first().ok_or("No test frames recorded")?;
+    let mut mean: Vec<Vec<f32>> = map(&first.response).iter().map(|row| vec![0.0; row.len()]).collect();
+    for record in records {
+        for (mean_row, row) in mean.iter_mut().zip(map(&record.response)) {
+            for (total, value) in mean_row.iter_mut().zip(row) {
+                *total += value / records.len() as f32;
+            }
+        }
+    }
+    Ok(mean)
+}
+
+/// Decodes the orientation of the tuning summed over several frames
+fn pooled_orientation(records: &[FrameRecord]) -> Option<f32> {
+    let mut tuning: Vec<(Orientation, f32)> = Vec::new();
+    for (orientation, activation) in records.iter().flat_map(|record| &record.orientation_tuning) {
+        match tuning.iter_mut().find(|(pooled, _)| pooled.degrees() == orientation.degrees()) {
+            Some((_, total)) => *total += activation,
+            None => tuning.push((*orientation, *activation)),
+        }
+    }
+    OrientationEstimate::from_tuning(&tuning).map(|estimate| estimate.orientation.degrees())
+}
+
+/// Returns the signed difference between two orientations (-90 to 90 degrees)
+fn orientation_difference(a: f32, b: f32) -> f32 {
+    (a - b + 90.0).rem_euclid(180.0) - 90.0
+}
+
+/// Returns the mean of the red-green opponent map (positive = reddish)
+pub fn mean_red_green(response: &VisualResponse) -> f32 {
+    mean_value(&response.red_green_map)
+}
+
+/// Returns the mean of the blue-yellow opponent map (positive = bluish)
+pub fn mean_blue_yellow(response: &VisualResponse) -> f32 {
+    mean_value(&response.blue_yellow_map)
+}
+
+/// Returns the summed ganglion edge map
+pub fn edge_energy(response: &VisualResponse) -> f32 {
+    response.edge_map.iter().flatten().sum()
+}
+
+fn mean_value(map: &[Vec<f32>]) -> f32 {
+    let count = map.iter().map(Vec::len).sum::<usize>();
+    if count == 0 {
+        return 0.0;
+    }
+    map.iter().flatten().sum::<f32>() / count as f32
+}
+
+/// Frames of a grating drifting across its orientation through one period
+fn drifting_grating(width: usize, height: usize, degrees: f32, contrast: f32) -> Vec<Stimulus> {
+    let steps = (PRESET_GRATING_PERIOD / DRIFT_STEP).round() as usize;
+    (0..steps)
+        .map(|step| {
+            Stimulus::Grayscale(test_patterns::sine_grating(
+                width,
+                height,
+                PRESET_GRATING_PERIOD,
+                degrees,
+                step as f32 * DRIFT_STEP,
+                contrast,
+            ))
+        })
+        .collect()
+}
+
+/// Colour afterimage: adapt to a uniform coloured field, then view grey
+///
+/// Compare `mean_red_green` and `mean_blue_yellow` with `aftereffect`:
+/// a red adapter leaves a greenish afterimage, a blue one a yellowish one.
+///
+/// # Arguments
+/// * `width`, `height` - Size of the visual field
+/// * `color` - sRGB colour of the adapting field
+/// * `adapt_frames` - Number of frames of adaptation
+pub fn color_afterimage(width: usize, height: usize, color: [f32; 3], adapt_frames: usize) -> AdaptationProtocol {
+    AdaptationProtocol::new(
+        vec![Stimulus::Color(vec![vec![color; width]; height])],
+        adapt_frames,
+        Stimulus::Color(vec![vec![[0.5; 3]; width]; height]),
+    )
+    .with_test_frames(AFTERIMAGE_TEST_FRAMES)
+}
+
+/// Tilt aftereffect: adapt to a drifting grating, then test with a static one
+///
+/// V1 neurons tuned near the adapter fatigue, so the population readout of
+/// the test swings away from the adapter; see `tilt_repulsion`. The test
+/// is shown for several frames, so the brief afterimage of the last adapter
+/// frames does not dominate the readout.
+///
+/// # Arguments
+/// * `width`, `height` - Size of the visual field
+/// * `adapt_degrees` - Orientation of the adapting grating
+/// * `test_degrees` - Orientation of the test grating
+/// * `adapt_frames` - Number of frames of adaptation
+pub fn tilt_aftereffect(
+    width: usize,
+    height: usize,
+    adapt_degrees: f32,
+    test_degrees: f32,
+    adapt_frames: usize,
+) -> AdaptationProtocol {
+    AdaptationProtocol::new(
+        drifting_grating(width, height, adapt_degrees, 1.0),
+        adapt_frames,
+        Stimulus::Grayscale(test_patterns::sine_grating(
+            width,
+            height,
+            PRESET_GRATING_PERIOD,
+            test_degrees,
+            0.0,
+            1.0,
+        )),
+    )
+    .with_test_frames(TILT_TEST_FRAMES)
+}
+
+/// Contrast adaptation: adapt to a drifting grating, then test with a static one
+///
+/// Both gratings are vertical; compare `edge_energy` with
+/// `relative_aftereffect` to see the loss of response.
+///
+/// # Arguments
+/// * `width`, `height` - Size of the visual field
+/// * `adapt_contrast` - Michelson contrast of the adapting grating
+/// * `test_contrast` - Michelson contrast of the test grating
+/// * `adapt_frames` - Number of frames of adaptation
+pub fn contrast_adaptation(
+    width: usize,
+    height: usize,
+    adapt_contrast: f32,
+    test_contrast: f32,
+    adapt_frames: usize,
+) -> AdaptationProtocol {
+    AdaptationProtocol::new(
+        drifting_grating(width, height, 90.0, adapt_contrast),
+        adapt_frames,
+        Stimulus::Grayscale(test_patterns::sine_grating(
+            width,
+            height,
+            PRESET_GRATING_PERIOD,
+            90.0,
+            0.0,
+            test_contrast,
+        )),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_color_afterimage_is_complementary() {
+        let red = color_afterimage(32, 32, [0.9, 0.1, 0.1], 30).run().unwrap();
+        let blue = color_afterimage(32, 32, [0.1, 0.1, 0.9], 30).run().unwrap();
+
+        assert_eq!(red.adaptation.len(), 30);
+        assert_eq!(red.test.len(), AFTERIMAGE_TEST_FRAMES);
+        // Red adapter: grey looks green; blue adapter: grey looks yellow
+        assert!(red.aftereffect(mean_red_green) < 0.0);
+        assert!(blue.aftereffect(mean_blue_yellow) < 0.0);
+    }
+
+    #[test]
+    fn test_tilt_aftereffect_repels_test() {
+        let from_horizontal = tilt_aftereffect(32, 32, 0.0, 45.0, 40).run().unwrap();
+        let from_vertical = tilt_aftereffect(32, 32, 90.0, 45.0, 40).run().unwrap();
+
+        // Adapters on either side push the readout in opposite directions
+        for r in [&from_horizontal, &from_vertical] { eprintln!("TILT adapt {:?} test {:?} control {:?} shift {}", r.adaptation.iter().map(|f| f.decoded_orientation()).collect::<Vec<_>>(), r.test[0].decoded_orientation(), r.control[0].decoded_orientation(), r.orientation_shift()); eprintln!("TUNE {:?}\n{:?}\nA0 {:?}\nA39 {:?}", r.test[0].orientation_tuning, r.control[0].orientation_tuning, r.adaptation[0].orientation_tuning, r.adaptation[39].orientation_tuning); }
+        assert!(from_horizontal.orientation_shift() * from_vertical.orientation_shift() < 0.0);
+        assert!(from_horizontal.tilt_repulsion() > 1.0);
+        assert!(from_vertical.tilt_repulsion() > 1.0);
+    }
+
+    #[test]
+    fn test_contrast_adaptation_weakens_response() {
+        let adapted = contrast_adaptation(32, 32, 1.0, 0.3, 40).run().unwrap();
+        assert!(adapted.relative_aftereffect(edge_energy) < -0.1);
+    }
+
+    #[test]
+    fn test_save_comparison() {
+        let result = contrast_adaptation(16, 16, 1.0, 0.3, 5).run().unwrap();
+        let path = std::env::temp_dir().join(format!("neuron_comparison_{}.png", std::process::id()));
+        result.save_comparison(|response| &response.edge_map, path.to_str().unwrap()).unwrap();
+        let img = crate::image_utils::load_grayscale_image(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        // Control, adapted and difference panels separated by gaps
+        let map = &result.test[0].response.edge_map;
+        assert_eq!((img.len(), img[0].len()), (map.len(), 3 * map[0].len() + 4));
+    }
+
+    #[test]
+    fn test_mismatched_stimuli_are_rejected() {
+        let protocol = AdaptationProtocol::new(
+            vec![Stimulus::Grayscale(vec![vec![0.5; 8]; 8])],
+            2,
+            Stimulus::Grayscale(vec![vec![0.5; 16]; 16]),
+        );
+        assert!(protocol.run().is_err());
+        assert!(AdaptationProtocol::new(Vec::new(), 2, Stimulus::Grayscale(vec![vec![0.5; 8]; 8]))
+            .run()
+            .is_err());
+    }
+
+    #[test]
+    fn test_mean_field_and_orientation_difference() {
+        let field = Stimulus::Grayscale(vec![vec![0.0, 1.0]; 2]).mean_field();
+        assert_eq!(field, Stimulus::Grayscale(vec![vec![0.5, 0.5]; 2]));
+        assert_eq!(orientation_difference(10.0, 170.0), 20.0);
+        assert_eq!(orientation_difference(170.0, 10.0), -20.0);
+    }
+}
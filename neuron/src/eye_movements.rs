@@ -0,0 +1,609 @@
+//! Eye movements - Fixational motion and saccades
+//!
+//! The eye is never still. Even while fixating, three movements keep the
+//! retinal image in motion:
+//! - Drift: a slow random walk of a few arcmin per second
+//! - Tremor: a tiny (well under one cone) high-frequency oscillation
+//! - Microsaccades: brief jumps of 5-30 arcmin, one or two per second,
+//!   that mostly carry the eye back towards the fixation target
+//!
+//! Between fixations, saccades shift the fixation point. Their duration
+//! grows with amplitude (the "main sequence") and the eye follows a smooth
+//! minimum-jerk trajectory. Without this motion, adapting photoreceptors
+//! and transient ganglion cells fade an unchanging image (Troxler fading).
+//!
+//! Gaze positions are in scene pixels; `retinal_frame` cuts out the part
+//! of the scene imaged on the retina around the current gaze.
+
+use std::collections::VecDeque;
+
+use crate::optics::DEFAULT_PIXELS_PER_DEGREE;
+use crate::rng::SeededRng;
+
+/// Diffusion constant of ocular drift (arcmin²/s)
+const DEFAULT_DRIFT_DIFFUSION: f32 = 20.0;
+
+/// Amplitude (arcmin) and frequency (Hz) of physiological tremor
+const DEFAULT_TREMOR_AMPLITUDE: f32 = 0.25;
+const DEFAULT_TREMOR_FREQUENCY: f32 = 90.0;
+
+/// Mean microsaccade rate (per second) and typical amplitude (arcmin)
+const DEFAULT_MICROSACCADE_RATE: f32 = 1.5;
+const DEFAULT_MICROSACCADE_AMPLITUDE: f32 = 15.0;
+
+/// Drift beyond this fraction of the microsaccade amplitude triggers a corrective jump
+const CORRECTIVE_THRESHOLD: f32 = 0.25;
+
+/// Main sequence: saccade duration = intercept + slope × amplitude
+const SACCADE_DURATION_INTERCEPT_MS: f32 = 21.0;
+const SACCADE_DURATION_SLOPE_MS_PER_DEGREE: f32 = 2.2;
+
+/// Default time spent at each fixation of a scan path (ms)
+const DEFAULT_FIXATION_DURATION_MS: f32 = 300.0;
+
+/// Time step used to integrate eye movements within a frame (ms)
+const EYE_TIME_STEP_MS: f32 = 1.0;
+
+/// Kind of movement the eye is making
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EyeMovement {
+    /// Fixating: drift and tremor only
+    Fixation,
+    /// A microsaccade occurred during the last step
+    Microsaccade,
+    /// A saccade is in flight
+    Saccade,
+}
+
+/// Position of the eye at one moment
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GazeSample {
+    /// Time since the eye started moving (ms)
+    pub time_ms: f32,
+    /// Scene coordinates imaged on the centre of the retina (pixels)
+    pub x: f32,
+    pub y: f32,
+    /// Movement in progress
+    pub movement: EyeMovement,
+}
+
+/// Statistics of drift, tremor and microsaccades during fixation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FixationalEyeMovements {
+    drift_diffusion: f32,
+    tremor_amplitude: f32,
+    tremor_frequency: f32,
+    microsaccade_rate: f32,
+    microsaccade_amplitude: f32,
+    pixels_per_degree: f32,
+}
+
+impl FixationalEyeMovements {
+    /// Creates the fixational movements of a typical observer
+    ///
+    /// Scenes are taken to be sampled at 120 pixels per degree, as in
+    /// `EyeOptics`.
+    pub fn new() -> Self {
+        Self {
+            drift_diffusion: DEFAULT_DRIFT_DIFFUSION,
+            tremor_amplitude: DEFAULT_TREMOR_AMPLITUDE,
+            tremor_frequency: DEFAULT_TREMOR_FREQUENCY,
+            microsaccade_rate: DEFAULT_MICROSACCADE_RATE,
+            microsaccade_amplitude: DEFAULT_MICROSACCADE_AMPLITUDE,
+            pixels_per_degree: DEFAULT_PIXELS_PER_DEGREE,
+        }
+    }
+
+    /// Creates a perfectly still eye (retinal stabilization)
+    pub fn none() -> Self {
+        Self::new().with_drift(0.0).with_tremor(0.0, DEFAULT_TREMOR_FREQUENCY).with_microsaccades(0.0, 0.0)
+    }
+
+    /// Returns a copy with a different drift diffusion constant (arcmin²/s)
+    pub fn with_drift(mut self, diffusion: f32) -> Self {
+        self.drift_diffusion = diffusion.max(0.0);
+        self
+    }
+
+    /// Returns a copy with different tremor
+    ///
+    /// # Arguments
+    /// * `amplitude` - Peak tremor displacement (arcmin)
+    /// * `frequency` - Tremor frequency (Hz)
+    pub fn with_tremor(mut self, amplitude: f32, frequency: f32) -> Self {
+        self.tremor_amplitude = amplitude.max(0.0);
+        self.tremor_frequency = frequency.max(0.0);
+        self
+    }
+
+    /// Returns a copy with different microsaccades
+    ///
+    /// # Arguments
+    /// * `rate` - Mean number of microsaccades per second
+    /// * `amplitude` - Typical microsaccade amplitude (arcmin)
+    pub fn with_microsaccades(mut self, rate: f32, amplitude: f32) -> Self {
+        self.microsaccade_rate = rate.max(0.0);
+        self.microsaccade_amplitude = amplitude.max(0.0);
+        self
+    }
+
+    /// Returns a copy for scenes sampled at a different resolution
+    pub fn with_pixels_per_degree(mut self, pixels_per_degree: f32) -> Self {
+        self.pixels_per_degree = pixels_per_degree.max(f32::EPSILON);
+        self
+    }
+
+    /// Returns the drift diffusion constant (arcmin²/s)
+    pub fn drift_diffusion(&self) -> f32 {
+        self.drift_diffusion
+    }
+
+    /// Returns the mean microsaccade rate (per second)
+    pub fn microsaccade_rate(&self) -> f32 {
+        self.microsaccade_rate
+    }
+
+    /// Returns the scene resolution (pixels per degree)
+    pub fn pixels_per_degree(&self) -> f32 {
+        self.pixels_per_degree
+    }
+
+    /// Converts arcmin to scene pixels
+    fn arcmin_to_pixels(&self, arcmin: f32) -> f32 {
+        arcmin * self.pixels_per_degree / 60.0
+    }
+}
+
+impl Default for FixationalEyeMovements {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A saccade in flight
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Saccade {
+    from: (f32, f32),
+    to: (f32, f32),
+    elapsed_ms: f32,
+    duration_ms: f32,
+}
+
+impl Saccade {
+    /// Position along a minimum-jerk trajectory
+    fn position(&self) -> (f32, f32) {
+        let t = (self.elapsed_ms / self.duration_ms).clamp(0.0, 1.0);
+        let s = t * t * t * (10.0 - 15.0 * t + 6.0 * t * t);
+        (self.from.0 + s * (self.to.0 - self.from.0), self.from.1 + s * (self.to.1 - self.from.1))
+    }
+}
+
+/// Moves the fixation point from target to target with saccades
+#[derive(Debug, Clone, PartialEq)]
+pub struct SaccadeController {
+    fixation: (f32, f32),
+    targets: VecDeque<(f32, f32)>,
+    in_flight: Option<Saccade>,
+    fixation_duration_ms: f32,
+    dwell_ms: f32,
+    pixels_per_degree: f32,
+}
+
+impl SaccadeController {
+    /// Creates a new controller fixating a scene point
+    ///
+    /// # Arguments
+    /// * `fixation` - Initial fixation point (scene pixels)
+    pub fn new(fixation: (f32, f32)) -> Self {
+        Self {
+            fixation,
+            targets: VecDeque::new(),
+            in_flight: None,
+            fixation_duration_ms: DEFAULT_FIXATION_DURATION_MS,
+            dwell_ms: 0.0,
+            pixels_per_degree: DEFAULT_PIXELS_PER_DEGREE,
+        }
+    }
+
+    /// Returns a copy that visits targets in order (a scan path)
+    pub fn with_scan_path(mut self, targets: &[(f32, f32)]) -> Self {
+        self.targets.extend(targets.iter().copied());
+        self
+    }
+
+    /// Returns a copy that stays at each fixation for a different time (ms)
+    pub fn with_fixation_duration(mut self, duration_ms: f32) -> Self {
+        self.fixation_duration_ms = duration_ms.max(0.0);
+        self
+    }
+
+    /// Returns a copy for scenes sampled at a different resolution
+    pub fn with_pixels_per_degree(mut self, pixels_per_degree: f32) -> Self {
+        self.pixels_per_degree = pixels_per_degree.max(f32::EPSILON);
+        self
+    }
+
+    /// Launches a saccade to a target now, abandoning any queued targets
+    pub fn saccade_to(&mut self, target: (f32, f32)) {
+        self.targets.clear();
+        self.launch(target);
+    }
+
+    /// Adds a target to visit after the current fixation
+    pub fn queue_target(&mut self, target: (f32, f32)) {
+        self.targets.push_back(target);
+    }
+
+    /// Returns the current (or, during a saccade, the next) fixation point
+    pub fn fixation(&self) -> (f32, f32) {
+        self.in_flight.map_or(self.fixation, |saccade| saccade.to)
+    }
+
+    /// Returns true while a saccade is in flight
+    pub fn in_flight(&self) -> bool {
+        self.in_flight.is_some()
+    }
+
+    /// Returns the duration of a saccade of a given size (ms)
+    ///
+    /// # Arguments
+    /// * `amplitude` - Saccade amplitude (scene pixels)
+    pub fn saccade_duration(&self, amplitude: f32) -> f32 {
+        SACCADE_DURATION_INTERCEPT_MS + SACCADE_DURATION_SLOPE_MS_PER_DEGREE * amplitude / self.pixels_per_degree
+    }
+
+    /// Advances the controller by one time step
+    ///
+    /// # Returns
+    /// The saccadic eye position while a saccade is in flight, None while fixating
+    fn step(&mut self, dt_ms: f32) -> Option<(f32, f32)> {
+        if self.in_flight.is_none() {
+            self.dwell_ms += dt_ms;
+            if self.dwell_ms >= self.fixation_duration_ms
+                && let Some(target) = self.targets.pop_front()
+            {
+                self.launch(target);
+            }
+        }
+
+        let saccade = self.in_flight.as_mut()?;
+        saccade.elapsed_ms += dt_ms;
+        let position = saccade.position();
+        if saccade.elapsed_ms >= saccade.duration_ms {
+            self.fixation = saccade.to;
+            self.in_flight = None;
+            self.dwell_ms = 0.0;
+        }
+        Some(position)
+    }
+
+    fn launch(&mut self, target: (f32, f32)) {
+        let from = self.in_flight.map_or(self.fixation, |saccade| saccade.position());
+        let amplitude = (target.0 - from.0).hypot(target.1 - from.1);
+        self.in_flight = Some(Saccade {
+            from,
+            to: target,
+            elapsed_ms: 0.0,
+            duration_ms: self.saccade_duration(amplitude),
+        });
+    }
+}
+
+/// An eye that fixates, drifts and makes saccades across a scene
+#[derive(Debug, Clone)]
+pub struct Eye {
+    movements: FixationalEyeMovements,
+    saccades: SaccadeController,
+    // Drift and microsaccade displacement from the fixation point (pixels)
+    offset: (f32, f32),
+    // Gaze at the end of the last step, including tremor
+    gaze: (f32, f32),
+    movement: EyeMovement,
+    tremor_phase: [f32; 2],
+    time_ms: f32,
+    rng: SeededRng,
+}
+
+impl Eye {
+    /// Creates a new eye fixating a scene point
+    ///
+    /// # Arguments
+    /// * `fixation` - Initial fixation point (scene pixels)
+    /// * `movements` - Drift, tremor and microsaccade statistics
+    /// * `seed` - Seed of the random movements
+    pub fn new(fixation: (f32, f32), movements: FixationalEyeMovements, seed: u64) -> Self {
+        let mut rng = SeededRng::new(seed);
+        let tremor_phase = [rng.range(0.0, std::f32::consts::TAU), rng.range(0.0, std::f32::consts::TAU)];
+        Self {
+            movements,
+            saccades: SaccadeController::new(fixation).with_pixels_per_degree(movements.pixels_per_degree),
+            offset: (0.0, 0.0),
+            gaze: fixation,
+            movement: EyeMovement::Fixation,
+            tremor_phase,
+            time_ms: 0.0,
+            rng,
+        }
+    }
+
+    /// Creates an eye that never moves
+    pub fn still(fixation: (f32, f32)) -> Self {
+        Self::new(fixation, FixationalEyeMovements::none(), 0)
+    }
+
+    /// Returns a copy driven by a different saccade controller
+    pub fn with_saccades(mut self, saccades: SaccadeController) -> Self {
+        self.gaze = saccades.fixation();
+        self.saccades = saccades;
+        self
+    }
+
+    /// Returns the saccade controller
+    pub fn saccades(&self) -> &SaccadeController {
+        &self.saccades
+    }
+
+    /// Returns the saccade controller, e.g. to launch a saccade
+    pub fn saccades_mut(&mut self) -> &mut SaccadeController {
+        &mut self.saccades
+    }
+
+    /// Returns the fixational movement statistics
+    pub fn movements(&self) -> &FixationalEyeMovements {
+        &self.movements
+    }
+
+    /// Returns the current gaze
+    pub fn gaze(&self) -> GazeSample {
+        GazeSample {
+            time_ms: self.time_ms,
+            x: self.gaze.0,
+            y: self.gaze.1,
+            movement: self.movement,
+        }
+    }
+
+    /// Moves the eye for a period of time
+    ///
+    /// Movements are integrated in 1 ms steps. The returned movement is a
+    /// saccade or microsaccade if one happened at any point in the period.
+    ///
+    /// # Arguments
+    /// * `duration_ms` - Time to advance (ms)
+    ///
+    /// # Returns
+    /// The gaze at the end of the period
+    pub fn advance(&mut self, duration_ms: f32) -> GazeSample {
+        let mut movement = EyeMovement::Fixation;
+        let mut remaining = duration_ms.max(0.0);
+        while remaining > 0.0 {
+            let dt = remaining.min(EYE_TIME_STEP_MS);
+            let step = self.step(dt);
+            if step != EyeMovement::Fixation && movement != EyeMovement::Saccade {
+                movement = step;
+            }
+            remaining -= dt;
+        }
+        self.movement = movement;
+        self.gaze()
+    }
+
+    /// Samples the gaze once per frame
+    ///
+    /// # Arguments
+    /// * `frames` - Number of frames
+    /// * `frame_interval_ms` - Time between frames (ms)
+    pub fn trajectory(&mut self, frames: usize, frame_interval_ms: f32) -> Vec<GazeSample> {
+        (0..frames).map(|_| self.advance(frame_interval_ms)).collect()
+    }
+
+    /// Advances all movements by one time step
+    fn step(&mut self, dt_ms: f32) -> EyeMovement {
+        self.time_ms += dt_ms;
+
+        if let Some(position) = self.saccades.step(dt_ms) {
+            // Fixational motion restarts at the new fixation point
+            self.offset = (0.0, 0.0);
+            self.gaze = position;
+            return EyeMovement::Saccade;
+        }
+
+        self.drift(dt_ms);
+        let movement = self.microsaccade(dt_ms);
+
+        let tremor = self.tremor();
+        let fixation = self.saccades.fixation();
+        self.gaze = (fixation.0 + self.offset.0 + tremor.0, fixation.1 + self.offset.1 + tremor.1);
+        movement
+    }
+
+    /// Random walk with variance 2·D·t along each axis
+    fn drift(&mut self, dt_ms: f32) {
+        let sigma = self.movements.arcmin_to_pixels((2.0 * self.movements.drift_diffusion * dt_ms / 1000.0).sqrt());
+        self.offset.0 += sigma * self.rng.gaussian();
+        self.offset.1 += sigma * self.rng.gaussian();
+    }
+
+    /// Poisson microsaccades, corrective once drift has carried the eye away
+    fn microsaccade(&mut self, dt_ms: f32) -> EyeMovement {
+        let probability = 1.0 - (-self.movements.microsaccade_rate * dt_ms / 1000.0).exp();
+        if !self.rng.chance(probability) {
+            return EyeMovement::Fixation;
+        }
+
+        let amplitude = self.movements.arcmin_to_pixels(self.movements.microsaccade_amplitude) * self.rng.range(0.5, 1.5);
+        let displacement = self.offset.0.hypot(self.offset.1);
+        if displacement > CORRECTIVE_THRESHOLD * amplitude {
+            // Jump back towards the target, overshooting or undershooting a little
+            let scale = 1.0 - self.rng.range(0.8, 1.2);
+            self.offset = (self.offset.0 * scale, self.offset.1 * scale);
+        } else {
+            let direction = self.rng.range(0.0, std::f32::consts::TAU);
+            self.offset.0 += amplitude * direction.cos();
+            self.offset.1 += amplitude * direction.sin();
+        }
+        EyeMovement::Microsaccade
+    }
+
+    /// Tremor displacement at the current time
+    fn tremor(&self) -> (f32, f32) {
+        let amplitude = self.movements.arcmin_to_pixels(self.movements.tremor_amplitude);
+        let angle = std::f32::consts::TAU * self.movements.tremor_frequency * self.time_ms / 1000.0;
+        (
+            amplitude * (angle + self.tremor_phase[0]).sin(),
+            amplitude * (angle + self.tremor_phase[1]).sin(),
+        )
+    }
+}
+
+/// Cuts out the part of a scene imaged on the retina
+///
+/// Pixel (x, y) of the frame shows the scene at gaze + (x, y) - centre,
+/// with bilinear interpolation for sub-pixel gaze positions. Beyond the
+/// scene border the edge pixels are repeated.
+///
+/// # Arguments
+/// * `scene` - Grayscale scene (reflectances)
+/// * `gaze` - Gaze whose position lands on the centre of the frame
+/// * `width`, `height` - Size of the retinal frame
+pub fn retinal_frame(scene: &[Vec<f32>], gaze: &GazeSample, width: usize, height: usize) -> Vec<Vec<f32>> {
+    let scene_height = scene.len();
+    let scene_width = scene.first().map_or(0, Vec::len);
+    if scene_width == 0 {
+        return vec![vec![0.0; width]; height];
+    }
+
+    let pixel = |x: i64, y: i64| {
+        let x = x.clamp(0, scene_width as i64 - 1) as usize;
+        let y = y.clamp(0, scene_height as i64 - 1) as usize;
+        scene[y][x]
+    };
+    let origin_x = gaze.x - (width / 2) as f32;
+    let origin_y = gaze.y - (height / 2) as f32;
+
+    (0..height)
+        .map(|y| {
+            (0..width)
+                .map(|x| {
+                    let sx = origin_x + x as f32;
+                    let sy = origin_y + y as f32;
+                    let (x0, y0) = (sx.floor(), sy.floor());
+                    let (fx, fy) = (sx - x0, sy - y0);
+                    let (x0, y0) = (x0 as i64, y0 as i64);
+                    let top = (1.0 - fx) * pixel(x0, y0) + fx * pixel(x0 + 1, y0);
+                    let bottom = (1.0 - fx) * pixel(x0, y0 + 1) + fx * pixel(x0 + 1, y0 + 1);
+                    (1.0 - fy) * top + fy * bottom
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn displacement(sample: &GazeSample, from: (f32, f32)) -> f32 {
+        (sample.x - from.0).hypot(sample.y - from.1)
+    }
+
+    #[test]
+    fn test_still_eye_does_not_move() {
+        let mut eye = Eye::still((16.0, 16.0));
+        for sample in eye.trajectory(30, 33.0) {
+            assert_eq!((sample.x, sample.y), (16.0, 16.0));
+            assert_eq!(sample.movement, EyeMovement::Fixation);
+        }
+    }
+
+    #[test]
+    fn test_drift_is_diffusive() {
+        let movements = FixationalEyeMovements::new().with_microsaccades(0.0, 0.0).with_tremor(0.0, 90.0);
+        let mean_squared = |duration_ms: f32| {
+            (0..200)
+                .map(|seed| {
+                    let sample = Eye::new((0.0, 0.0), movements, seed).advance(duration_ms);
+                    displacement(&sample, (0.0, 0.0)).powi(2)
+                })
+                .sum::<f32>()
+                / 200.0
+        };
+
+        // Mean squared displacement 4·D·t, in pixels² (2 pixels per arcmin)
+        let expected = 4.0 * DEFAULT_DRIFT_DIFFUSION * 0.5 * 4.0;
+        let measured = mean_squared(500.0);
+        assert!((measured - expected).abs() < 0.25 * expected, "MSD {} vs {}", measured, expected);
+        assert!(mean_squared(1000.0) > 1.5 * measured);
+    }
+
+    #[test]
+    fn test_microsaccades_keep_gaze_near_fixation() {
+        let drift_only = FixationalEyeMovements::new().with_microsaccades(0.0, 0.0);
+        let rms = |movements: FixationalEyeMovements| {
+            let mut total = 0.0;
+            let mut count = 0;
+            for seed in 0..20 {
+                for sample in Eye::new((0.0, 0.0), movements, seed).trajectory(300, 33.0) {
+                    total += displacement(&sample, (0.0, 0.0)).powi(2);
+                    count += 1;
+                }
+            }
+            (total / count as f32).sqrt()
+        };
+        assert!(rms(FixationalEyeMovements::new()) < 0.75 * rms(drift_only));
+
+        // Roughly the requested rate over 10 s
+        let mut eye = Eye::new((0.0, 0.0), FixationalEyeMovements::new().with_drift(0.0), 3);
+        let count = (0..10_000)
+            .filter(|_| eye.advance(1.0).movement == EyeMovement::Microsaccade)
+            .count();
+        assert!((8..=25).contains(&count), "{} microsaccades", count);
+    }
+
+    #[test]
+    fn test_saccades_follow_main_sequence() {
+        let controller = SaccadeController::new((0.0, 0.0)).with_fixation_duration(100.0).with_scan_path(&[(120.0, 0.0)]);
+        assert!((controller.saccade_duration(120.0) - 23.2).abs() < 1e-3);
+        assert!(controller.saccade_duration(1200.0) > controller.saccade_duration(120.0));
+
+        let mut eye = Eye::still((0.0, 0.0)).with_saccades(controller);
+        let samples: Vec<GazeSample> = (0..200).map(|_| eye.advance(1.0)).collect();
+        let flight: Vec<&GazeSample> = samples.iter().filter(|s| s.movement == EyeMovement::Saccade).collect();
+
+        // Launches after the fixation, lasts ~23 ms and lands on target
+        assert!(flight.len() >= 22 && flight.len() <= 25);
+        assert!(flight[0].time_ms > 99.0);
+        assert!(flight.windows(2).all(|pair| pair[1].x >= pair[0].x));
+        assert_eq!(eye.saccades().fixation(), (120.0, 0.0));
+        assert_eq!((eye.gaze().x, eye.gaze().y), (120.0, 0.0));
+    }
+
+    #[test]
+    fn test_retinal_frame_follows_gaze() {
+        let scene: Vec<Vec<f32>> = (0..16).map(|y| (0..16).map(|x| (x + 16 * y) as f32).collect()).collect();
+        let gaze = |x: f32, y: f32| GazeSample { time_ms: 0.0, x, y, movement: EyeMovement::Fixation };
+
+        let frame = retinal_frame(&scene, &gaze(8.0, 8.0), 8, 8);
+        assert_eq!(frame[0][0], scene[4][4]);
+        assert_eq!(frame[7][7], scene[11][11]);
+
+        // Moving the eye right shifts the image left on the retina
+        let shifted = retinal_frame(&scene, &gaze(9.0, 8.0), 8, 8);
+        assert_eq!(shifted[3][3], frame[3][4]);
+
+        // Half-pixel gaze interpolates, edges repeat
+        let half = retinal_frame(&scene, &gaze(8.5, 8.0), 8, 8);
+        assert!((half[0][0] - 0.5 * (scene[4][4] + scene[4][5])).abs() < 1e-4);
+        let outside = retinal_frame(&scene, &gaze(0.0, 0.0), 8, 8);
+        assert_eq!(outside[0][0], scene[0][0]);
+    }
+
+    #[test]
+    fn test_movements_are_reproducible_from_seed() {
+        let a = Eye::new((10.0, 10.0), FixationalEyeMovements::new(), 9).trajectory(50, 33.0);
+        let b = Eye::new((10.0, 10.0), FixationalEyeMovements::new(), 9).trajectory(50, 33.0);
+        let c = Eye::new((10.0, 10.0), FixationalEyeMovements::new(), 10).trajectory(50, 33.0);
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+}
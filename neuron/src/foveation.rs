@@ -0,0 +1,486 @@
+//! Foveated retina: eccentricity-dependent sampling and cortical magnification
+//!
+//! Receptor and ganglion cell spacing grow roughly linearly with
+//! eccentricity: spacing(e) = spacing(0) * (1 + e / E2), where E2 is the
+//! eccentricity at which spacing has doubled. Integrating the matching
+//! cortical magnification M(e) ∝ 1 / (e + E2) gives the log-polar
+//! (complex-log) map from the visual field onto V1.
+
+use std::f32::consts::PI;
+
+/// Default eccentricity at which sampling spacing doubles (pixels)
+const DEFAULT_SCALING_ECCENTRICITY: f32 = 16.0;
+
+/// A rectangular patch of the visual field sampled by a single cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SamplingBlock {
+    /// Left edge (pixels)
+    pub x: usize,
+    /// Top edge (pixels)
+    pub y: usize,
+    /// Width (pixels)
+    pub width: usize,
+    /// Height (pixels)
+    pub height: usize,
+}
+
+impl SamplingBlock {
+    /// Returns the pixel at the centre of the block
+    pub fn center(&self) -> (usize, usize) {
+        (self.x + self.width / 2, self.y + self.height / 2)
+    }
+
+    /// Returns the number of pixels in the block
+    pub fn area(&self) -> usize {
+        self.width * self.height
+    }
+}
+
+/// Fixation point and eccentricity scaling of a foveated retina
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Foveation {
+    /// Fixation point in image coordinates (pixels)
+    pub fixation: (f32, f32),
+    /// Eccentricity at which sampling spacing doubles (pixels)
+    pub scaling_eccentricity: f32,
+}
+
+impl Foveation {
+    /// Creates a new foveation centred on a fixation point
+    ///
+    /// # Arguments
+    /// * `fixation_x`, `fixation_y` - Fixation point in image coordinates
+    pub fn new(fixation_x: f32, fixation_y: f32) -> Self {
+        Self {
+            fixation: (fixation_x, fixation_y),
+            scaling_eccentricity: DEFAULT_SCALING_ECCENTRICITY,
+        }
+    }
+
+    /// Creates a new foveation fixating the centre of an image
+    pub fn centered(width: usize, height: usize) -> Self {
+        Self::new(width as f32 / 2.0, height as f32 / 2.0)
+    }
+
+    /// Returns a copy with a different spacing-doubling eccentricity
+    ///
+    /// Smaller values give a steeper fall-off of acuity with eccentricity.
+    pub fn with_scaling_eccentricity(mut self, scaling_eccentricity: f32) -> Self {
+        self.scaling_eccentricity = scaling_eccentricity.max(f32::EPSILON);
+        self
+    }
+
+    /// Returns the distance of a point from fixation (pixels)
+    pub fn eccentricity(&self, x: f32, y: f32) -> f32 {
+        ((x - self.fixation.0).powi(2) + (y - self.fixation.1).powi(2)).sqrt()
+    }
+
+    /// Returns how much larger sampling spacing is at a point than at fixation
+    pub fn spacing_scale(&self, x: f32, y: f32) -> f32 {
+        1.0 + self.eccentricity(x, y) / self.scaling_eccentricity
+    }
+
+    /// Returns the relative cortical magnification at a point (1.0 at fixation)
+    pub fn cortical_magnification(&self, x: f32, y: f32) -> f32 {
+        1.0 / self.spacing_scale(x, y)
+    }
+
+    /// Tiles the visual field with blocks whose size follows the local spacing
+    ///
+    /// Blocks come from a quadtree: a block is split while it is larger
+    /// than `base_spacing * spacing_scale` at its centre, so block sizes are
+    /// powers of two that grow with eccentricity. The blocks cover every
+    /// pixel exactly once.
+    ///
+    /// # Arguments
+    /// * `width`, `height` - Dimensions of visual field
+    /// * `base_spacing` - Spacing at fixation (pixels)
+    pub fn sampling_blocks(&self, width: usize, height: usize, base_spacing: f32) -> Vec<SamplingBlock> {
+        let mut blocks = Vec::new();
+        let mut pending = vec![(0, 0, width.max(height).max(1).next_power_of_two())];
+
+        while let Some((x, y, size)) = pending.pop() {
+            if x >= width || y >= height {
+                continue;
+            }
+
+            let block = SamplingBlock {
+                x,
+                y,
+                width: size.min(width - x),
+                height: size.min(height - y),
+            };
+            let (cx, cy) = block.center();
+            let required = base_spacing * self.spacing_scale(cx as f32, cy as f32);
+
+            if size > 1 && size as f32 > required {
+                let half = size / 2;
+                for (dx, dy) in [(half, half), (0, half), (half, 0), (0, 0)] {
+                    pending.push((x + dx, y + dy, half));
+                }
+            } else {
+                blocks.push(block);
+            }
+        }
+
+        blocks
+    }
+
+    /// Maps a point of the visual field to V1 coordinates
+    ///
+    /// # Returns
+    /// (cortical distance from the foveal representation, polar angle in
+    /// radians from -π to π). Cortical distance is ln(1 + e / E2), in units
+    /// of E2 of cortex at fixation.
+    pub fn to_cortex(&self, x: f32, y: f32) -> (f32, f32) {
+        let dx = x - self.fixation.0;
+        let dy = y - self.fixation.1;
+        let eccentricity = (dx * dx + dy * dy).sqrt();
+
+        ((1.0 + eccentricity / self.scaling_eccentricity).ln(), dy.atan2(dx))
+    }
+
+    /// Maps V1 coordinates back to the visual field (inverse of `to_cortex`)
+    pub fn from_cortex(&self, distance: f32, angle: f32) -> (f32, f32) {
+        let eccentricity = self.scaling_eccentricity * (distance.exp() - 1.0);
+        (
+            self.fixation.0 + eccentricity * angle.cos(),
+            self.fixation.1 + eccentricity * angle.sin(),
+        )
+    }
+
+    /// Resamples a retinotopic map into log-polar V1 coordinates
+    ///
+    /// Columns run from the foveal representation (left) to the largest
+    /// eccentricity in the image (right); rows cover polar angle from -π
+    /// (top) to π (bottom). Every non-zero value is projected forward and
+    /// each cortical pixel keeps the maximum it receives, so sparse maps
+    /// such as ganglion edge maps are preserved.
+    ///
+    /// # Arguments
+    /// * `map` - Retinotopic map (e.g. an edge map)
+    /// * `cortex_width`, `cortex_height` - Size of the cortical map
+    pub fn cortical_map(&self, map: &[Vec<f32>], cortex_width: usize, cortex_height: usize) -> Vec<Vec<f32>> {
+        let mut cortex = vec![vec![0.0; cortex_width]; cortex_height];
+        if map.is_empty() || map[0].is_empty() || cortex_width == 0 || cortex_height == 0 {
+            return cortex;
+        }
+
+        let grid = CorticalGrid::new(*self, map[0].len(), map.len(), cortex_width, cortex_height);
+        for (y, row) in map.iter().enumerate() {
+            for (x, &value) in row.iter().enumerate() {
+                if value == 0.0 {
+                    continue;
+                }
+                let (column, cortex_row) = grid.cortical_pixel(x as f32, y as f32);
+                let cell = &mut cortex[cortex_row][column];
+                *cell = cell.max(value);
+            }
+        }
+
+        cortex
+    }
+
+    /// Returns a log-polar grid with one cortical pixel per sampling spacing
+    ///
+    /// Both axes have E2 / `base_spacing` pixels per unit of cortical
+    /// distance (one radian of polar angle), so away from fixation a
+    /// cortical pixel covers about one sampling block of the field.
+    ///
+    /// # Arguments
+    /// * `width`, `height` - Dimensions of visual field
+    /// * `base_spacing` - Spacing at fixation (pixels)
+    pub fn cortical_grid(&self, width: usize, height: usize, base_spacing: f32) -> CorticalGrid {
+        let pixels_per_unit = self.scaling_eccentricity / base_spacing.max(f32::EPSILON);
+        let max_distance = self.max_cortical_distance(width, height);
+        CorticalGrid::new(
+            *self,
+            width,
+            height,
+            ((max_distance * pixels_per_unit).ceil() as usize).max(1),
+            ((2.0 * PI * pixels_per_unit).ceil() as usize).max(1),
+        )
+    }
+
+    /// Cortical distance of the field corner furthest from fixation
+    fn max_cortical_distance(&self, width: usize, height: usize) -> f32 {
+        let (width, height) = (width as f32, height as f32);
+        let max_eccentricity = [(0.0, 0.0), (width, 0.0), (0.0, height), (width, height)]
+            .iter()
+            .map(|&(x, y)| self.eccentricity(x, y))
+            .fold(0.0, f32::max);
+        (1.0 + max_eccentricity / self.scaling_eccentricity).ln()
+    }
+}
+
+/// Log-polar V1 map of a visual field, at a fixed resolution
+///
+/// Columns run from the foveal representation (left) to the largest
+/// eccentricity in the field (right); rows cover polar angle from -π
+/// (top) to π (bottom).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CorticalGrid {
+    foveation: Foveation,
+    field_width: usize,
+    field_height: usize,
+    width: usize,
+    height: usize,
+    max_distance: f32,
+}
+
+impl CorticalGrid {
+    /// Creates a grid of a given size over a visual field
+    ///
+    /// # Arguments
+    /// * `foveation` - Fixation point and eccentricity scaling
+    /// * `field_width`, `field_height` - Dimensions of visual field
+    /// * `width`, `height` - Size of the cortical map
+    pub fn new(foveation: Foveation, field_width: usize, field_height: usize, width: usize, height: usize) -> Self {
+        Self {
+            foveation,
+            field_width,
+            field_height,
+            width,
+            height,
+            max_distance: foveation.max_cortical_distance(field_width, field_height).max(f32::EPSILON),
+        }
+    }
+
+    /// Returns the size of the cortical map (columns, rows)
+    pub fn dimensions(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    /// Returns the dimensions of the visual field the grid covers
+    pub fn field_dimensions(&self) -> (usize, usize) {
+        (self.field_width, self.field_height)
+    }
+
+    /// Returns the cortical pixel a point of the visual field falls on
+    pub fn cortical_pixel(&self, x: f32, y: f32) -> (usize, usize) {
+        let (distance, angle) = self.foveation.to_cortex(x, y);
+        let column = (distance / self.max_distance * self.width as f32) as usize;
+        let row = ((angle + PI) / (2.0 * PI) * self.height as f32) as usize;
+        (column.min(self.width.saturating_sub(1)), row.min(self.height.saturating_sub(1)))
+    }
+
+    /// Returns the point of the visual field at the centre of a cortical pixel
+    pub fn field_position(&self, column: usize, row: usize) -> (f32, f32) {
+        let (distance, angle) = self.cortical_location(column, row);
+        self.foveation.from_cortex(distance, angle)
+    }
+
+    /// Converts an orientation on the cortical map to one in the visual field
+    ///
+    /// Radial and tangential steps of the field are scaled differently
+    /// onto the map (and the tangential direction turns with polar
+    /// angle), so a line keeps its orientation only along the horizontal
+    /// meridian far from fixation.
+    ///
+    /// # Arguments
+    /// * `column`, `row` - Cortical pixel
+    /// * `degrees` - Orientation on the cortical map (0° = along a row)
+    ///
+    /// # Returns
+    /// Orientation in the visual field (degrees, 0 to 180)
+    pub fn field_orientation(&self, column: usize, row: usize, degrees: f32) -> f32 {
+        let (distance, angle) = self.cortical_location(column, row);
+        let eccentricity = self.foveation.scaling_eccentricity * (distance.exp() - 1.0);
+        let (sin, cos) = degrees.to_radians().sin_cos();
+
+        // Field steps (radial, tangential) that map onto one cortical step
+        let radial = cos * (eccentricity + self.foveation.scaling_eccentricity) * self.max_distance / self.width as f32;
+        let tangential = sin * eccentricity * 2.0 * PI / self.height as f32;
+        (angle + tangential.atan2(radial)).to_degrees().rem_euclid(180.0)
+    }
+
+    /// Resamples a dense retinotopic map onto the grid
+    ///
+    /// Each cortical pixel averages the field pixels that project onto it;
+    /// magnified foveal pixels that receive none interpolate the field at
+    /// their centre. Unlike `Foveation::cortical_map`, signed and smoothly
+    /// varying maps keep their values.
+    ///
+    /// # Arguments
+    /// * `map` - Retinotopic map covering the grid's visual field
+    pub fn resample(&self, map: &[Vec<f32>]) -> Vec<Vec<f32>> {
+        let mut totals = vec![vec![(0.0f32, 0usize); self.width]; self.height];
+        for (y, row) in map.iter().enumerate() {
+            for (x, &value) in row.iter().enumerate() {
+                let (column, cortex_row) = self.cortical_pixel(x as f32, y as f32);
+                let total = &mut totals[cortex_row][column];
+                total.0 += value;
+                total.1 += 1;
+            }
+        }
+
+        let map_height = map.len();
+        let map_width = map.first().map_or(0, Vec::len);
+        totals
+            .iter()
+            .enumerate()
+            .map(|(row, cells)| {
+                cells
+                    .iter()
+                    .enumerate()
+                    .map(|(column, &(sum, count))| {
+                        if count > 0 {
+                            return sum / count as f32;
+                        }
+                        if map_width == 0 || map_height == 0 {
+                            return 0.0;
+                        }
+                        let (x, y) = self.field_position(column, row);
+                        let x = x.clamp(0.0, (map_width - 1) as f32);
+                        let y = y.clamp(0.0, (map_height - 1) as f32);
+                        let (x0, y0) = (x as usize, y as usize);
+                        let (x1, y1) = ((x0 + 1).min(map_width - 1), (y0 + 1).min(map_height - 1));
+                        let (fx, fy) = (x - x0 as f32, y - y0 as f32);
+                        let top = map[y0][x0] * (1.0 - fx) + map[y0][x1] * fx;
+                        let bottom = map[y1][x0] * (1.0 - fx) + map[y1][x1] * fx;
+                        top * (1.0 - fy) + bottom * fy
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Cortical distance and polar angle at the centre of a cortical pixel
+    fn cortical_location(&self, column: usize, row: usize) -> (f32, f32) {
+        (
+            (column as f32 + 0.5) / self.width as f32 * self.max_distance,
+            (row as f32 + 0.5) / self.height as f32 * 2.0 * PI - PI,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spacing_grows_with_eccentricity() {
+        let foveation = Foveation::new(50.0, 50.0);
+        assert_eq!(foveation.spacing_scale(50.0, 50.0), 1.0);
+        assert!((foveation.spacing_scale(66.0, 50.0) - 2.0).abs() < 1e-6);
+        assert!(foveation.cortical_magnification(90.0, 50.0) < foveation.cortical_magnification(60.0, 50.0));
+    }
+
+    #[test]
+    fn test_blocks_tile_visual_field() {
+        let foveation = Foveation::new(20.0, 30.0);
+        let blocks = foveation.sampling_blocks(100, 70, 1.0);
+
+        let mut coverage = vec![vec![0; 100]; 70];
+        for block in &blocks {
+            for row in coverage.iter_mut().skip(block.y).take(block.height) {
+                for count in row.iter_mut().skip(block.x).take(block.width) {
+                    *count += 1;
+                }
+            }
+        }
+        assert!(coverage.iter().flatten().all(|&count| count == 1));
+    }
+
+    #[test]
+    fn test_density_falls_with_eccentricity() {
+        let foveation = Foveation::centered(256, 256);
+        let blocks = foveation.sampling_blocks(256, 256, 1.0);
+
+        let block_at = |x: usize, y: usize| {
+            *blocks
+                .iter()
+                .find(|b| x >= b.x && x < b.x + b.width && y >= b.y && y < b.y + b.height)
+                .unwrap()
+        };
+        assert_eq!(block_at(128, 128).area(), 1);
+        assert!(block_at(2, 2).area() >= 64);
+        assert!(blocks.len() < 256 * 256 / 8);
+    }
+
+    #[test]
+    fn test_cortex_round_trip() {
+        let foveation = Foveation::new(10.0, 20.0);
+        let (distance, angle) = foveation.to_cortex(40.0, -5.0);
+        let (x, y) = foveation.from_cortex(distance, angle);
+
+        assert!((x - 40.0).abs() < 1e-3);
+        assert!((y + 5.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_fovea_is_magnified_in_cortex() {
+        let foveation = Foveation::new(0.0, 0.0);
+        // Equal 10-pixel steps of the visual field
+        let foveal_step = foveation.to_cortex(10.0, 0.0).0 - foveation.to_cortex(0.0, 0.0).0;
+        let peripheral_step = foveation.to_cortex(110.0, 0.0).0 - foveation.to_cortex(100.0, 0.0).0;
+
+        assert!(foveal_step > peripheral_step * 5.0);
+    }
+
+    #[test]
+    fn test_cortical_map_places_fixation_on_the_left() {
+        let foveation = Foveation::centered(64, 64);
+        let mut map = vec![vec![0.0; 64]; 64];
+        map[32][36] = 1.0; // near fixation
+        map[32][63] = 0.5; // right edge of the field
+
+        let cortex = foveation.cortical_map(&map, 32, 16);
+        let column_of = |value: f32| {
+            cortex
+                .iter()
+                .find_map(|row| row.iter().position(|&v| v == value))
+                .unwrap()
+        };
+        assert!(column_of(1.0) < column_of(0.5));
+    }
+
+    #[test]
+    fn test_cortical_grid_has_one_pixel_per_block() {
+        let foveation = Foveation::centered(256, 256);
+        let grid = foveation.cortical_grid(256, 256, 1.0);
+        let (width, height) = grid.dimensions();
+        let blocks = foveation.sampling_blocks(256, 256, 1.0).len();
+
+        assert_eq!(grid.field_dimensions(), (256, 256));
+        assert!(width * height < 256 * 256 / 8);
+        assert!(width * height > blocks / 4 && width * height < blocks * 4);
+    }
+
+    #[test]
+    fn test_resample_keeps_dense_values() {
+        let foveation = Foveation::centered(64, 64);
+        let grid = foveation.cortical_grid(64, 64, 1.0);
+        let uniform = grid.resample(&vec![vec![-0.5; 64]; 64]);
+        assert!(uniform.iter().flatten().all(|&v| (v + 0.5).abs() < 1e-5));
+
+        // A gradient across the field survives the resampling
+        let gradient: Vec<Vec<f32>> = (0..64).map(|_| (0..64).map(|x| x as f32).collect()).collect();
+        let cortex = grid.resample(&gradient);
+        let value_at = |x: f32, y: f32| {
+            let (column, row) = grid.cortical_pixel(x, y);
+            cortex[row][column]
+        };
+        assert!(value_at(56.0, 32.0) > value_at(8.0, 32.0) + 30.0);
+    }
+
+    #[test]
+    fn test_field_orientation() {
+        let foveation = Foveation::centered(512, 512);
+        let grid = foveation.cortical_grid(512, 512, 1.0);
+        let (width, _) = grid.dimensions();
+
+        // Far out on the right horizontal meridian the map preserves orientation
+        let (column, row) = grid.cortical_pixel(500.0, 256.0);
+        assert!(column > width / 2);
+        for degrees in [0.0, 90.0] {
+            let field = grid.field_orientation(column, row, degrees);
+            assert!((field - degrees).abs() < 5.0, "{} → {}", degrees, field);
+        }
+
+        // Below fixation the map is turned by a quarter turn: a row runs vertically
+        let (column, row) = grid.cortical_pixel(256.0, 500.0);
+        let field = grid.field_orientation(column, row, 0.0);
+        assert!((field - 90.0).abs() < 5.0, "{}", field);
+    }
+}
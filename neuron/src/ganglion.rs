@@ -1,5 +1,48 @@
 //! Ganglion cells - Edge and contrast detection through center-surround receptive fields
 
+use crate::amacrine::{AmacrineLayer, MotionDirection, StarburstType};
+use crate::convolution::{gaussian_blur_at, GaussianKernel};
+use crate::foveation::{Foveation, SamplingBlock};
+use crate::melanopsin::MelanopsinCell;
+use crate::photopigment::ConeType;
+use crate::temporal::{relaxation_rate, DEFAULT_FRAME_INTERVAL_MS};
+use std::f32::consts::SQRT_2;
+
+/// Strength of starburst inhibition relative to bipolar excitation
+const STARBURST_INHIBITION_GAIN: f32 = 2.0;
+
+/// Amplification of the center-surround difference into a firing rate
+const DEFAULT_CONTRAST_GAIN: f32 = 500.0;
+
+/// Pooled contrast (mean |center - surround| of neighbouring cells) at
+/// which contrast gain control leaves the gain unchanged
+const REFERENCE_CONTRAST: f32 = 0.1;
+
+/// Pooled contrast that halves the gain relative to a contrast-free scene
+const CONTRAST_SEMI_SATURATION: f32 = 0.05;
+
+/// Time constant with which the contrast estimate rises towards a higher
+/// pooled contrast (ms)
+const CONTRAST_ADAPTATION_TIME_CONSTANT_MS: f32 = 48.0;
+
+/// Time constant with which the contrast estimate falls towards a lower
+/// pooled contrast (ms); gain recovers more slowly than it is reduced
+const CONTRAST_RECOVERY_TIME_CONSTANT_MS: f32 = 650.0;
+
+/// Width of the contrast pool relative to the surround sigma
+const CONTRAST_POOL_SCALE: f32 = 2.0;
+
+/// Midget cells: small, densely packed receptive fields
+const MIDGET_SPACING: usize = 4;
+const MIDGET_CENTER_RADIUS: f32 = 1.5;
+const MIDGET_SURROUND_RADIUS: f32 = 4.0;
+
+/// Parasol cells: receptive fields about twice the midget size, higher contrast gain
+const PARASOL_SPACING: usize = 8;
+const PARASOL_CENTER_RADIUS: f32 = 3.0;
+const PARASOL_SURROUND_RADIUS: f32 = 8.0;
+const PARASOL_CONTRAST_GAIN: f32 = 1500.0;
+
 /// Type of ganglion cell response
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum GanglionType {
@@ -7,6 +50,108 @@ pub enum GanglionType {
     OnCenter,
     /// OFF-center: Responds to dark in center, inhibited by dark in surround
     OffCenter,
+    /// ON-OFF direction-selective: responds to light and dark edges moving
+    /// in its preferred direction, vetoed by starburst amacrine inhibition
+    /// for motion in the opposite (null) direction
+    OnOffDirectionSelective(MotionDirection),
+    /// Red-green midget cell: L-cone center minus M-cone surround (L−M)
+    RedOnGreenOff,
+    /// Green-red midget cell: M-cone center minus L-cone surround (M−L)
+    GreenOnRedOff,
+    /// Small bistratified blue-yellow cell: S-cones minus L+M cones (S−(L+M))
+    BlueOnYellowOff,
+    /// Intrinsically photosensitive cell (ipRGC): melanopsin gives it a
+    /// slow, sustained response peaking near 480 nm (see `melanopsin`).
+    /// It is driven by light through `compute_melanopsin_response` and
+    /// ignores image and bipolar input.
+    Melanopsin,
+}
+
+impl GanglionType {
+    /// Returns the cone classes feeding the center and the surround of a
+    /// colour-opponent cell, or None for luminance cells
+    pub fn cone_inputs(&self) -> Option<(&'static [ConeType], &'static [ConeType])> {
+        match self {
+            Self::RedOnGreenOff => Some((&[ConeType::L], &[ConeType::M])),
+            Self::GreenOnRedOff => Some((&[ConeType::M], &[ConeType::L])),
+            Self::BlueOnYellowOff => Some((&[ConeType::S], &[ConeType::L, ConeType::M])),
+            _ => None,
+        }
+    }
+
+    /// Returns whether this is a colour-opponent type
+    pub fn is_color_opponent(&self) -> bool {
+        self.cone_inputs().is_some()
+    }
+
+    /// Returns whether the cell responds to light without photoreceptor input
+    pub fn is_intrinsically_photosensitive(&self) -> bool {
+        matches!(self, Self::Melanopsin)
+    }
+}
+
+/// Difference-of-Gaussians (DoG) receptive field profile
+///
+/// The center and surround are normalized Gaussians, so with a surround
+/// strength of 1.0 a uniform field produces no response. Luminance cells
+/// respond with Center - strength × Surround.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DogProfile {
+    /// Standard deviation of the center Gaussian (pixels)
+    pub center_sigma: f32,
+    /// Surround sigma divided by center sigma
+    pub sigma_ratio: f32,
+    /// Weight of the surround relative to the center (1.0 = balanced)
+    pub surround_strength: f32,
+}
+
+impl DogProfile {
+    /// Creates a new DoG profile
+    ///
+    /// # Arguments
+    /// * `center_sigma` - Standard deviation of the center (pixels)
+    /// * `sigma_ratio` - Surround sigma divided by center sigma
+    /// * `surround_strength` - Weight of the surround (1.0 = balanced)
+    pub fn new(center_sigma: f32, sigma_ratio: f32, surround_strength: f32) -> Self {
+        Self {
+            center_sigma: center_sigma.max(0.0),
+            sigma_ratio: sigma_ratio.max(1.0),
+            surround_strength: surround_strength.max(0.0),
+        }
+    }
+
+    /// Creates a balanced profile matching disc-shaped center and surround
+    ///
+    /// Each sigma is the radius divided by √2, so the effective area of the
+    /// Gaussian (2πσ²) equals the area of the disc it replaces (πr²).
+    ///
+    /// # Arguments
+    /// * `center_radius` - Radius of center region
+    /// * `surround_radius` - Radius of surround region
+    pub fn from_radii(center_radius: f32, surround_radius: f32) -> Self {
+        Self::new(
+            center_radius / SQRT_2,
+            surround_radius / center_radius.max(f32::EPSILON),
+            1.0,
+        )
+    }
+
+    /// Returns a copy with a different surround-to-center sigma ratio
+    pub fn with_sigma_ratio(mut self, sigma_ratio: f32) -> Self {
+        self.sigma_ratio = sigma_ratio.max(1.0);
+        self
+    }
+
+    /// Returns a copy with a different surround strength
+    pub fn with_surround_strength(mut self, surround_strength: f32) -> Self {
+        self.surround_strength = surround_strength.max(0.0);
+        self
+    }
+
+    /// Returns the standard deviation of the surround Gaussian (pixels)
+    pub fn surround_sigma(&self) -> f32 {
+        self.center_sigma * self.sigma_ratio
+    }
 }
 
 /// Ganglion cell with center-surround receptive field
@@ -20,16 +165,28 @@ pub struct GanglionCell {
     y: usize,
     center_radius: f32,
     surround_radius: f32,
+    profile: DogProfile, // Weighting used by ON/OFF-center cells
     
     // Response state
     center_activation: f32,
     surround_activation: f32,
+    response: f32,    // Center-surround difference for this cell's polarity
+    contrast_gain: f32,
+    contrast_estimate: f32, // Pooled local contrast the gain is adapted to
+    frame_interval_ms: f32, // Time between successive adaptation steps
+    response_gain: f32, // Effective gain when the response was computed
     output_rate: f32, // Firing rate in Hz
+    melanopsin: Option<MelanopsinCell>, // Phototransduction of ipRGCs
 }
 
 impl GanglionCell {
     /// Creates a new ganglion cell
     ///
+    /// ON- and OFF-center cells weight their receptive field with a
+    /// balanced DoG profile derived from the radii (see
+    /// `DogProfile::from_radii`); direction-selective and colour-opponent
+    /// cells sample the center disc and surround disc directly.
+    ///
     /// # Arguments
     /// * `id` - Unique identifier
     /// * `cell_type` - ON-center or OFF-center
@@ -51,9 +208,16 @@ impl GanglionCell {
             y,
             center_radius,
             surround_radius,
+            profile: DogProfile::from_radii(center_radius, surround_radius),
             center_activation: 0.0,
             surround_activation: 0.0,
+            response: 0.0,
+            contrast_gain: DEFAULT_CONTRAST_GAIN,
+            contrast_estimate: REFERENCE_CONTRAST,
+            frame_interval_ms: DEFAULT_FRAME_INTERVAL_MS,
+            response_gain: DEFAULT_CONTRAST_GAIN,
             output_rate: 0.0,
+            melanopsin: (cell_type == GanglionType::Melanopsin).then(|| MelanopsinCell::new(id, x, y)),
         }
     }
 
@@ -72,24 +236,370 @@ impl GanglionCell {
         (self.x, self.y)
     }
 
+    /// Returns the radius of the receptive field center
+    pub fn center_radius(&self) -> f32 {
+        self.center_radius
+    }
+
+    /// Returns the radius of the receptive field surround
+    pub fn surround_radius(&self) -> f32 {
+        self.surround_radius
+    }
+
+    /// Returns the DoG receptive field profile
+    pub fn profile(&self) -> DogProfile {
+        self.profile
+    }
+
+    /// Sets the DoG receptive field profile
+    pub fn set_profile(&mut self, profile: DogProfile) {
+        self.profile = profile;
+    }
+
+    /// Returns the amplification from center-surround difference to firing rate
+    pub fn contrast_gain(&self) -> f32 {
+        self.contrast_gain
+    }
+
+    /// Sets the amplification from center-surround difference to firing rate
+    pub fn set_contrast_gain(&mut self, contrast_gain: f32) {
+        self.contrast_gain = contrast_gain.max(0.0);
+    }
+
+    /// Sets the time between successive calls of `adapt_contrast` (ms)
+    pub fn set_frame_interval_ms(&mut self, frame_interval_ms: f32) {
+        self.frame_interval_ms = frame_interval_ms.max(0.0);
+    }
+
+    /// Returns the pooled local contrast the gain is adapted to
+    pub fn contrast_estimate(&self) -> f32 {
+        self.contrast_estimate
+    }
+
+    /// Returns the contrast gain after contrast gain control
+    ///
+    /// Divisive: the gain falls as the pooled contrast around the cell
+    /// rises, and equals `contrast_gain` at the reference contrast.
+    pub fn effective_gain(&self) -> f32 {
+        self.contrast_gain * (REFERENCE_CONTRAST + CONTRAST_SEMI_SATURATION)
+            / (self.contrast_estimate + CONTRAST_SEMI_SATURATION)
+    }
+
+    /// Converts the response into a firing rate at the current effective gain
+    fn update_output_rate(&mut self) {
+        self.response_gain = self.effective_gain();
+        self.output_rate = (self.response * self.response_gain).max(0.0);
+    }
+
+    /// Adapts the contrast gain control to the contrast pooled around the cell
+    ///
+    /// The new gain applies from the next response on, so the cell
+    /// adapts over successive frames. Gain drops quickly when contrast
+    /// rises but recovers slowly when it falls.
+    ///
+    /// # Arguments
+    /// * `pooled_contrast` - Mean |center - surround| of neighbouring cells
+    pub fn adapt_contrast(&mut self, pooled_contrast: f32) {
+        let pooled_contrast = pooled_contrast.max(0.0);
+        let time_constant_ms = if pooled_contrast > self.contrast_estimate {
+            CONTRAST_ADAPTATION_TIME_CONSTANT_MS
+        } else {
+            CONTRAST_RECOVERY_TIME_CONSTANT_MS
+        };
+        let rate = relaxation_rate(time_constant_ms, self.frame_interval_ms);
+        self.contrast_estimate += (pooled_contrast - self.contrast_estimate) * rate;
+    }
+
     /// Returns the current firing rate
     pub fn firing_rate(&self) -> f32 {
         self.output_rate
     }
 
+    /// Returns the melanopsin cascade of an ipRGC (None for other types)
+    pub fn melanopsin_cell(&self) -> Option<&MelanopsinCell> {
+        self.melanopsin.as_ref()
+    }
+
+    /// Advances the intrinsic light response of an ipRGC
+    ///
+    /// The cell pools melanopsin excitation over its center disc and
+    /// fires at the rate of its `MelanopsinCell`. Other cell types are
+    /// left unchanged.
+    ///
+    /// # Arguments
+    /// * `excitation` - Melanopsin excitation at each pixel
+    /// * `dt_ms` - Time step (ms)
+    pub fn compute_melanopsin_response(&mut self, excitation: &[Vec<f32>], dt_ms: f32) {
+        if self.melanopsin.is_none() || excitation.is_empty() {
+            return;
+        }
+        self.sample_receptive_field(excitation);
+        if let Some(cell) = &mut self.melanopsin {
+            cell.step(self.center_activation, dt_ms);
+            self.response = cell.response();
+            self.output_rate = cell.firing_rate();
+        }
+    }
+
     /// Computes the response to an input image region
     ///
     /// # Arguments
     /// * `image` - 2D array of pixel intensities (0.0 to 1.0)
     ///
-    /// The center-surround antagonism:
-    /// - ON-center: Response = Center - Surround
-    /// - OFF-center: Response = Surround - Center
+    /// The center-surround antagonism (k = surround strength):
+    /// - ON-center: Response = Center - k × Surround
+    /// - OFF-center: Response = k × Surround - Center
+    ///
+    /// This evaluates the DoG directly at the cell; `GanglionLayer` uses
+    /// a faster separable convolution that gives the same result.
+    /// ipRGCs ignore the image (see `compute_melanopsin_response`).
     pub fn compute_response(&mut self, image: &[Vec<f32>]) {
-        if image.is_empty() {
+        if image.is_empty() || self.melanopsin.is_some() {
+            return;
+        }
+
+        if self.is_luminance() {
+            let (center, surround) = self.dog_means(image);
+            self.set_dog_response(center, surround, self.cell_type == GanglionType::OffCenter);
+            return;
+        }
+
+        self.sample_receptive_field(image);
+        self.response = match self.cell_type {
+            GanglionType::OffCenter => self.surround_activation - self.center_activation,
+            GanglionType::OnOffDirectionSelective(_) => {
+                (self.center_activation - self.surround_activation).abs()
+            }
+            // Colour-opponent cells see an achromatic image as luminance
+            _ => self.center_activation - self.surround_activation,
+        };
+
+        // Convert to firing rate (rectified and amplified for better detection)
+        // Amplify (500 by default) to make edges more visible
+        self.update_output_rate();
+    }
+
+    /// Computes the response to bipolar cell input
+    ///
+    /// ON-center cells are excited by ON bipolar cells and OFF-center
+    /// cells by OFF bipolar cells, so both compute Center - k × Surround
+    /// of the map of their own polarity.
+    ///
+    /// # Arguments
+    /// * `on_bipolar` - ON bipolar cell output at each pixel (0.0 to 1.0)
+    /// * `off_bipolar` - OFF bipolar cell output at each pixel (0.0 to 1.0)
+    pub fn compute_bipolar_response(&mut self, on_bipolar: &[Vec<f32>], off_bipolar: &[Vec<f32>]) {
+        let input = match self.cell_type {
+            // ipRGCs are driven by melanopsin, not by bipolar cells
+            GanglionType::Melanopsin => return,
+            GanglionType::OffCenter => off_bipolar,
+            GanglionType::OnOffDirectionSelective(_) => {
+                let on = self.center_surround_difference(on_bipolar);
+                let off = self.center_surround_difference(off_bipolar);
+                self.response = on.max(off);
+                self.update_output_rate();
+                return;
+            }
+            _ => on_bipolar,
+        };
+        if input.is_empty() {
             return;
         }
 
+        if self.is_luminance() {
+            let (center, surround) = self.dog_means(input);
+            self.set_dog_response(center, surround, false);
+            return;
+        }
+
+        self.response = self.center_surround_difference(input);
+        self.update_output_rate();
+    }
+
+    /// Returns whether the cell is an ON- or OFF-center (DoG) cell
+    fn is_luminance(&self) -> bool {
+        matches!(self.cell_type, GanglionType::OnCenter | GanglionType::OffCenter)
+    }
+
+    /// Gaussian-weighted center and surround means, evaluated per pixel
+    fn dog_means(&self, image: &[Vec<f32>]) -> (f32, f32) {
+        let height = image.len() as i32;
+        let width = image[0].len() as i32;
+        let center = GaussianKernel::new(self.profile.center_sigma);
+        let surround = GaussianKernel::new(self.profile.surround_sigma());
+        let reach = center.radius().max(surround.radius()) as i32;
+
+        let (mut center_sum, mut center_weight) = (0.0, 0.0);
+        let (mut surround_sum, mut surround_weight) = (0.0, 0.0);
+        for dy in -reach..=reach {
+            for dx in -reach..=reach {
+                let px = self.x as i32 + dx;
+                let py = self.y as i32 + dy;
+                if px < 0 || py < 0 || px >= width || py >= height {
+                    continue;
+                }
+
+                let intensity = image[py as usize][px as usize];
+                let wc = center.weight(dx) * center.weight(dy);
+                let ws = surround.weight(dx) * surround.weight(dy);
+                center_sum += wc * intensity;
+                center_weight += wc;
+                surround_sum += ws * intensity;
+                surround_weight += ws;
+            }
+        }
+
+        (
+            if center_weight > 0.0 { center_sum / center_weight } else { 0.0 },
+            if surround_weight > 0.0 { surround_sum / surround_weight } else { 0.0 },
+        )
+    }
+
+    /// Stores a DoG response from center and surround means
+    ///
+    /// `inverted` gives Surround - Center, used by OFF-center cells that
+    /// read the same image as ON-center cells.
+    fn set_dog_response(&mut self, center: f32, surround: f32, inverted: bool) {
+        self.center_activation = center;
+        self.surround_activation = surround;
+        let difference = center - self.profile.surround_strength * surround;
+        self.response = if inverted { -difference } else { difference };
+        self.update_output_rate();
+    }
+
+    /// Computes the response of a direction-selective cell to one frame
+    ///
+    /// Excitation comes from transient ON and OFF bipolar cells in the
+    /// receptive field center; each is vetoed by starburst inhibition of
+    /// the same polarity. Cells of other types are left unchanged.
+    ///
+    /// # Arguments
+    /// * `on_transient` - ON transient bipolar output at each pixel
+    /// * `off_transient` - OFF transient bipolar output at each pixel
+    /// * `amacrine` - Starburst amacrine layer updated with the same frame
+    pub fn compute_direction_selective_response(
+        &mut self,
+        on_transient: &[Vec<f32>],
+        off_transient: &[Vec<f32>],
+        amacrine: &AmacrineLayer,
+    ) {
+        let GanglionType::OnOffDirectionSelective(preferred) = self.cell_type else {
+            return;
+        };
+        if on_transient.is_empty() || off_transient.is_empty() {
+            return;
+        }
+
+        let height = on_transient.len() as i32;
+        let width = on_transient[0].len() as i32;
+        let reach = self.center_radius as i32;
+
+        // Bipolar excitation and starburst inhibition pooled over the center
+        let mut excitation = [0.0; 2];
+        let mut inhibition = [0.0; 2];
+        let mut count = 0;
+        for dy in -reach..=reach {
+            for dx in -reach..=reach {
+                let px = self.x as i32 + dx;
+                let py = self.y as i32 + dy;
+                if px < 0 || py < 0 || px >= width || py >= height {
+                    continue;
+                }
+                if ((dx * dx + dy * dy) as f32).sqrt() > self.center_radius {
+                    continue;
+                }
+
+                let (px, py) = (px as usize, py as usize);
+                excitation[0] += on_transient[py][px];
+                excitation[1] += off_transient[py][px];
+                inhibition[0] += amacrine.inhibition_onto(px, py, preferred, StarburstType::On);
+                inhibition[1] += amacrine.inhibition_onto(px, py, preferred, StarburstType::Off);
+                count += 1;
+            }
+        }
+
+        let mut response = 0.0;
+        if count > 0 {
+            for (e, i) in excitation.iter().zip(&inhibition) {
+                response += ((e - STARBURST_INHIBITION_GAIN * i) / count as f32).max(0.0);
+            }
+            self.center_activation = (excitation[0] + excitation[1]) / count as f32;
+        }
+
+        self.response = response;
+        self.update_output_rate();
+    }
+
+    /// Computes the response of a colour-opponent cell to cone signals
+    ///
+    /// The center averages cones of the center classes within the center
+    /// radius; the surround averages cones of the surround classes over
+    /// the whole receptive field, so a uniform field still drives the
+    /// cell when the two classes are excited differently. Luminance cells
+    /// are left unchanged.
+    ///
+    /// # Arguments
+    /// * `cone_responses` - Cone output at each pixel (0.0 to 1.0)
+    /// * `cone_types` - Cone class at each pixel
+    pub fn compute_opponent_response(&mut self, cone_responses: &[Vec<f32>], cone_types: &[Vec<ConeType>]) {
+        let Some((center_types, surround_types)) = self.cell_type.cone_inputs() else {
+            return;
+        };
+        if cone_responses.is_empty() {
+            return;
+        }
+
+        let height = cone_responses.len() as i32;
+        let width = cone_responses[0].len() as i32;
+        let reach = self.surround_radius as i32;
+
+        let (mut center_sum, mut center_count) = (0.0, 0);
+        let (mut surround_sum, mut surround_count) = (0.0, 0);
+        for dy in -reach..=reach {
+            for dx in -reach..=reach {
+                let px = self.x as i32 + dx;
+                let py = self.y as i32 + dy;
+                if px < 0 || py < 0 || px >= width || py >= height {
+                    continue;
+                }
+
+                let distance = ((dx * dx + dy * dy) as f32).sqrt();
+                let cone_type = cone_types[py as usize][px as usize];
+                let response = cone_responses[py as usize][px as usize];
+                if distance <= self.center_radius && center_types.contains(&cone_type) {
+                    center_sum += response;
+                    center_count += 1;
+                }
+                if distance <= self.surround_radius && surround_types.contains(&cone_type) {
+                    surround_sum += response;
+                    surround_count += 1;
+                }
+            }
+        }
+
+        if center_count == 0 || surround_count == 0 {
+            self.response = 0.0;
+            self.output_rate = 0.0;
+            return;
+        }
+
+        self.center_activation = center_sum / center_count as f32;
+        self.surround_activation = surround_sum / surround_count as f32;
+        self.response = self.center_activation - self.surround_activation;
+        self.update_output_rate();
+    }
+
+    /// Returns Center - Surround of one input map
+    fn center_surround_difference(&mut self, input: &[Vec<f32>]) -> f32 {
+        if input.is_empty() {
+            return 0.0;
+        }
+        self.sample_receptive_field(input);
+        self.center_activation - self.surround_activation
+    }
+
+    /// Averages the input over the center disc and the surround annulus
+    fn sample_receptive_field(&mut self, image: &[Vec<f32>]) {
         let height = image.len();
         let width = image[0].len();
 
@@ -132,32 +642,29 @@ impl GanglionCell {
         } else {
             0.0
         };
+    }
 
-        // Compute center-surround difference
-        let response = match self.cell_type {
-            GanglionType::OnCenter => self.center_activation - self.surround_activation,
-            GanglionType::OffCenter => self.surround_activation - self.center_activation,
-        };
-
-        // Convert to firing rate (rectified and amplified for better detection)
-        // Amplify by 500 to make edges more visible
-        self.output_rate = (response * 500.0).max(0.0);
+    /// Returns the amplified center-surround difference, keeping its sign
+    ///
+    /// Negative values mean the cell is inhibited, e.g. a red-green cell
+    /// viewing green light.
+    pub fn signed_response(&self) -> f32 {
+        self.response * self.response_gain
     }
 
     /// Returns the center-surround difference (positive = active)
     pub fn response_strength(&self) -> f32 {
-        let base_response = match self.cell_type {
-            GanglionType::OnCenter => self.center_activation - self.surround_activation,
-            GanglionType::OffCenter => self.surround_activation - self.center_activation,
-        };
         // Amplify for better visualization
-        (base_response * 500.0).max(0.0)
+        (self.response * self.response_gain).max(0.0)
     }
 }
 
 /// Layer of ganglion cells covering a visual field
 pub struct GanglionLayer {
     cells: Vec<GanglionCell>,
+    // Patch of the field each cell reports for (foveated layers only)
+    cell_blocks: Vec<SamplingBlock>,
+    spacing: usize,
     width: usize,
     height: usize,
 }
@@ -208,18 +715,473 @@ impl GanglionLayer {
 
         Self {
             cells,
+            cell_blocks: Vec::new(),
+            spacing,
+            width,
+            height,
+        }
+    }
+
+    /// Creates a layer of midget cells
+    ///
+    /// Midget cells have small receptive fields (center 1.5, surround 4.0
+    /// at spacing 4) and are driven by sustained bipolar cells. They feed
+    /// the parvocellular LGN layers and carry fine form and colour.
+    pub fn midget(width: usize, height: usize) -> Self {
+        Self::new(width, height, MIDGET_SPACING, MIDGET_CENTER_RADIUS, MIDGET_SURROUND_RADIUS)
+    }
+
+    /// Creates a layer of parasol cells
+    ///
+    /// Parasol cells have receptive fields about twice the midget size,
+    /// three times the contrast gain and are driven by transient bipolar
+    /// cells. They feed the magnocellular LGN layers and carry motion and
+    /// low-contrast luminance signals.
+    pub fn parasol(width: usize, height: usize) -> Self {
+        Self::new(width, height, PARASOL_SPACING, PARASOL_CENTER_RADIUS, PARASOL_SURROUND_RADIUS)
+            .with_contrast_gain(PARASOL_CONTRAST_GAIN)
+    }
+
+    /// Creates a foveated layer of midget cells (see `midget` and `foveated`)
+    pub fn foveated_midget(width: usize, height: usize, foveation: &Foveation) -> Self {
+        Self::foveated(width, height, foveation, MIDGET_SPACING, MIDGET_CENTER_RADIUS, MIDGET_SURROUND_RADIUS)
+    }
+
+    /// Creates a foveated layer of parasol cells (see `parasol` and `foveated`)
+    pub fn foveated_parasol(width: usize, height: usize, foveation: &Foveation) -> Self {
+        Self::foveated(width, height, foveation, PARASOL_SPACING, PARASOL_CENTER_RADIUS, PARASOL_SURROUND_RADIUS)
+            .with_contrast_gain(PARASOL_CONTRAST_GAIN)
+    }
+
+    /// Returns a copy in which every cell uses a different contrast gain
+    pub fn with_contrast_gain(mut self, contrast_gain: f32) -> Self {
+        for cell in &mut self.cells {
+            cell.set_contrast_gain(contrast_gain);
+        }
+        self
+    }
+
+    /// Sets the time between successive frames for every cell (ms)
+    pub fn set_frame_interval_ms(&mut self, frame_interval_ms: f32) {
+        for cell in &mut self.cells {
+            cell.set_frame_interval_ms(frame_interval_ms);
+        }
+    }
+
+    /// Returns the distance between cell centers (at fixation for foveated layers)
+    pub fn spacing(&self) -> usize {
+        self.spacing
+    }
+
+    /// Creates a layer whose spacing and receptive fields grow with eccentricity
+    ///
+    /// Each cell samples one of the foveation's sampling blocks; the
+    /// receptive field of each cell scales with its block, so neighbouring
+    /// cells keep overlapping coverage everywhere in the field.
+    ///
+    /// # Arguments
+    /// * `width`, `height` - Dimensions of visual field
+    /// * `foveation` - Fixation point and eccentricity scaling
+    /// * `spacing` - Distance between cell centers at fixation
+    /// * `center_radius` - Size of center region at fixation
+    /// * `surround_radius` - Size of surround region at fixation
+    pub fn foveated(
+        width: usize,
+        height: usize,
+        foveation: &Foveation,
+        spacing: usize,
+        center_radius: f32,
+        surround_radius: f32,
+    ) -> Self {
+        let mut cells = Vec::new();
+        let mut cell_blocks = Vec::new();
+        let mut id = 0;
+
+        for block in foveation.sampling_blocks(width, height, spacing as f32) {
+            // Cells sit at the block's corner, matching the uniform grid
+            let (x, y) = (block.x, block.y);
+            let scale = block.width.max(block.height).max(spacing) as f32 / spacing as f32;
+
+            for cell_type in [GanglionType::OnCenter, GanglionType::OffCenter] {
+                cells.push(GanglionCell::new(
+                    id,
+                    cell_type,
+                    x,
+                    y,
+                    center_radius * scale,
+                    surround_radius * scale,
+                ));
+                cell_blocks.push(block);
+                id += 1;
+            }
+        }
+
+        Self {
+            cells,
+            cell_blocks,
+            spacing,
+            width,
+            height,
+        }
+    }
+
+    /// Creates a layer of ON-OFF direction-selective cells
+    ///
+    /// Each location has one cell for each cardinal preferred direction.
+    ///
+    /// # Arguments
+    /// * `width`, `height` - Dimensions of visual field
+    /// * `spacing` - Distance between cell centers
+    /// * `center_radius` - Size of the excitatory receptive field
+    pub fn direction_selective(width: usize, height: usize, spacing: usize, center_radius: f32) -> Self {
+        let mut cells = Vec::new();
+        let mut id = 0;
+
+        for y in (0..height).step_by(spacing) {
+            for x in (0..width).step_by(spacing) {
+                for direction in MotionDirection::ALL {
+                    cells.push(GanglionCell::new(
+                        id,
+                        GanglionType::OnOffDirectionSelective(direction),
+                        x,
+                        y,
+                        center_radius,
+                        center_radius,
+                    ));
+                    id += 1;
+                }
+            }
+        }
+
+        Self {
+            cells,
+            cell_blocks: Vec::new(),
+            spacing,
+            width,
+            height,
+        }
+    }
+
+    /// Creates a layer of colour-opponent cells
+    ///
+    /// Each location has a red-green and a green-red midget cell with the
+    /// given receptive field, and a blue-yellow small bistratified cell
+    /// whose S-cone and L+M fields are coextensive (both use the surround
+    /// radius, since S-cones are sparse).
+    ///
+    /// # Arguments
+    /// * `width`, `height` - Dimensions of visual field
+    /// * `spacing` - Distance between cell centers
+    /// * `center_radius` - Size of midget center region
+    /// * `surround_radius` - Size of midget surround region
+    pub fn color_opponent(
+        width: usize,
+        height: usize,
+        spacing: usize,
+        center_radius: f32,
+        surround_radius: f32,
+    ) -> Self {
+        let mut cells = Vec::new();
+        let mut id = 0;
+
+        for y in (0..height).step_by(spacing) {
+            for x in (0..width).step_by(spacing) {
+                for (cell_type, center) in [
+                    (GanglionType::RedOnGreenOff, center_radius),
+                    (GanglionType::GreenOnRedOff, center_radius),
+                    (GanglionType::BlueOnYellowOff, surround_radius),
+                ] {
+                    cells.push(GanglionCell::new(id, cell_type, x, y, center, surround_radius));
+                    id += 1;
+                }
+            }
+        }
+
+        Self {
+            cells,
+            cell_blocks: Vec::new(),
+            spacing,
             width,
             height,
         }
     }
 
+    /// Returns a copy in which every cell uses a different sigma ratio
+    pub fn with_sigma_ratio(mut self, sigma_ratio: f32) -> Self {
+        for cell in &mut self.cells {
+            cell.set_profile(cell.profile().with_sigma_ratio(sigma_ratio));
+        }
+        self
+    }
+
+    /// Returns a copy in which every cell uses a different surround strength
+    pub fn with_surround_strength(mut self, surround_strength: f32) -> Self {
+        for cell in &mut self.cells {
+            cell.set_profile(cell.profile().with_surround_strength(surround_strength));
+        }
+        self
+    }
+
     /// Processes an entire image through all ganglion cells
+    ///
+    /// ON- and OFF-center cells are evaluated by separable Gaussian
+    /// convolution at the cell positions (see `GanglionCell::compute_response`
+    /// for the equivalent per-cell computation; `cargo bench --bench
+    /// ganglion_convolution` checks that the two agree and times them).
     pub fn process_image(&mut self, image: &[Vec<f32>]) {
-        for cell in &mut self.cells {
+        self.process_dog(image, image, true);
+        for cell in self.cells.iter_mut().filter(|c| !c.is_luminance()) {
             cell.compute_response(image);
         }
     }
 
+    /// Evaluates the DoG of every ON/OFF-center cell by convolution
+    ///
+    /// Cells sharing a profile and an input are convolved together.
+    ///
+    /// # Arguments
+    /// * `on_input`, `off_input` - Maps read by ON- and OFF-center cells
+    /// * `invert_off` - Whether OFF-center cells compute Surround - Center
+    ///   (when both read the same image)
+    fn process_dog(&mut self, on_input: &[Vec<f32>], off_input: &[Vec<f32>], invert_off: bool) {
+        if on_input.is_empty() || off_input.is_empty() {
+            return;
+        }
+
+        // (reads OFF map, center sigma, surround sigma) -> cell indices
+        let mut groups: Vec<((bool, f32, f32), Vec<usize>)> = Vec::new();
+        for (index, cell) in self.cells.iter().enumerate().filter(|(_, c)| c.is_luminance()) {
+            let reads_off = cell.cell_type() == GanglionType::OffCenter && !invert_off;
+            let key = (reads_off, cell.profile.center_sigma, cell.profile.surround_sigma());
+            match groups.iter_mut().find(|(k, _)| *k == key) {
+                Some((_, indices)) => indices.push(index),
+                None => groups.push((key, vec![index])),
+            }
+        }
+
+        for ((reads_off, center_sigma, surround_sigma), indices) in groups {
+            let input = if reads_off { off_input } else { on_input };
+            let points: Vec<(usize, usize)> = indices.iter().map(|&i| self.cells[i].position()).collect();
+            let centers = gaussian_blur_at(input, &GaussianKernel::new(center_sigma), &points);
+            let surrounds = gaussian_blur_at(input, &GaussianKernel::new(surround_sigma), &points);
+
+            for ((&index, center), surround) in indices.iter().zip(centers).zip(surrounds) {
+                let cell = &mut self.cells[index];
+                let inverted = invert_off && cell.cell_type() == GanglionType::OffCenter;
+                cell.set_dog_response(center, surround, inverted);
+            }
+
+            self.adapt_contrast_gain(&indices, &points, surround_sigma);
+        }
+    }
+
+    /// Contrast gain control: adapts each cell to the contrast around it
+    ///
+    /// The pooled contrast is the Gaussian-weighted mean |center - surround|
+    /// of the cells in the group, over a region twice the surround.
+    fn adapt_contrast_gain(&mut self, indices: &[usize], points: &[(usize, usize)], surround_sigma: f32) {
+        let mut magnitudes = vec![vec![0.0; self.width]; self.height];
+        let mut presence = vec![vec![0.0; self.width]; self.height];
+        for (&index, &(x, y)) in indices.iter().zip(points) {
+            if x < self.width && y < self.height {
+                magnitudes[y][x] += self.cells[index].response.abs();
+                presence[y][x] += 1.0;
+            }
+        }
+
+        let pool = GaussianKernel::new(surround_sigma * CONTRAST_POOL_SCALE);
+        let pooled_magnitudes = gaussian_blur_at(&magnitudes, &pool, points);
+        let pooled_presence = gaussian_blur_at(&presence, &pool, points);
+        for ((&index, magnitude), presence) in indices.iter().zip(pooled_magnitudes).zip(pooled_presence) {
+            if presence > 0.0 {
+                self.cells[index].adapt_contrast(magnitude / presence);
+            }
+        }
+    }
+
+    /// Processes bipolar cell output through all ganglion cells
+    ///
+    /// # Arguments
+    /// * `on_bipolar` - ON bipolar cell output at each pixel
+    /// * `off_bipolar` - OFF bipolar cell output at each pixel
+    pub fn process_bipolar(&mut self, on_bipolar: &[Vec<f32>], off_bipolar: &[Vec<f32>]) {
+        self.process_dog(on_bipolar, off_bipolar, false);
+        for cell in self.cells.iter_mut().filter(|c| !c.is_luminance()) {
+            cell.compute_bipolar_response(on_bipolar, off_bipolar);
+        }
+    }
+
+    /// Processes one frame through the direction-selective cells
+    ///
+    /// # Arguments
+    /// * `on_transient` - ON transient bipolar output at each pixel
+    /// * `off_transient` - OFF transient bipolar output at each pixel
+    /// * `amacrine` - Starburst amacrine layer updated with the same frame
+    pub fn process_direction_selective(
+        &mut self,
+        on_transient: &[Vec<f32>],
+        off_transient: &[Vec<f32>],
+        amacrine: &AmacrineLayer,
+    ) {
+        for cell in &mut self.cells {
+            cell.compute_direction_selective_response(on_transient, off_transient, amacrine);
+        }
+    }
+
+    /// Returns the summed firing rate of direction-selective cells
+    /// for each preferred direction, indexed by `MotionDirection::index`
+    pub fn direction_responses(&self) -> [f32; 4] {
+        let mut responses = [0.0; 4];
+        for cell in &self.cells {
+            if let GanglionType::OnOffDirectionSelective(direction) = cell.cell_type() {
+                responses[direction.index()] += cell.firing_rate();
+            }
+        }
+        responses
+    }
+
+    /// Processes cone signals through the colour-opponent cells
+    ///
+    /// # Arguments
+    /// * `cone_responses` - Cone output at each pixel (0.0 to 1.0)
+    /// * `cone_types` - Cone class at each pixel
+    pub fn process_cones(&mut self, cone_responses: &[Vec<f32>], cone_types: &[Vec<ConeType>]) {
+        for cell in &mut self.cells {
+            cell.compute_opponent_response(cone_responses, cone_types);
+        }
+    }
+
+    /// Creates a red-green opponent map (positive = red, negative = green)
+    ///
+    /// Each location holds the red-green minus the green-red cell
+    /// response, spread over the patch between cells.
+    pub fn red_green_map(&self) -> Vec<Vec<f32>> {
+        self.opponent_map(|cell| match cell.cell_type() {
+            GanglionType::RedOnGreenOff => Some(cell.response_strength()),
+            GanglionType::GreenOnRedOff => Some(-cell.response_strength()),
+            _ => None,
+        })
+    }
+
+    /// Creates a blue-yellow opponent map (positive = blue, negative = yellow)
+    pub fn blue_yellow_map(&self) -> Vec<Vec<f32>> {
+        self.opponent_map(|cell| match cell.cell_type() {
+            GanglionType::BlueOnYellowOff => Some(cell.signed_response()),
+            _ => None,
+        })
+    }
+
+    /// Sums a per-cell contribution over the patch each cell covers
+    fn opponent_map(&self, contribution: impl Fn(&GanglionCell) -> Option<f32>) -> Vec<Vec<f32>> {
+        let mut map = vec![vec![0.0; self.width]; self.height];
+        let step = self.spacing.max(1);
+
+        for cell in &self.cells {
+            if let Some(value) = contribution(cell) {
+                let (x, y) = cell.position();
+                for row in map.iter_mut().skip(y).take(step) {
+                    for pixel in row.iter_mut().skip(x).take(step) {
+                        *pixel += value;
+                    }
+                }
+            }
+        }
+
+        map
+    }
+
+    /// Creates an edge map resampled at a finer step
+    ///
+    /// Each cell's response is repeated over the patch between it and its
+    /// neighbours at `step` pixel intervals. This lets a sparse layer (e.g.
+    /// parasol cells) drive stages tuned to a denser one.
+    ///
+    /// # Arguments
+    /// * `step` - Spacing of the output samples (pixels)
+    pub fn create_resampled_edge_map(&self, step: usize) -> Vec<Vec<f32>> {
+        if !self.cell_blocks.is_empty() {
+            return self.create_edge_map();
+        }
+
+        let mut edge_map = vec![vec![0.0; self.width]; self.height];
+        let step = step.max(1);
+        for cell in &self.cells {
+            let (x, y) = cell.position();
+            let response = cell.response_strength().abs();
+            for py in (y..(y + self.spacing).min(self.height)).step_by(step) {
+                for px in (x..(x + self.spacing).min(self.width)).step_by(step) {
+                    edge_map[py][px] += response;
+                }
+            }
+        }
+
+        edge_map
+    }
+
+    /// Creates a dense map of the firing rates of one cell type
+    ///
+    /// Unlike `create_edge_map`, ON-center and OFF-center cells are kept
+    /// apart, so the maps of the two types together carry the sign of
+    /// contrast. Between cells the map is interpolated bilinearly over the
+    /// cell lattice; foveated layers fill each cell's sampling block, so
+    /// every pixel carries a value.
+    ///
+    /// # Arguments
+    /// * `cell_type` - Cell type whose firing rates are mapped
+    pub fn create_rate_map(&self, cell_type: GanglionType) -> Vec<Vec<f32>> {
+        let rate = |cell: &GanglionCell| {
+            if cell.cell_type() == cell_type {
+                cell.firing_rate()
+            } else {
+                0.0
+            }
+        };
+        let mut map = vec![vec![0.0; self.width]; self.height];
+
+        if !self.cell_blocks.is_empty() {
+            for (cell, block) in self.cells.iter().zip(&self.cell_blocks) {
+                let value = rate(cell);
+                for row in map.iter_mut().skip(block.y).take(block.height) {
+                    for pixel in row.iter_mut().skip(block.x).take(block.width) {
+                        *pixel += value;
+                    }
+                }
+            }
+            return map;
+        }
+
+        // Rate at each node of the cell lattice
+        let step = self.spacing.max(1);
+        let (columns, rows) = (self.width.div_ceil(step), self.height.div_ceil(step));
+        if columns == 0 || rows == 0 {
+            return map;
+        }
+        let mut nodes = vec![vec![0.0; columns]; rows];
+        for cell in &self.cells {
+            let (x, y) = cell.position();
+            if let Some(node) = nodes.get_mut(y / step).and_then(|row| row.get_mut(x / step)) {
+                *node += rate(cell);
+            }
+        }
+
+        let lattice_position = |pixel: usize, count: usize| {
+            let position = pixel as f32 / step as f32;
+            let lower = (position.floor() as usize).min(count - 1);
+            (lower, (lower + 1).min(count - 1), position - lower as f32)
+        };
+        for (y, row) in map.iter_mut().enumerate() {
+            let (y0, y1, ty) = lattice_position(y, rows);
+            for (x, pixel) in row.iter_mut().enumerate() {
+                let (x0, x1, tx) = lattice_position(x, columns);
+                let top = nodes[y0][x0] * (1.0 - tx) + nodes[y0][x1] * tx;
+                let bottom = nodes[y1][x0] * (1.0 - tx) + nodes[y1][x1] * tx;
+                *pixel = top * (1.0 - ty) + bottom * ty;
+            }
+        }
+
+        map
+    }
+
     /// Returns all cells
     pub fn cells(&self) -> &[GanglionCell] {
         &self.cells
@@ -234,9 +1196,25 @@ impl GanglionLayer {
     }
 
     /// Creates an edge map from ganglion responses
+    ///
+    /// Foveated layers repeat each cell's response over its sampling block
+    /// at the foveal spacing, so the map has the same density everywhere.
     pub fn create_edge_map(&self) -> Vec<Vec<f32>> {
         let mut edge_map = vec![vec![0.0; self.width]; self.height];
 
+        if !self.cell_blocks.is_empty() {
+            let step = self.spacing.max(1);
+            for (cell, block) in self.cells.iter().zip(&self.cell_blocks) {
+                let response = cell.response_strength().abs();
+                for y in (block.y..block.y + block.height).step_by(step) {
+                    for x in (block.x..block.x + block.width).step_by(step) {
+                        edge_map[y][x] += response;
+                    }
+                }
+            }
+            return edge_map;
+        }
+
         for cell in &self.cells {
             let (x, y) = cell.position();
             if x < self.width && y < self.height {
@@ -325,6 +1303,25 @@ mod tests {
         assert_eq!(on_cells.len(), off_cells.len());
     }
 
+    #[test]
+    fn test_foveated_layer() {
+        let foveation = Foveation::new(10.0, 10.0);
+        let uniform = GanglionLayer::new(128, 128, 4, 1.5, 4.0);
+        let foveated = GanglionLayer::foveated(128, 128, &foveation, 4, 1.5, 4.0);
+
+        assert!(foveated.cells().len() < uniform.cells().len() / 4);
+
+        let radius_near = |x: usize, y: usize| {
+            foveated
+                .cells()
+                .iter()
+                .min_by_key(|c| c.position().0.abs_diff(x) + c.position().1.abs_diff(y))
+                .unwrap()
+                .center_radius()
+        };
+        assert!(radius_near(120, 120) > radius_near(10, 10) * 2.0);
+    }
+
     #[test]
     fn test_edge_detection() {
         let mut layer = GanglionLayer::new(20, 20, 3, 1.5, 4.0);
@@ -337,4 +1334,227 @@ mod tests {
         let total_response: f32 = edge_map.iter().flatten().sum();
         assert!(total_response > 0.0);
     }
+
+    #[test]
+    fn test_bipolar_input_matches_image_input() {
+        let edge_image = create_edge_image(20, 20);
+        let off_bipolar: Vec<Vec<f32>> = edge_image
+            .iter()
+            .map(|row| row.iter().map(|&v| 1.0 - v).collect())
+            .collect();
+
+        let mut image_layer = GanglionLayer::new(20, 20, 3, 1.5, 4.0);
+        image_layer.process_image(&edge_image);
+        let mut bipolar_layer = GanglionLayer::new(20, 20, 3, 1.5, 4.0);
+        bipolar_layer.process_bipolar(&edge_image, &off_bipolar);
+
+        for (a, b) in image_layer.cells().iter().zip(bipolar_layer.cells()) {
+            assert!((a.response_strength() - b.response_strength()).abs() < 1e-3);
+        }
+        assert!(bipolar_layer.cells_by_type(GanglionType::OffCenter).iter().any(|c| c.firing_rate() > 0.0));
+    }
+
+    #[test]
+    fn test_direction_selective_preferred_vs_null() {
+        // ON edge stepping across a 1-row field, one frame at a time
+        let run = |frames: &[usize]| -> [f32; 4] {
+            let mut amacrine = AmacrineLayer::new(12, 1, 1, 2);
+            let mut layer = GanglionLayer::direction_selective(12, 1, 1, 0.5);
+            let dark = vec![vec![0.0; 12]; 1];
+            let mut totals = [0.0; 4];
+            for &x in frames {
+                let mut on = dark.clone();
+                on[0][x] = 1.0;
+                amacrine.process(&on, &dark);
+                layer.process_direction_selective(&on, &dark, &amacrine);
+                for (total, response) in totals.iter_mut().zip(layer.direction_responses()) {
+                    *total += response;
+                }
+            }
+            totals
+        };
+
+        let rightward = run(&[2, 4, 6, 8]);
+        let leftward = run(&[8, 6, 4, 2]);
+        let right = MotionDirection::Rightward.index();
+        let left = MotionDirection::Leftward.index();
+
+        assert!(rightward[right] > rightward[left] * 2.0);
+        assert!(leftward[left] > leftward[right] * 2.0);
+    }
+
+    #[test]
+    fn test_color_opponent_cells() {
+        // Uniform field: L-cones more excited than M-cones, S-cones silent
+        let cone_types: Vec<Vec<ConeType>> = (0..12)
+            .map(|y| {
+                (0..12)
+                    .map(|x| match (x + y) % 4 {
+                        0 => ConeType::S,
+                        1 => ConeType::M,
+                        _ => ConeType::L,
+                    })
+                    .collect()
+            })
+            .collect();
+        let reddish: Vec<Vec<f32>> = cone_types
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|t| match t {
+                        ConeType::L => 0.8,
+                        ConeType::M => 0.3,
+                        ConeType::S => 0.0,
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let mut layer = GanglionLayer::color_opponent(12, 12, 4, 1.5, 4.0);
+        layer.process_cones(&reddish, &cone_types);
+
+        let rate = |cell_type: GanglionType| -> f32 {
+            layer.cells_by_type(cell_type).iter().map(|c| c.firing_rate()).sum()
+        };
+        assert!(rate(GanglionType::RedOnGreenOff) > 0.0);
+        assert_eq!(rate(GanglionType::GreenOnRedOff), 0.0);
+        assert_eq!(rate(GanglionType::BlueOnYellowOff), 0.0);
+
+        assert!(layer.red_green_map()[6][6] > 0.0);
+        assert!(layer.blue_yellow_map()[6][6] < 0.0); // yellowish: L+M above S
+        assert!(GanglionType::BlueOnYellowOff.is_color_opponent());
+        assert!(!GanglionType::OnCenter.is_color_opponent());
+    }
+
+    #[test]
+    fn test_parasol_cells_are_larger_and_more_sensitive() {
+        let midget = GanglionLayer::midget(32, 32);
+        let parasol = GanglionLayer::parasol(32, 32);
+        assert!(parasol.cells()[0].center_radius() > midget.cells()[0].center_radius());
+        assert!(parasol.cells().len() < midget.cells().len());
+
+        // The same low-contrast spot drives a parasol cell harder
+        let mut image = vec![vec![0.5; 32]; 32];
+        for row in image.iter_mut().skip(14).take(5) {
+            for pixel in row.iter_mut().skip(14).take(5) {
+                *pixel = 0.52;
+            }
+        }
+        let mut midget_cell = GanglionCell::new(0, GanglionType::OnCenter, 16, 16, 1.5, 4.0);
+        let mut parasol_cell = GanglionCell::new(1, GanglionType::OnCenter, 16, 16, 3.0, 8.0);
+        parasol_cell.set_contrast_gain(1500.0);
+        midget_cell.compute_response(&image);
+        parasol_cell.compute_response(&image);
+
+        assert!(parasol_cell.firing_rate() > midget_cell.firing_rate());
+    }
+
+    #[test]
+    fn test_layer_convolution_matches_per_cell_reference() {
+        let image: Vec<Vec<f32>> = (0..48)
+            .map(|y| (0..48).map(|x| ((x * 7 + y * 13) % 11) as f32 / 10.0).collect())
+            .collect();
+        let foveation = Foveation::new(24.0, 24.0);
+        let layers = [
+            GanglionLayer::new(48, 48, 4, 1.5, 4.0),
+            GanglionLayer::foveated(48, 48, &foveation, 4, 1.5, 4.0).with_sigma_ratio(3.0),
+        ];
+
+        for mut layer in layers {
+            layer.process_image(&image);
+            for cell in layer.cells() {
+                let (x, y) = cell.position();
+                let mut reference = GanglionCell::new(0, cell.cell_type(), x, y, 1.0, 2.0);
+                reference.set_profile(cell.profile());
+                reference.compute_response(&image);
+                assert!((reference.signed_response() - cell.signed_response()).abs() < 0.05);
+            }
+        }
+    }
+
+    #[test]
+    fn test_dog_profile_parameters() {
+        let profile = DogProfile::from_radii(1.5, 4.0);
+        assert!((profile.center_sigma - 1.5 / SQRT_2).abs() < 1e-6);
+        assert!((profile.surround_sigma() - 4.0 / SQRT_2).abs() < 1e-5);
+        assert_eq!(DogProfile::new(1.0, 0.5, -1.0).sigma_ratio, 1.0);
+
+        // A weakened surround lets a uniform field through to ON cells only
+        let image = create_test_image(20, 20);
+        let mut layer = GanglionLayer::new(20, 20, 4, 1.5, 4.0).with_surround_strength(0.5);
+        layer.process_image(&image);
+        for cell in layer.cells() {
+            match cell.cell_type() {
+                GanglionType::OnCenter => assert!((cell.signed_response() - 0.25 * DEFAULT_CONTRAST_GAIN).abs() < 0.05),
+                _ => assert!(cell.firing_rate() == 0.0),
+            }
+        }
+    }
+
+    #[test]
+    fn test_contrast_gain_control() {
+        let mut cell = GanglionCell::new(0, GanglionType::OnCenter, 5, 5, 2.0, 5.0);
+        assert!((cell.effective_gain() - DEFAULT_CONTRAST_GAIN).abs() < 1e-3);
+
+        // High contrast quickly turns the gain down
+        for _ in 0..10 {
+            cell.adapt_contrast(0.4);
+        }
+        let adapted = cell.effective_gain();
+        assert!(adapted < 0.5 * DEFAULT_CONTRAST_GAIN);
+
+        // Low contrast lets it recover, more slowly than it fell
+        cell.adapt_contrast(0.0);
+        assert!(cell.effective_gain() > adapted);
+        assert!(cell.effective_gain() < DEFAULT_CONTRAST_GAIN);
+        for _ in 0..200 {
+            cell.adapt_contrast(0.0);
+        }
+        assert!(cell.effective_gain() > DEFAULT_CONTRAST_GAIN);
+    }
+
+    #[test]
+    fn test_layer_normalizes_scene_contrast() {
+        let edge = |low: f32, high: f32| -> Vec<Vec<f32>> {
+            (0..20).map(|_| (0..20).map(|x| if x < 10 { low } else { high }).collect()).collect()
+        };
+        let total_rate = |image: &[Vec<f32>], frames: usize| -> f32 {
+            let mut layer = GanglionLayer::new(20, 20, 4, 1.5, 4.0);
+            for _ in 0..frames {
+                layer.process_image(image);
+            }
+            layer.cells().iter().map(|cell| cell.firing_rate()).sum()
+        };
+
+        let high = edge(0.0, 1.0);
+        let low = edge(0.45, 0.55);
+        let first_ratio = total_rate(&high, 1) / total_rate(&low, 1);
+        let adapted_ratio = total_rate(&high, 20) / total_rate(&low, 20);
+
+        // Adaptation compresses the range of responses to different contrasts
+        assert!(first_ratio > 5.0);
+        assert!(adapted_ratio < first_ratio * 0.8);
+    }
+
+    #[test]
+    fn test_melanopsin_cells_are_driven_by_light_not_bipolars() {
+        let mut cell = GanglionCell::new(0, GanglionType::Melanopsin, 5, 5, 2.0, 4.0);
+        let mut reference = MelanopsinCell::new(0, 5, 5);
+        let bright = vec![vec![100.0; 11]; 11];
+
+        // Strong ON bipolar input alone leaves an ipRGC silent
+        cell.compute_bipolar_response(&create_edge_image(11, 11), &vec![vec![0.0; 11]; 11]);
+        cell.compute_response(&create_edge_image(11, 11));
+        assert_eq!(cell.firing_rate(), 0.0);
+
+        // Melanopsin excitation drives it like a standalone ipRGC
+        for _ in 0..100 {
+            cell.compute_melanopsin_response(&bright, 10.0);
+            reference.step(100.0, 10.0);
+        }
+        assert!(cell.firing_rate() > 0.0);
+        assert!((cell.firing_rate() - reference.firing_rate()).abs() < 1e-4);
+        assert_eq!(cell.melanopsin_cell(), Some(&reference));
+        assert!(GanglionCell::new(1, GanglionType::OnCenter, 5, 5, 2.0, 4.0).melanopsin_cell().is_none());
+    }
 }
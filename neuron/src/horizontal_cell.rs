@@ -0,0 +1,351 @@
+//! Horizontal cells - Lateral feedback in the outer retina
+//!
+//! Horizontal cells collect input from many cones, are electrically
+//! coupled to each other through gap junctions and feed back onto the
+//! cone terminals. The feedback carries a spatially blurred copy of cone
+//! activity, which is subtracted from each cone's output: this is where
+//! the antagonistic receptive field surround is first formed.
+
+use crate::photopigment::ConeType;
+
+/// Number of relaxation steps used to settle the coupled network
+const COUPLING_ITERATIONS: usize = 30;
+
+/// Type of horizontal cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HorizontalCellType {
+    /// H1: contacts L- and M-cones, avoids S-cones; feeds back onto L/M cones
+    H1,
+    /// H2: contacts all cones with a strong S-cone input; feeds back onto S-cones
+    H2,
+}
+
+impl HorizontalCellType {
+    /// Returns the relative synaptic weight of a cone class onto this cell type
+    pub fn cone_weight(&self, cone_type: ConeType) -> f32 {
+        match (self, cone_type) {
+            (Self::H1, ConeType::S) => 0.0,
+            (Self::H1, _) => 1.0,
+            (Self::H2, ConeType::S) => 4.0,
+            (Self::H2, _) => 1.0,
+        }
+    }
+
+    /// Returns the type of horizontal cell that feeds back onto a cone class
+    pub fn feeding_back_to(cone_type: ConeType) -> Self {
+        match cone_type {
+            ConeType::S => Self::H2,
+            ConeType::L | ConeType::M => Self::H1,
+        }
+    }
+}
+
+/// Horizontal cell with a dendritic field over the cone mosaic
+#[derive(Debug)]
+pub struct HorizontalCell {
+    id: usize,
+    cell_type: HorizontalCellType,
+
+    // Position of the soma
+    x: usize,
+    y: usize,
+
+    // Physiological state
+    cone_input: f32, // Weighted mean of cone hyperpolarization in the dendritic field
+    potential: f32,  // Hyperpolarization after gap-junction coupling (0.0 = dark)
+}
+
+impl HorizontalCell {
+    /// Creates a new horizontal cell
+    ///
+    /// # Arguments
+    /// * `id` - Unique identifier
+    /// * `cell_type` - H1 or H2
+    /// * `x`, `y` - Position in visual field
+    pub fn new(id: usize, cell_type: HorizontalCellType, x: usize, y: usize) -> Self {
+        Self {
+            id,
+            cell_type,
+            x,
+            y,
+            cone_input: 0.0,
+            potential: 0.0,
+        }
+    }
+
+    /// Returns the cell ID
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /// Returns the cell type
+    pub fn cell_type(&self) -> HorizontalCellType {
+        self.cell_type
+    }
+
+    /// Returns the position
+    pub fn position(&self) -> (usize, usize) {
+        (self.x, self.y)
+    }
+
+    /// Returns the direct cone input before coupling
+    pub fn cone_input(&self) -> f32 {
+        self.cone_input
+    }
+
+    /// Returns the cell's hyperpolarization after coupling (0.0 = dark)
+    pub fn potential(&self) -> f32 {
+        self.potential
+    }
+
+    /// Pools cone signals over the dendritic field
+    ///
+    /// # Arguments
+    /// * `cone_signals` - Cone hyperpolarization at each pixel
+    /// * `cone_types` - Cone class at each pixel
+    /// * `dendritic_radius` - Half-width of the dendritic field (pixels)
+    pub fn pool_cones(&mut self, cone_signals: &[Vec<f32>], cone_types: &[Vec<ConeType>], dendritic_radius: usize) {
+        let height = cone_signals.len();
+        let width = if height > 0 { cone_signals[0].len() } else { 0 };
+
+        let mut weighted_sum = 0.0;
+        let mut weight_total = 0.0;
+
+        for py in self.y.saturating_sub(dendritic_radius)..(self.y + dendritic_radius + 1).min(height) {
+            for px in self.x.saturating_sub(dendritic_radius)..(self.x + dendritic_radius + 1).min(width) {
+                let weight = self.cell_type.cone_weight(cone_types[py][px]);
+                weighted_sum += weight * cone_signals[py][px];
+                weight_total += weight;
+            }
+        }
+
+        self.cone_input = if weight_total > 0.0 { weighted_sum / weight_total } else { 0.0 };
+    }
+}
+
+/// Layer of electrically coupled horizontal cells of both types
+pub struct HorizontalCellLayer {
+    h1_cells: Vec<HorizontalCell>,
+    h2_cells: Vec<HorizontalCell>,
+    grid_width: usize,
+    grid_height: usize,
+    spacing: usize,
+    dendritic_radius: usize,
+    coupling: f32,
+    feedback_gain: f32,
+    width: usize,
+    height: usize,
+}
+
+impl HorizontalCellLayer {
+    /// Creates a new layer of horizontal cells
+    ///
+    /// # Arguments
+    /// * `width`, `height` - Dimensions of visual field
+    /// * `spacing` - Distance between cell bodies
+    /// * `dendritic_radius` - Half-width of each cell's dendritic field
+    /// * `coupling` - Gap-junction conductance relative to membrane leak;
+    ///   the space constant of the network is about `sqrt(coupling)` cells
+    /// * `feedback_gain` - Fraction of the horizontal signal fed back to cones
+    pub fn new(
+        width: usize,
+        height: usize,
+        spacing: usize,
+        dendritic_radius: usize,
+        coupling: f32,
+        feedback_gain: f32,
+    ) -> Self {
+        let spacing = spacing.max(1);
+        let grid_width = width.div_ceil(spacing);
+        let grid_height = height.div_ceil(spacing);
+        let mut h1_cells = Vec::with_capacity(grid_width * grid_height);
+        let mut h2_cells = Vec::with_capacity(grid_width * grid_height);
+        let mut id = 0;
+
+        for gy in 0..grid_height {
+            for gx in 0..grid_width {
+                let (x, y) = (gx * spacing, gy * spacing);
+                h1_cells.push(HorizontalCell::new(id, HorizontalCellType::H1, x, y));
+                h2_cells.push(HorizontalCell::new(id + 1, HorizontalCellType::H2, x, y));
+                id += 2;
+            }
+        }
+
+        Self {
+            h1_cells,
+            h2_cells,
+            grid_width,
+            grid_height,
+            spacing,
+            dendritic_radius,
+            coupling: coupling.max(0.0),
+            feedback_gain,
+            width,
+            height,
+        }
+    }
+
+    /// Drives the layer with cone signals and lets the coupled network settle
+    ///
+    /// # Arguments
+    /// * `cone_signals` - Cone hyperpolarization at each pixel (0.0 = dark)
+    /// * `cone_types` - Cone class at each pixel
+    pub fn process(&mut self, cone_signals: &[Vec<f32>], cone_types: &[Vec<ConeType>]) {
+        let (grid_width, grid_height, coupling) = (self.grid_width, self.grid_height, self.coupling);
+
+        for cells in [&mut self.h1_cells, &mut self.h2_cells] {
+            for cell in cells.iter_mut() {
+                cell.pool_cones(cone_signals, cone_types, self.dendritic_radius);
+                cell.potential = cell.cone_input;
+            }
+
+            // Gap junctions: each cell relaxes towards its input plus the
+            // current flowing in from its four neighbours
+            for _ in 0..COUPLING_ITERATIONS {
+                let previous: Vec<f32> = cells.iter().map(|c| c.potential).collect();
+                for (index, cell) in cells.iter_mut().enumerate() {
+                    let (gx, gy) = (index % grid_width, index / grid_width);
+                    let mut neighbour_sum = 0.0;
+                    let mut neighbour_count = 0.0;
+
+                    if gx > 0 {
+                        neighbour_sum += previous[index - 1];
+                        neighbour_count += 1.0;
+                    }
+                    if gx + 1 < grid_width {
+                        neighbour_sum += previous[index + 1];
+                        neighbour_count += 1.0;
+                    }
+                    if gy > 0 {
+                        neighbour_sum += previous[index - grid_width];
+                        neighbour_count += 1.0;
+                    }
+                    if gy + 1 < grid_height {
+                        neighbour_sum += previous[index + grid_width];
+                        neighbour_count += 1.0;
+                    }
+
+                    cell.potential =
+                        (cell.cone_input + coupling * neighbour_sum) / (1.0 + coupling * neighbour_count);
+                }
+            }
+        }
+    }
+
+    /// Returns the horizontal signal at a point (bilinear between cell bodies)
+    pub fn signal_at(&self, cell_type: HorizontalCellType, x: usize, y: usize) -> f32 {
+        let cells = match cell_type {
+            HorizontalCellType::H1 => &self.h1_cells,
+            HorizontalCellType::H2 => &self.h2_cells,
+        };
+        if cells.is_empty() {
+            return 0.0;
+        }
+
+        let gx = x as f32 / self.spacing as f32;
+        let gy = y as f32 / self.spacing as f32;
+        let x0 = (gx.floor() as usize).min(self.grid_width - 1);
+        let y0 = (gy.floor() as usize).min(self.grid_height - 1);
+        let x1 = (x0 + 1).min(self.grid_width - 1);
+        let y1 = (y0 + 1).min(self.grid_height - 1);
+        let (fx, fy) = (gx - x0 as f32, gy - y0 as f32);
+        let potential = |cx: usize, cy: usize| cells[cy * self.grid_width + cx].potential;
+
+        let top = potential(x0, y0) * (1.0 - fx) + potential(x1, y0) * fx;
+        let bottom = potential(x0, y1) * (1.0 - fx) + potential(x1, y1) * fx;
+        top * (1.0 - fy) + bottom * fy
+    }
+
+    /// Returns the feedback a cone of the given class receives at a point
+    ///
+    /// # Returns
+    /// Feedback as a fraction of the cone's voltage range, ready for
+    /// `Cone::apply_horizontal_feedback`
+    pub fn feedback_at(&self, cone_type: ConeType, x: usize, y: usize) -> f32 {
+        self.feedback_gain * self.signal_at(HorizontalCellType::feeding_back_to(cone_type), x, y)
+    }
+
+    /// Returns the horizontal signal of one cell type at every pixel
+    pub fn activation_map(&self, cell_type: HorizontalCellType) -> Vec<Vec<f32>> {
+        (0..self.height)
+            .map(|y| (0..self.width).map(|x| self.signal_at(cell_type, x, y)).collect())
+            .collect()
+    }
+
+    /// Returns cells of a specific type
+    pub fn cells(&self, cell_type: HorizontalCellType) -> &[HorizontalCell] {
+        match cell_type {
+            HorizontalCellType::H1 => &self.h1_cells,
+            HorizontalCellType::H2 => &self.h2_cells,
+        }
+    }
+
+    /// Returns the feedback gain
+    pub fn feedback_gain(&self) -> f32 {
+        self.feedback_gain
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn uniform_types(width: usize, height: usize, cone_type: ConeType) -> Vec<Vec<ConeType>> {
+        vec![vec![cone_type; width]; height]
+    }
+
+    #[test]
+    fn test_h1_ignores_s_cones() {
+        let mut cell = HorizontalCell::new(0, HorizontalCellType::H1, 2, 2);
+        let signals = vec![vec![1.0; 5]; 5];
+        cell.pool_cones(&signals, &uniform_types(5, 5, ConeType::S), 2);
+        assert_eq!(cell.cone_input(), 0.0);
+
+        cell.pool_cones(&signals, &uniform_types(5, 5, ConeType::L), 2);
+        assert!((cell.cone_input() - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_uniform_field() {
+        let mut layer = HorizontalCellLayer::new(20, 20, 2, 2, 4.0, 0.5);
+        layer.process(&vec![vec![0.3; 20]; 20], &uniform_types(20, 20, ConeType::M));
+
+        assert!((layer.signal_at(HorizontalCellType::H1, 10, 10) - 0.3).abs() < 1e-4);
+        assert!((layer.feedback_at(ConeType::M, 10, 10) - 0.15).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_coupling_spreads_signal() {
+        // A small bright spot: coupled cells carry it further than uncoupled ones
+        let mut signals = vec![vec![0.0; 40]; 40];
+        signals[20][20] = 1.0;
+        let types = uniform_types(40, 40, ConeType::L);
+
+        let mut uncoupled = HorizontalCellLayer::new(40, 40, 2, 1, 0.0, 1.0);
+        let mut coupled = HorizontalCellLayer::new(40, 40, 2, 1, 8.0, 1.0);
+        uncoupled.process(&signals, &types);
+        coupled.process(&signals, &types);
+
+        assert_eq!(uncoupled.signal_at(HorizontalCellType::H1, 28, 20), 0.0);
+        assert!(coupled.signal_at(HorizontalCellType::H1, 28, 20) > 0.0);
+        assert!(coupled.signal_at(HorizontalCellType::H1, 20, 20) < uncoupled.signal_at(HorizontalCellType::H1, 20, 20));
+    }
+
+    #[test]
+    fn test_h2_carries_s_cone_signal() {
+        // S-cones alone are excited (e.g. a violet field)
+        let mut types = uniform_types(10, 10, ConeType::L);
+        let mut signals = vec![vec![0.0; 10]; 10];
+        for y in 0..10 {
+            for x in (0..10).step_by(3) {
+                types[y][x] = ConeType::S;
+                signals[y][x] = 1.0;
+            }
+        }
+
+        let mut layer = HorizontalCellLayer::new(10, 10, 2, 2, 2.0, 1.0);
+        layer.process(&signals, &types);
+        assert!(layer.signal_at(HorizontalCellType::H2, 5, 5) > 0.3);
+        assert!(layer.signal_at(HorizontalCellType::H1, 5, 5) < 0.01);
+    }
+}
@@ -1,8 +1,17 @@
 //! Image loading and preprocessing utilities
 
-use image::{DynamicImage, GenericImageView, ImageReader};
+use image::codecs::gif::GifDecoder;
+use image::{AnimationDecoder, DynamicImage, GenericImageView, ImageFormat, ImageReader};
+use std::fs::File;
+use std::io::BufReader;
 use std::path::Path;
 
+/// GIF frame delays below this are not honoured by viewers (ms)
+const MIN_GIF_FRAME_DELAY_MS: f32 = 20.0;
+
+/// Delay viewers show in place of one that is too short (ms)
+const DEFAULT_GIF_FRAME_DELAY_MS: f32 = 100.0;
+
 /// Load an image from a file and convert to grayscale matrix
 ///
 /// # Arguments
@@ -16,20 +25,7 @@ pub fn load_grayscale_image<P: AsRef<Path>>(path: P) -> Result<Vec<Vec<f32>>, St
         .decode()
         .map_err(|e| format!("Failed to decode image: {}", e))?;
 
-    let gray_img = img.grayscale();
-    let (width, height) = gray_img.dimensions();
-
-    let mut matrix = vec![vec![0.0; width as usize]; height as usize];
-
-    for y in 0..height {
-        for x in 0..width {
-            let pixel = gray_img.get_pixel(x, y);
-            // Convert to 0.0-1.0 range (normalize)
-            matrix[y as usize][x as usize] = pixel[0] as f32 / 255.0;
-        }
-    }
-
-    Ok(matrix)
+    Ok(to_grayscale_matrix(&img))
 }
 
 /// Load an image and resize it to specified dimensions
@@ -91,6 +87,251 @@ pub fn save_grayscale_image<P: AsRef<Path>>(
         .map_err(|e| format!("Failed to save image: {}", e))
 }
 
+/// Linear sRGB → LMS cone excitation matrix
+///
+/// The Hunt-Pointer-Estévez XYZ → LMS transform applied to the sRGB
+/// primaries and normalised to the D65 white point, so that sRGB white
+/// (1, 1, 1) excites all three cone classes equally. It approximates the
+/// Smith-Pokorny cone fundamentals rather than Stockman-Sharpe.
+pub const LINEAR_RGB_TO_LMS: [[f32; 3]; 3] = [
+    [0.313_990, 0.639_513, 0.046_498],
+    [0.155_372, 0.757_894, 0.086_701],
+    [0.017_752, 0.109_442, 0.872_569],
+];
+
+/// Linear sRGB → rod (rhodopsin) excitation weights
+///
+/// Scotopic luminance of the sRGB primaries (Larson et al. 1997),
+/// normalised so that white gives 1.0.
+pub const LINEAR_RGB_TO_ROD: [f32; 3] = [-0.0714, 0.6448, 0.4266];
+
+/// Converts an sRGB-encoded value (0.0-1.0) to linear light
+pub fn srgb_to_linear(value: f32) -> f32 {
+    let value = value.clamp(0.0, 1.0);
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Converts a linear light value (0.0-1.0) back to sRGB encoding
+pub fn linear_to_srgb(value: f32) -> f32 {
+    let value = value.clamp(0.0, 1.0);
+    if value <= 0.003_130_8 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Converts a linear RGB triplet to LMS cone excitations
+pub fn linear_rgb_to_lms(rgb: [f32; 3]) -> [f32; 3] {
+    LINEAR_RGB_TO_LMS.map(|row| (row[0] * rgb[0] + row[1] * rgb[1] + row[2] * rgb[2]).max(0.0))
+}
+
+/// Converts a linear RGB triplet to rod (rhodopsin) excitation
+pub fn linear_rgb_to_rod(rgb: [f32; 3]) -> f32 {
+    let w = LINEAR_RGB_TO_ROD;
+    (w[0] * rgb[0] + w[1] * rgb[1] + w[2] * rgb[2]).max(0.0)
+}
+
+/// Converts an sRGB-encoded image to LMS cone excitations
+///
+/// # Returns
+/// A 2D vector of [L, M, S] excitations (white = [1.0, 1.0, 1.0])
+pub fn srgb_image_to_lms(image: &[Vec<[f32; 3]>]) -> Vec<Vec<[f32; 3]>> {
+    image
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|pixel| linear_rgb_to_lms(pixel.map(srgb_to_linear)))
+                .collect()
+        })
+        .collect()
+}
+
+/// Convert a decoded image to an sRGB matrix
+fn to_color_matrix(img: &DynamicImage) -> Vec<Vec<[f32; 3]>> {
+    let rgb_img = img.to_rgb8();
+    let (width, height) = rgb_img.dimensions();
+
+    let mut matrix = vec![vec![[0.0; 3]; width as usize]; height as usize];
+
+    for (x, y, pixel) in rgb_img.enumerate_pixels() {
+        matrix[y as usize][x as usize] = pixel.0.map(|channel| channel as f32 / 255.0);
+    }
+
+    matrix
+}
+
+/// Convert a decoded image to a grayscale matrix (0.0 to 1.0)
+fn to_grayscale_matrix(img: &DynamicImage) -> Vec<Vec<f32>> {
+    let gray_img = img.to_luma8();
+    let (width, height) = gray_img.dimensions();
+
+    let mut matrix = vec![vec![0.0; width as usize]; height as usize];
+
+    for (x, y, pixel) in gray_img.enumerate_pixels() {
+        matrix[y as usize][x as usize] = pixel[0] as f32 / 255.0;
+    }
+
+    matrix
+}
+
+/// Frames of a grayscale movie, each a 2D array of intensities (0.0 to 1.0)
+pub type GrayscaleFrames = Vec<Vec<Vec<f32>>>;
+
+/// Load every image in a directory as the frames of a grayscale movie
+///
+/// Frames are ordered by file name (e.g. `frame_000.png`, `frame_001.png`);
+/// files that are not images are skipped.
+///
+/// # Arguments
+/// * `dir` - Directory containing the frames
+///
+/// # Returns
+/// Frames in presentation order, all of the same size
+pub fn load_grayscale_sequence<P: AsRef<Path>>(dir: P) -> Result<GrayscaleFrames, String> {
+    let mut paths: Vec<_> = std::fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read directory: {}", e))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file() && ImageFormat::from_path(path).is_ok())
+        .collect();
+    paths.sort();
+
+    let frames = paths
+        .iter()
+        .map(load_grayscale_image)
+        .collect::<Result<Vec<_>, String>>()?;
+    check_frame_sizes(&frames)?;
+
+    Ok(frames)
+}
+
+/// Load the frames of an animated GIF as a grayscale movie
+///
+/// # Arguments
+/// * `path` - Path to the GIF file
+///
+/// Frames with a delay under 20 ms (often 0 in GIFs meant to play "as
+/// fast as possible") count as 100 ms, as browsers show them.
+///
+/// # Returns
+/// (frames in presentation order, mean frame interval in ms)
+pub fn load_grayscale_gif<P: AsRef<Path>>(path: P) -> Result<(GrayscaleFrames, f32), String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open image: {}", e))?;
+    let decoder = GifDecoder::new(BufReader::new(file))
+        .map_err(|e| format!("Failed to decode image: {}", e))?;
+    let gif_frames = decoder
+        .into_frames()
+        .collect_frames()
+        .map_err(|e| format!("Failed to decode image: {}", e))?;
+
+    let mut total_delay_ms = 0.0;
+    let frames: GrayscaleFrames = gif_frames
+        .into_iter()
+        .map(|frame| {
+            let (numerator, denominator) = frame.delay().numer_denom_ms();
+            let delay_ms = numerator as f32 / denominator.max(1) as f32;
+            total_delay_ms += if delay_ms < MIN_GIF_FRAME_DELAY_MS {
+                DEFAULT_GIF_FRAME_DELAY_MS
+            } else {
+                delay_ms
+            };
+            to_grayscale_matrix(&DynamicImage::ImageRgba8(frame.into_buffer()))
+        })
+        .collect();
+    check_frame_sizes(&frames)?;
+
+    let frame_interval_ms = total_delay_ms / frames.len() as f32;
+    Ok((frames, frame_interval_ms))
+}
+
+/// Checks that a movie has frames and that they all have the same size
+fn check_frame_sizes(frames: &[Vec<Vec<f32>>]) -> Result<(), String> {
+    let first = frames.first().ok_or_else(|| "No frames found".to_string())?;
+    let size = (first.len(), first.first().map_or(0, Vec::len));
+    if frames.iter().any(|frame| (frame.len(), frame.first().map_or(0, Vec::len)) != size) {
+        return Err("Frames have different sizes".to_string());
+    }
+    Ok(())
+}
+
+/// Load an image from a file keeping its colour
+///
+/// # Arguments
+/// * `path` - Path to the image file
+///
+/// # Returns
+/// A 2D vector of sRGB-encoded [R, G, B] values (0.0 to 1.0)
+pub fn load_color_image<P: AsRef<Path>>(path: P) -> Result<Vec<Vec<[f32; 3]>>, String> {
+    let img = ImageReader::open(path)
+        .map_err(|e| format!("Failed to open image: {}", e))?
+        .decode()
+        .map_err(|e| format!("Failed to decode image: {}", e))?;
+
+    Ok(to_color_matrix(&img))
+}
+
+/// Load a colour image and resize it to specified dimensions
+pub fn load_and_resize_color<P: AsRef<Path>>(
+    path: P,
+    target_width: u32,
+    target_height: u32,
+) -> Result<Vec<Vec<[f32; 3]>>, String> {
+    let img = ImageReader::open(path)
+        .map_err(|e| format!("Failed to open image: {}", e))?
+        .decode()
+        .map_err(|e| format!("Failed to decode image: {}", e))?;
+
+    let resized = img.resize_exact(
+        target_width,
+        target_height,
+        image::imageops::FilterType::Lanczos3,
+    );
+
+    Ok(to_color_matrix(&resized))
+}
+
+/// Save an sRGB matrix as a colour image file
+pub fn save_color_image<P: AsRef<Path>>(
+    matrix: &[Vec<[f32; 3]>],
+    path: P,
+) -> Result<(), String> {
+    if matrix.is_empty() || matrix[0].is_empty() {
+        return Err("Empty matrix".to_string());
+    }
+
+    let height = matrix.len() as u32;
+    let width = matrix[0].len() as u32;
+
+    let mut img_buffer = image::RgbImage::new(width, height);
+
+    for (x, y, pixel) in img_buffer.enumerate_pixels_mut() {
+        let value = matrix[y as usize][x as usize];
+        *pixel = image::Rgb(value.map(|channel| (channel * 255.0).clamp(0.0, 255.0) as u8));
+    }
+
+    img_buffer
+        .save(path)
+        .map_err(|e| format!("Failed to save image: {}", e))
+}
+
+/// Save a cone mosaic as a colour image (L red, M green, S blue)
+///
+/// # Arguments
+/// * `mosaic` - Cone mosaic to draw
+/// * `scale` - Pixels per cone spacing
+/// * `output_path` - Destination file
+pub fn visualize_cone_mosaic(
+    mosaic: &crate::mosaic::ConeMosaic,
+    scale: usize,
+    output_path: &str,
+) -> Result<(), String> {
+    save_color_image(&mosaic.render(scale), output_path)
+}
+
 /// Create a simple ASCII visualization of a grayscale matrix
 pub fn ascii_visualization(matrix: &[Vec<f32>], max_width: usize) -> String {
     if matrix.is_empty() {
@@ -366,6 +607,49 @@ pub fn visualize_v4_with_legend(
         .map_err(|e| format!("Failed to save V4 with legend: {}", e))
 }
 
+/// Render signed maps side by side, with mid-grey for zero
+///
+/// Positive values are drawn lighter and negative values darker, on a
+/// common scale set by the largest magnitude in any panel. Panels are
+/// separated by a black gap; they need not have the same size.
+///
+/// # Arguments
+/// * `panels` - Maps to draw, left to right
+/// * `output_path` - Where to save the image
+pub fn visualize_signed_maps(panels: &[Vec<Vec<f32>>], output_path: &str) -> Result<(), String> {
+    use image::{ImageBuffer, Luma};
+
+    const GAP: usize = 2;
+
+    if panels.is_empty() || panels.iter().any(|panel| panel.is_empty() || panel[0].is_empty()) {
+        return Err("Empty panel".to_string());
+    }
+
+    let height = panels.iter().map(Vec::len).max().unwrap_or(0);
+    let width = panels.iter().map(|panel| panel[0].len() + GAP).sum::<usize>() - GAP;
+    let scale = panels
+        .iter()
+        .flatten()
+        .flatten()
+        .fold(0.0f32, |max, value| max.max(value.abs()))
+        .max(f32::EPSILON);
+
+    let mut img: ImageBuffer<Luma<u8>, Vec<u8>> = ImageBuffer::new(width as u32, height as u32);
+    let mut offset = 0;
+    for panel in panels {
+        for (y, row) in panel.iter().enumerate() {
+            for (x, &value) in row.iter().enumerate() {
+                let level = (0.5 + 0.5 * value / scale).clamp(0.0, 1.0);
+                img.put_pixel((offset + x) as u32, y as u32, Luma([(level * 255.0) as u8]));
+            }
+        }
+        offset += panel[0].len() + GAP;
+    }
+
+    img.save(output_path)
+        .map_err(|e| format!("Failed to save signed maps: {}", e))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -381,4 +665,106 @@ mod tests {
         assert!(!viz.is_empty());
         assert!(viz.contains('\n'));
     }
+
+    #[test]
+    fn test_srgb_linear_round_trip() {
+        for &value in &[0.0, 0.02, 0.2, 0.5, 0.9, 1.0] {
+            assert!((linear_to_srgb(srgb_to_linear(value)) - value).abs() < 1e-4);
+        }
+        // Mid-grey in sRGB is only ~21% of white in linear light
+        assert!((srgb_to_linear(0.5) - 0.214).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_white_maps_to_equal_lms() {
+        let lms = linear_rgb_to_lms([1.0, 1.0, 1.0]);
+        for excitation in lms {
+            assert!((excitation - 1.0).abs() < 0.01);
+        }
+        assert!((linear_rgb_to_rod([1.0, 1.0, 1.0]) - 1.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_primaries_excite_expected_cones() {
+        let red = linear_rgb_to_lms([1.0, 0.0, 0.0]);
+        let blue = linear_rgb_to_lms([0.0, 0.0, 1.0]);
+
+        // Red drives L more than M, blue drives mostly S
+        assert!(red[0] > red[1]);
+        assert!(blue[2] > blue[0] && blue[2] > blue[1]);
+    }
+
+    #[test]
+    fn test_load_sequence_directory_in_name_order() {
+        let dir = std::env::temp_dir().join(format!("neuron_sequence_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for (name, value) in [("frame_1.png", 1.0), ("frame_0.png", 0.0), ("frame_2.png", 0.5)] {
+            save_grayscale_image(&vec![vec![value; 4]; 3], dir.join(name)).unwrap();
+        }
+        std::fs::write(dir.join("notes.txt"), "not a frame").unwrap();
+
+        let frames = load_grayscale_sequence(&dir).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(frames.len(), 3);
+        assert_eq!((frames[0].len(), frames[0][0].len()), (3, 4));
+        assert_eq!(frames[0][0][0], 0.0);
+        assert_eq!(frames[1][0][0], 1.0);
+    }
+
+    #[test]
+    fn test_visualize_signed_maps() {
+        let path = std::env::temp_dir().join(format!("neuron_signed_{}.png", std::process::id()));
+        let panels = vec![vec![vec![-1.0, 0.0, 1.0]; 2], vec![vec![0.5; 2]; 3]];
+        visualize_signed_maps(&panels, path.to_str().unwrap()).unwrap();
+        let img = load_grayscale_image(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        // Panels side by side with a gap; the height of the tallest panel
+        assert_eq!((img.len(), img[0].len()), (3, 3 + 2 + 2));
+        assert!(img[0][0] < 0.05 && img[0][2] > 0.95);
+        assert!((img[0][1] - 0.5).abs() < 0.01);
+        assert!((img[2][5] - 0.75).abs() < 0.01);
+        assert!(visualize_signed_maps(&[], path.to_str().unwrap()).is_err());
+        assert!(visualize_signed_maps(&[Vec::new()], path.to_str().unwrap()).is_err());
+    }
+
+    /// Writes a two-frame GIF (black then white) with the given frame delay
+    fn write_test_gif(name: &str, delay_ms: u32) -> std::path::PathBuf {
+        use image::codecs::gif::GifEncoder;
+        use image::{Delay, Frame, Rgba, RgbaImage};
+
+        let path = std::env::temp_dir().join(format!("neuron_{}_{}.gif", name, std::process::id()));
+        let mut encoder = GifEncoder::new(File::create(&path).unwrap());
+        for value in [0u8, 255] {
+            let buffer = RgbaImage::from_pixel(5, 2, Rgba([value, value, value, 255]));
+            encoder
+                .encode_frame(Frame::from_parts(buffer, 0, 0, Delay::from_numer_denom_ms(delay_ms, 1)))
+                .unwrap();
+        }
+        path
+    }
+
+    #[test]
+    fn test_load_animated_gif() {
+        let path = write_test_gif("movie", 40);
+        let (frames, frame_interval_ms) = load_grayscale_gif(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(frames.len(), 2);
+        assert_eq!((frames[1].len(), frames[1][0].len()), (2, 5));
+        assert!(frames[0][0][0] < 0.1 && frames[1][0][0] > 0.9);
+        assert!((frame_interval_ms - 40.0).abs() < 1.0);
+        assert!(load_grayscale_sequence(std::env::temp_dir().join("neuron_missing_dir")).is_err());
+    }
+
+    #[test]
+    fn test_zero_delay_gif_gets_viewer_interval() {
+        let path = write_test_gif("zero_delay", 0);
+        let (frames, frame_interval_ms) = load_grayscale_gif(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(frames.len(), 2);
+        assert!((frame_interval_ms - DEFAULT_GIF_FRAME_DELAY_MS).abs() < 1.0);
+    }
 }
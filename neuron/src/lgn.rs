@@ -0,0 +1,630 @@
+//! Lateral geniculate nucleus - Thalamic relay between retina and V1
+//!
+//! The LGN keeps the retina's parallel pathways apart in separate layers:
+//! - Parvocellular layers relay midget cells (fine form, red-green colour)
+//! - Magnocellular layers relay parasol cells (motion, low contrast)
+//! - Koniocellular layers, between them, relay small bistratified cells
+//!   (blue-yellow colour)
+//!
+//! Relaying is not passive. Local interneurons subtract a blurred copy of
+//! the retinal input (feedforward inhibition), activity in the
+//! neighbourhood divides the response (contrast gain control), and
+//! cortical feedback and attention scale it. Relay cells fire in one of
+//! two modes set by their membrane potential: depolarized cells relay
+//! their input faithfully (tonic mode), while hyperpolarized cells
+//! de-inactivate T-type calcium channels and answer the next input with a
+//! high-frequency burst (burst mode).
+
+use crate::temporal::{relaxation_rate, DEFAULT_FRAME_INTERVAL_MS};
+
+/// Resting potential of awake, depolarized relay cells (mV)
+const TONIC_RESTING_POTENTIAL: f32 = -60.0;
+
+/// Potential at which half the T-type calcium channels are inactivated (mV)
+const T_CHANNEL_HALF_INACTIVATION: f32 = -70.0;
+
+/// Steepness of T-channel inactivation (mV per e-fold)
+const T_CHANNEL_SLOPE: f32 = 4.0;
+
+/// Time constant of T-channel recovery towards steady state (ms)
+const T_CHANNEL_TIME_CONSTANT_MS: f32 = 48.0;
+
+/// T-channel availability above which a cell is in burst mode
+const BURST_MODE_THRESHOLD: f32 = 0.5;
+
+/// Input needed to trigger a low-threshold calcium spike (Hz)
+const BURST_TRIGGER: f32 = 1.0;
+
+/// Firing rate added by a burst with fully available T-channels (Hz)
+const BURST_RATE: f32 = 300.0;
+
+/// Radius and weight of feedforward interneuron inhibition
+const INTERNEURON_RADIUS: usize = 2;
+const INTERNEURON_WEIGHT: f32 = 0.5;
+
+/// Radius and semi-saturation (Hz) of contrast gain control
+const GAIN_CONTROL_RADIUS: usize = 4;
+const GAIN_CONTROL_SEMI_SATURATION: f32 = 200.0;
+
+/// Depolarization produced by full cortical feedback (mV)
+const FEEDBACK_DEPOLARIZATION: f32 = 15.0;
+
+/// Relay gain added by full cortical feedback
+const FEEDBACK_GAIN: f32 = 0.5;
+
+/// Layer of the LGN, one per retinal stream
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LgnLayerType {
+    /// Parvocellular: small cells relaying midget ganglion cells
+    Parvocellular,
+    /// Magnocellular: large cells relaying parasol ganglion cells
+    Magnocellular,
+    /// Koniocellular: intercalated cells relaying blue-yellow ganglion cells
+    Koniocellular,
+}
+
+impl LgnLayerType {
+    /// All LGN layer types
+    pub const ALL: [LgnLayerType; 3] = [
+        LgnLayerType::Parvocellular,
+        LgnLayerType::Magnocellular,
+        LgnLayerType::Koniocellular,
+    ];
+
+    /// Returns the position of this layer type in `ALL`
+    pub fn index(&self) -> usize {
+        match self {
+            Self::Parvocellular => 0,
+            Self::Magnocellular => 1,
+            Self::Koniocellular => 2,
+        }
+    }
+}
+
+/// Firing mode of a thalamic relay cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RelayMode {
+    /// Depolarized: single spikes at a rate that follows the input
+    Tonic,
+    /// Hyperpolarized: T-channels available, input triggers a burst
+    Burst,
+}
+
+/// State of one population of relay cells, one cell per pixel
+#[derive(Debug)]
+struct RelayCells {
+    t_channel_availability: Vec<Vec<f32>>,
+    bursting: Vec<Vec<bool>>,
+    activity: Vec<Vec<f32>>,
+}
+
+impl RelayCells {
+    /// Creates silent relay cells with T-channels at a steady state
+    fn new(width: usize, height: usize, availability: f32) -> Self {
+        Self {
+            t_channel_availability: vec![vec![availability; width]; height],
+            bursting: vec![vec![false; width]; height],
+            activity: vec![vec![0.0; width]; height],
+        }
+    }
+
+    /// Relays one frame of excitation (input already scaled by the gains)
+    ///
+    /// # Arguments
+    /// * `excitation` - Scaled retinal input at each pixel
+    /// * `resting_potential` - Resting potential of the cells (mV)
+    /// * `feedback` - Cortical feedback at each pixel (0.0 = none, 1.0 = full)
+    /// * `t_channel_rate` - Fraction of the way T-channels recover this frame
+    fn relay(&mut self, excitation: &[Vec<f32>], resting_potential: f32, feedback: &[Vec<f32>], t_channel_rate: f32) {
+        // Interneurons pool the same input; gain control pools a wider area
+        let inhibition = local_mean(excitation, INTERNEURON_RADIUS);
+        let pooled_activity = local_mean(excitation, GAIN_CONTROL_RADIUS);
+
+        for (y, row) in excitation.iter().enumerate() {
+            for (x, &excitation) in row.iter().enumerate() {
+                let drive = (excitation - INTERNEURON_WEIGHT * inhibition[y][x]).max(0.0)
+                    / (1.0 + pooled_activity[y][x] / GAIN_CONTROL_SEMI_SATURATION);
+
+                let potential =
+                    resting_potential + FEEDBACK_DEPOLARIZATION * sample(feedback, x, y, 0.0).clamp(0.0, 1.0);
+                let availability = &mut self.t_channel_availability[y][x];
+                let bursting = *availability > BURST_MODE_THRESHOLD;
+
+                self.activity[y][x] = if bursting {
+                    // Low-threshold spike: all-or-none, then T-channels inactivate
+                    if drive > BURST_TRIGGER {
+                        let rate = drive + BURST_RATE * *availability;
+                        *availability = 0.0;
+                        rate
+                    } else {
+                        0.0
+                    }
+                } else {
+                    drive
+                };
+                self.bursting[y][x] = bursting && self.activity[y][x] > 0.0;
+
+                *availability += (t_channel_steady_state(potential) - *availability) * t_channel_rate;
+            }
+        }
+    }
+}
+
+/// Retinotopic layer of relay cells and interneurons for one stream
+///
+/// Besides the cells relaying the stream's (rectified) ganglion map, the
+/// layer holds ON-centre and OFF-centre relay cells, which relay the two
+/// ganglion populations separately with the same gains and dynamics.
+#[derive(Debug)]
+pub struct LgnLayer {
+    layer_type: LgnLayerType,
+    width: usize,
+    height: usize,
+    relay_gain: f32,
+    resting_potential: f32,
+    frame_interval_ms: f32,
+
+    // Relay cell state at each pixel
+    cells: RelayCells,
+    on_cells: RelayCells,
+    off_cells: RelayCells,
+}
+
+impl LgnLayer {
+    /// Creates a new LGN layer with relay cells in tonic mode
+    ///
+    /// # Arguments
+    /// * `layer_type` - Stream relayed by this layer
+    /// * `width`, `height` - Dimensions of visual field
+    pub fn new(layer_type: LgnLayerType, width: usize, height: usize) -> Self {
+        let availability = t_channel_steady_state(TONIC_RESTING_POTENTIAL);
+        Self {
+            layer_type,
+            width,
+            height,
+            relay_gain: 1.0,
+            resting_potential: TONIC_RESTING_POTENTIAL,
+            frame_interval_ms: DEFAULT_FRAME_INTERVAL_MS,
+            cells: RelayCells::new(width, height, availability),
+            on_cells: RelayCells::new(width, height, availability),
+            off_cells: RelayCells::new(width, height, availability),
+        }
+    }
+
+    /// Returns the stream relayed by this layer
+    pub fn layer_type(&self) -> LgnLayerType {
+        self.layer_type
+    }
+
+    /// Returns the ratio of relay output to retinal input
+    pub fn relay_gain(&self) -> f32 {
+        self.relay_gain
+    }
+
+    /// Sets the ratio of relay output to retinal input
+    pub fn set_relay_gain(&mut self, relay_gain: f32) {
+        self.relay_gain = relay_gain.max(0.0);
+    }
+
+    /// Returns the resting potential of the relay cells (mV)
+    pub fn resting_potential(&self) -> f32 {
+        self.resting_potential
+    }
+
+    /// Sets the resting potential of the relay cells (mV)
+    ///
+    /// -60 mV (the default) is the awake, tonic state; around -70 mV and
+    /// below, as in drowsiness or sleep, T-channels recover over the next
+    /// frames and the cells switch to burst mode.
+    pub fn set_resting_potential(&mut self, resting_potential: f32) {
+        self.resting_potential = resting_potential;
+    }
+
+    /// Sets the time between successive calls of `relay` (ms)
+    pub fn set_frame_interval_ms(&mut self, frame_interval_ms: f32) {
+        self.frame_interval_ms = frame_interval_ms.max(0.0);
+    }
+
+    /// Relays one frame of retinal input (ganglion firing rates)
+    ///
+    /// # Arguments
+    /// * `retinal_input` - Ganglion cell map of this layer's stream
+    /// * `modulation` - Attentional gain at each pixel (1.0 = neutral)
+    /// * `feedback` - Cortical feedback at each pixel (0.0 = none, 1.0 = full)
+    pub fn relay(&mut self, retinal_input: &[Vec<f32>], modulation: &[Vec<f32>], feedback: &[Vec<f32>]) {
+        let excitation = self.excitation(retinal_input, modulation, feedback);
+        let t_channel_rate = relaxation_rate(T_CHANNEL_TIME_CONSTANT_MS, self.frame_interval_ms);
+        self.cells.relay(&excitation, self.resting_potential, feedback, t_channel_rate);
+    }
+
+    /// Relays one frame of ON-centre and OFF-centre ganglion input
+    ///
+    /// Each population passes through its own relay cells, with the same
+    /// interneuron inhibition, contrast gain control and T-channels as
+    /// `relay`.
+    ///
+    /// # Arguments
+    /// * `on_input` - ON-centre ganglion firing rates of this layer's stream
+    /// * `off_input` - OFF-centre ganglion firing rates of this layer's stream
+    /// * `modulation` - Attentional gain at each pixel (1.0 = neutral)
+    /// * `feedback` - Cortical feedback at each pixel (0.0 = none, 1.0 = full)
+    pub fn relay_on_off(
+        &mut self,
+        on_input: &[Vec<f32>],
+        off_input: &[Vec<f32>],
+        modulation: &[Vec<f32>],
+        feedback: &[Vec<f32>],
+    ) {
+        let t_channel_rate = relaxation_rate(T_CHANNEL_TIME_CONSTANT_MS, self.frame_interval_ms);
+        let on_excitation = self.excitation(on_input, modulation, feedback);
+        self.on_cells.relay(&on_excitation, self.resting_potential, feedback, t_channel_rate);
+        let off_excitation = self.excitation(off_input, modulation, feedback);
+        self.off_cells.relay(&off_excitation, self.resting_potential, feedback, t_channel_rate);
+    }
+
+    /// Scales rectified retinal input by the relay gain, attention and feedback
+    fn excitation(&self, retinal_input: &[Vec<f32>], modulation: &[Vec<f32>], feedback: &[Vec<f32>]) -> Vec<Vec<f32>> {
+        let mut excitation = vec![vec![0.0; self.width]; self.height];
+        for (y, row) in excitation.iter_mut().enumerate() {
+            for (x, value) in row.iter_mut().enumerate() {
+                let input = sample(retinal_input, x, y, 0.0).max(0.0);
+                let gain = sample(modulation, x, y, 1.0) * (1.0 + FEEDBACK_GAIN * sample(feedback, x, y, 0.0));
+                *value = input * self.relay_gain * gain;
+            }
+        }
+        excitation
+    }
+
+    /// Returns the relay cell activity at each pixel (Hz)
+    pub fn activity(&self) -> &[Vec<f32>] {
+        &self.cells.activity
+    }
+
+    /// Returns the ON-centre relay cell activity at each pixel (Hz)
+    pub fn on_activity(&self) -> &[Vec<f32>] {
+        &self.on_cells.activity
+    }
+
+    /// Returns the OFF-centre relay cell activity at each pixel (Hz)
+    pub fn off_activity(&self) -> &[Vec<f32>] {
+        &self.off_cells.activity
+    }
+
+    /// Returns the firing mode the relay cell at a pixel is in
+    pub fn mode_at(&self, x: usize, y: usize) -> RelayMode {
+        if self.cells.t_channel_availability[y][x] > BURST_MODE_THRESHOLD {
+            RelayMode::Burst
+        } else {
+            RelayMode::Tonic
+        }
+    }
+
+    /// Returns the number of relay cells that fired a burst on the last frame
+    pub fn burst_count(&self) -> usize {
+        self.cells.bursting.iter().flatten().filter(|&&b| b).count()
+    }
+}
+
+/// Fraction of T-type calcium channels available at a membrane potential
+fn t_channel_steady_state(potential: f32) -> f32 {
+    1.0 / (1.0 + ((potential - T_CHANNEL_HALF_INACTIVATION) / T_CHANNEL_SLOPE).exp())
+}
+
+/// Returns the value of a map at a pixel, or a default outside it
+fn sample(map: &[Vec<f32>], x: usize, y: usize, default: f32) -> f32 {
+    map.get(y).and_then(|row| row.get(x)).copied().unwrap_or(default)
+}
+
+/// Mean of a map over a square window around each pixel (summed-area table)
+fn local_mean(map: &[Vec<f32>], radius: usize) -> Vec<Vec<f32>> {
+    let height = map.len();
+    let width = if height > 0 { map[0].len() } else { 0 };
+
+    let mut integral = vec![vec![0.0f64; width + 1]; height + 1];
+    for y in 0..height {
+        for x in 0..width {
+            integral[y + 1][x + 1] = map[y][x] as f64 + integral[y][x + 1] + integral[y + 1][x] - integral[y][x];
+        }
+    }
+
+    let mut mean = vec![vec![0.0; width]; height];
+    for (y, row) in mean.iter_mut().enumerate() {
+        let (y0, y1) = (y.saturating_sub(radius), (y + radius + 1).min(height));
+        for (x, value) in row.iter_mut().enumerate() {
+            let (x0, x1) = (x.saturating_sub(radius), (x + radius + 1).min(width));
+            let sum = integral[y1][x1] - integral[y0][x1] - integral[y1][x0] + integral[y0][x0];
+            *value = (sum / ((y1 - y0) * (x1 - x0)) as f64) as f32;
+        }
+    }
+
+    mean
+}
+
+/// Output of the LGN: one activity map per stream
+#[derive(Debug, Clone)]
+pub struct LgnResponse {
+    /// Parvocellular relay activity (form, red-green)
+    pub parvocellular: Vec<Vec<f32>>,
+    /// Magnocellular relay activity (motion, luminance contrast)
+    pub magnocellular: Vec<Vec<f32>>,
+    /// Koniocellular relay activity (blue-yellow)
+    pub koniocellular: Vec<Vec<f32>>,
+}
+
+impl LgnResponse {
+    /// Returns the activity map of one stream
+    pub fn layer(&self, layer_type: LgnLayerType) -> &[Vec<f32>] {
+        match layer_type {
+            LgnLayerType::Parvocellular => &self.parvocellular,
+            LgnLayerType::Magnocellular => &self.magnocellular,
+            LgnLayerType::Koniocellular => &self.koniocellular,
+        }
+    }
+}
+
+/// Lateral geniculate nucleus with parvo-, magno- and koniocellular layers
+pub struct LateralGeniculateNucleus {
+    layers: [LgnLayer; 3],
+    attention: Vec<Vec<f32>>,
+    cortical_feedback: Vec<Vec<f32>>,
+    width: usize,
+    height: usize,
+}
+
+impl LateralGeniculateNucleus {
+    /// Creates a new LGN with all relay cells awake (tonic mode)
+    ///
+    /// # Arguments
+    /// * `width`, `height` - Dimensions of visual field
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            layers: LgnLayerType::ALL.map(|layer_type| LgnLayer::new(layer_type, width, height)),
+            attention: vec![vec![1.0; width]; height],
+            cortical_feedback: vec![vec![0.0; width]; height],
+            width,
+            height,
+        }
+    }
+
+    /// Relays one frame of the three retinal streams through their layers
+    ///
+    /// # Arguments
+    /// * `midget` - Midget ganglion cell map (e.g. edge map)
+    /// * `parasol` - Parasol ganglion cell map
+    /// * `bistratified` - Blue-ON small bistratified ganglion cell map
+    pub fn process(&mut self, midget: &[Vec<f32>], parasol: &[Vec<f32>], bistratified: &[Vec<f32>]) -> LgnResponse {
+        for (layer, input) in self.layers.iter_mut().zip([midget, parasol, bistratified]) {
+            layer.relay(input, &self.attention, &self.cortical_feedback);
+        }
+
+        LgnResponse {
+            parvocellular: self.layer(LgnLayerType::Parvocellular).activity().to_vec(),
+            magnocellular: self.layer(LgnLayerType::Magnocellular).activity().to_vec(),
+            koniocellular: self.layer(LgnLayerType::Koniocellular).activity().to_vec(),
+        }
+    }
+
+    /// Relays one frame of a stream's ON and OFF ganglion cells
+    ///
+    /// Relay cells only fire, so ON-centre and OFF-centre cells are
+    /// relayed as separate populations (see `LgnLayer::relay_on_off`).
+    /// V1 reads both; their difference is a signed centre-surround map.
+    ///
+    /// # Arguments
+    /// * `layer_type` - Layer relaying the stream
+    /// * `on_input` - ON-centre ganglion firing rates
+    /// * `off_input` - OFF-centre ganglion firing rates
+    ///
+    /// # Returns
+    /// ON minus OFF relay activity at each pixel (Hz)
+    pub fn relay_on_off(&mut self, layer_type: LgnLayerType, on_input: &[Vec<f32>], off_input: &[Vec<f32>]) -> Vec<Vec<f32>> {
+        let layer = &mut self.layers[layer_type.index()];
+        layer.relay_on_off(on_input, off_input, &self.attention, &self.cortical_feedback);
+        layer
+            .on_activity()
+            .iter()
+            .zip(layer.off_activity())
+            .map(|(on_row, off_row)| on_row.iter().zip(off_row).map(|(on, off)| on - off).collect())
+            .collect()
+    }
+
+    /// Sets the cortical (layer 6) feedback at each pixel
+    ///
+    /// Feedback (0.0 to 1.0) depolarizes relay cells, keeping them in
+    /// tonic mode, and raises their gain.
+    pub fn set_cortical_feedback(&mut self, feedback: &[Vec<f32>]) {
+        for (y, row) in self.cortical_feedback.iter_mut().enumerate() {
+            for (x, value) in row.iter_mut().enumerate() {
+                *value = sample(feedback, x, y, 0.0).clamp(0.0, 1.0);
+            }
+        }
+    }
+
+    /// Multiplies the relay gain inside a circular region (attentional spotlight)
+    ///
+    /// # Arguments
+    /// * `center_x`, `center_y` - Center of the attended region
+    /// * `radius` - Radius of the attended region (pixels)
+    /// * `gain` - Gain inside the region (1.0 = neutral)
+    pub fn attend(&mut self, center_x: f32, center_y: f32, radius: f32, gain: f32) {
+        for (y, row) in self.attention.iter_mut().enumerate() {
+            for (x, value) in row.iter_mut().enumerate() {
+                let distance = ((x as f32 - center_x).powi(2) + (y as f32 - center_y).powi(2)).sqrt();
+                if distance <= radius {
+                    *value = gain.max(0.0);
+                }
+            }
+        }
+    }
+
+    /// Removes all attentional modulation
+    pub fn clear_attention(&mut self) {
+        self.attention = vec![vec![1.0; self.width]; self.height];
+    }
+
+    /// Sets the resting potential of every relay cell (mV, see `LgnLayer`)
+    pub fn set_resting_potential(&mut self, resting_potential: f32) {
+        for layer in &mut self.layers {
+            layer.set_resting_potential(resting_potential);
+        }
+    }
+
+    /// Sets the time between successive frames for every layer (ms)
+    pub fn set_frame_interval_ms(&mut self, frame_interval_ms: f32) {
+        for layer in &mut self.layers {
+            layer.set_frame_interval_ms(frame_interval_ms);
+        }
+    }
+
+    /// Returns one layer
+    pub fn layer(&self, layer_type: LgnLayerType) -> &LgnLayer {
+        &self.layers[layer_type.index()]
+    }
+
+    /// Returns one layer for modification
+    pub fn layer_mut(&mut self, layer_type: LgnLayerType) -> &mut LgnLayer {
+        &mut self.layers[layer_type.index()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spot(width: usize, height: usize, x: usize, y: usize, rate: f32) -> Vec<Vec<f32>> {
+        let mut map = vec![vec![0.0; width]; height];
+        map[y][x] = rate;
+        map
+    }
+
+    #[test]
+    fn test_streams_stay_separate() {
+        let mut lgn = LateralGeniculateNucleus::new(8, 8);
+        let midget = spot(8, 8, 1, 1, 10.0);
+        let parasol = spot(8, 8, 5, 5, 20.0);
+        let bistratified = vec![vec![0.0; 8]; 8];
+
+        let response = lgn.process(&midget, &parasol, &bistratified);
+        assert!(response.parvocellular[1][1] > 0.0);
+        assert_eq!(response.parvocellular[5][5], 0.0);
+        assert!(response.layer(LgnLayerType::Magnocellular)[5][5] > 0.0);
+        assert!(response.koniocellular.iter().flatten().all(|&v| v == 0.0));
+    }
+
+    #[test]
+    fn test_relay_gain_and_rectification() {
+        let mut lgn = LateralGeniculateNucleus::new(4, 1);
+        let blue_yellow = vec![vec![0.0, 8.0, 0.0, -8.0]];
+        let empty = vec![vec![0.0; 4]; 1];
+
+        let full = lgn.process(&empty, &empty, &blue_yellow).koniocellular;
+        lgn.layer_mut(LgnLayerType::Koniocellular).set_relay_gain(0.5);
+        let half = lgn.process(&empty, &empty, &blue_yellow).koniocellular;
+
+        assert!(half[0][1] > 0.0 && half[0][1] < full[0][1]);
+        assert_eq!(full[0][3], 0.0);
+    }
+
+    #[test]
+    fn test_on_and_off_cells_are_relayed_apart() {
+        let mut lgn = LateralGeniculateNucleus::new(9, 9);
+        let on = spot(9, 9, 2, 4, 20.0);
+        let off = spot(9, 9, 6, 4, 20.0);
+
+        // The difference keeps the OFF half that a single relay layer loses
+        let signed = lgn.relay_on_off(LgnLayerType::Parvocellular, &on, &off);
+        assert!(signed[4][2] > 0.0 && signed[4][6] < 0.0);
+        assert!((signed[4][2] + signed[4][6]).abs() < 1e-4);
+        let layer = lgn.layer(LgnLayerType::Parvocellular);
+        assert_eq!(layer.off_activity()[4][2], 0.0);
+        assert!(layer.activity().iter().flatten().all(|&v| v == 0.0));
+
+        // Both populations share the layer's interneuron inhibition
+        let mut alone = LgnLayer::new(LgnLayerType::Parvocellular, 9, 9);
+        alone.relay_on_off(&on, &[], &[], &[]);
+        let mut crowded = vec![vec![0.0; 9]; 9];
+        for (y, x) in [(4, 2), (3, 2), (5, 2), (4, 1), (4, 3)] {
+            crowded[y][x] = 20.0;
+        }
+        let mut surrounded = LgnLayer::new(LgnLayerType::Parvocellular, 9, 9);
+        surrounded.relay_on_off(&crowded, &[], &[], &[]);
+        assert!(surrounded.on_activity()[4][2] < alone.on_activity()[4][2]);
+    }
+
+    #[test]
+    fn test_tonic_and_burst_modes() {
+        let input = spot(5, 5, 2, 2, 20.0);
+        let dark = vec![vec![0.0; 5]; 5];
+
+        let mut awake = LgnLayer::new(LgnLayerType::Parvocellular, 5, 5);
+        assert_eq!(awake.mode_at(2, 2), RelayMode::Tonic);
+        awake.relay(&input, &[], &[]);
+        let tonic = awake.activity()[2][2];
+
+        let mut drowsy = LgnLayer::new(LgnLayerType::Parvocellular, 5, 5);
+        drowsy.set_resting_potential(-75.0);
+        for _ in 0..5 {
+            drowsy.relay(&dark, &[], &[]);
+        }
+        assert_eq!(drowsy.mode_at(2, 2), RelayMode::Burst);
+
+        drowsy.relay(&input, &[], &[]);
+        let burst = drowsy.activity()[2][2];
+        assert!(burst > tonic * 5.0);
+        assert_eq!(drowsy.burst_count(), 1);
+
+        // The burst inactivates T-channels: sustained input is no longer amplified
+        drowsy.relay(&input, &[], &[]);
+        assert!(drowsy.activity()[2][2] < burst * 0.5);
+    }
+
+    #[test]
+    fn test_interneuron_inhibition() {
+        let alone = spot(9, 9, 4, 4, 50.0);
+        let mut crowded = vec![vec![0.0; 9]; 9];
+        for (y, x) in [(4, 4), (3, 4), (5, 4), (4, 3), (4, 5)] {
+            crowded[y][x] = 50.0;
+        }
+
+        let mut layer = LgnLayer::new(LgnLayerType::Parvocellular, 9, 9);
+        layer.relay(&alone, &[], &[]);
+        let isolated = layer.activity()[4][4];
+        layer.relay(&crowded, &[], &[]);
+
+        assert!(layer.activity()[4][4] < isolated);
+    }
+
+    #[test]
+    fn test_contrast_gain_control_compresses() {
+        let mut layer = LgnLayer::new(LgnLayerType::Magnocellular, 9, 9);
+        let respond = |layer: &mut LgnLayer, rate: f32| {
+            layer.relay(&vec![vec![rate; 9]; 9], &[], &[]);
+            layer.activity()[4][4]
+        };
+
+        let weak = respond(&mut layer, 20.0);
+        let strong = respond(&mut layer, 2000.0);
+        assert!(strong > weak);
+        assert!(strong / weak < 100.0 * 0.5);
+    }
+
+    #[test]
+    fn test_attention_and_cortical_feedback() {
+        let input = vec![vec![20.0; 16]; 16];
+        let empty = vec![vec![0.0; 16]; 16];
+
+        let mut lgn = LateralGeniculateNucleus::new(16, 16);
+        lgn.attend(4.0, 4.0, 2.0, 2.0);
+        let response = lgn.process(&input, &empty, &empty);
+        assert!(response.parvocellular[4][4] > response.parvocellular[12][12]);
+
+        // Feedback depolarizes hyperpolarized cells back into tonic mode
+        lgn.clear_attention();
+        lgn.set_resting_potential(-75.0);
+        lgn.set_cortical_feedback(&vec![vec![1.0; 16]; 16]);
+        for _ in 0..5 {
+            lgn.process(&empty, &empty, &empty);
+        }
+        assert_eq!(lgn.layer(LgnLayerType::Parvocellular).mode_at(8, 8), RelayMode::Tonic);
+    }
+}
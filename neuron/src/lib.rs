@@ -4,8 +4,8 @@
 //! - Anatomical components (dendrites, soma, axon, synapses)
 //! - Physiological properties (resting potential, action potentials, refractory period)
 //! - Neurotransmitter systems (glutamate, GABA, dopamine, serotonin)
-//! - Retinal photoreceptors (cone cells with phototransduction)
-//! - Complete visual pathway (retina → ganglion cells → V1 cortex)
+//! - Retinal photoreceptors (cone and rod cells with phototransduction)
+//! - Complete visual pathway (retina → ganglion cells → LGN → V1 cortex)
 //!
 //! # Examples
 //!
@@ -55,29 +55,67 @@
 //! println!("Dominant orientation: {}", response.features.dominant_orientation());
 //! ```
 
+pub mod amacrine;
+pub mod bipolar;
+pub mod color_vision;
 pub mod cone;
 pub mod constants;
+pub mod convolution;
+pub mod experiments;
+pub mod eye_movements;
+pub mod foveation;
 pub mod ganglion;
+pub mod horizontal_cell;
 pub mod image_utils;
+pub mod lgn;
+pub mod melanopsin;
+pub mod mosaic;
 pub mod network;
 pub mod neuron;
 pub mod neurotransmitter;
+pub mod optics;
 pub mod photopigment;
+pub mod pigment;
+pub mod pupil;
+mod rng;
+pub mod rod;
+pub mod spectrum;
+pub mod spike_encoding;
 pub mod synapse;
+pub mod temporal;
 pub mod v1_cortex;
 pub mod v2_cortex;
 pub mod v4_cortex;
 pub mod visual_pathway;
 
 // Re-export main types for convenience
+pub use amacrine::{AmacrineLayer, MotionDirection, StarburstAmacrineCell, StarburstType};
+pub use bipolar::{BipolarCell, BipolarLayer, BipolarType, GlutamateReceptor};
+pub use color_vision::{ColorVisionDeficiency, ConePopulation};
 pub use cone::Cone;
-pub use ganglion::{GanglionCell, GanglionLayer, GanglionType};
+pub use experiments::{AdaptationProtocol, AdaptationResult, FrameRecord, Stimulus};
+pub use eye_movements::{Eye, EyeMovement, FixationalEyeMovements, GazeSample, SaccadeController};
+pub use foveation::{CorticalGrid, Foveation, SamplingBlock};
+pub use ganglion::{DogProfile, GanglionCell, GanglionLayer, GanglionType};
+pub use horizontal_cell::{HorizontalCell, HorizontalCellLayer, HorizontalCellType};
+pub use lgn::{LateralGeniculateNucleus, LgnLayer, LgnLayerType, LgnResponse, RelayMode};
+pub use melanopsin::{MelanopsinCell, MelanopsinLayer};
+pub use mosaic::{ConeMosaic, MosaicPacking, MosaicParameters};
 pub use network::NeuralNetwork;
 pub use neuron::Neuron;
 pub use neurotransmitter::Neurotransmitter;
-pub use photopigment::{ConeType, LightStimulus};
+pub use optics::EyeOptics;
+pub use photopigment::{
+    ConeType, LightSource, LightStimulus, PreReceptoralFilter, SpectralSensitivity,
+};
+pub use pigment::{PigmentKinetics, ThresholdSample};
+pub use pupil::Pupil;
+pub use rod::Rod;
+pub use spectrum::SpectralPowerDistribution;
+pub use spike_encoding::{SpikeCoding, SpikeEncoder, SpikeTrain};
 pub use synapse::Synapse;
-pub use v1_cortex::{Orientation, V1Cortex, V1Neuron, V1NeuronType};
+pub use temporal::{TemporalFilter, TemporalProfile};
+pub use v1_cortex::{GaborReceptiveField, Orientation, OrientationEstimate, V1Cortex, V1Neuron, V1NeuronType};
 pub use v2_cortex::{CornerType, V2Cortex, V2Response};
 pub use v4_cortex::{ShapeType, V4Cortex, V4Response};
 pub use visual_pathway::VisualPathway;
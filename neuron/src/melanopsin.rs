@@ -0,0 +1,275 @@
+//! Melanopsin - Intrinsically photosensitive retinal ganglion cells
+//!
+//! A small population of ganglion cells (ipRGCs) expresses its own
+//! photopigment, melanopsin, peaking near 480 nm. They form the
+//! non-image-forming pathway: they report overall irradiance to the
+//! pupil and the circadian clock rather than spatial detail.
+//!
+//! Compared with rods and cones, the melanopsin response is
+//! - insensitive: it needs bright light to be driven strongly
+//! - slow: a long phototransduction cascade delays onset by hundreds of
+//!   milliseconds, less for brighter light
+//! - sustained: it barely adapts during steady light and decays slowly
+//!   after the light goes off (post-illumination persistence)
+//!
+//! The cascade is modelled as first-order stages driven by a saturating
+//! function of melanopsin excitation, each rising quickly and decaying
+//! slowly, so the output has a delayed onset and a long tail.
+
+use crate::ganglion::GanglionType;
+
+/// Melanopsin excitation at half-maximal drive
+const HALF_SATURATION_EXCITATION: f32 = 20.0;
+
+/// Number of stages in the melanopsin phototransduction cascade
+const CASCADE_STAGES: usize = 3;
+
+/// Time constant of each cascade stage while the response rises (ms)
+const RISE_TIME_CONSTANT_MS: f32 = 250.0;
+
+/// Time constant of each cascade stage while the response decays (ms)
+const DECAY_TIME_CONSTANT_MS: f32 = 2500.0;
+
+/// Highest sustained firing rate of an ipRGC (Hz)
+const MAX_FIRING_RATE: f32 = 30.0;
+
+/// Spacing of ipRGCs (pixels); each pools light over a spacing × spacing field
+const IPRGC_SPACING: usize = 16;
+
+/// An intrinsically photosensitive ganglion cell
+#[derive(Debug, Clone, PartialEq)]
+pub struct MelanopsinCell {
+    id: usize,
+    x: usize,
+    y: usize,
+    stages: [f32; CASCADE_STAGES],
+}
+
+impl MelanopsinCell {
+    /// Creates a new ipRGC in darkness
+    ///
+    /// # Arguments
+    /// * `id` - Unique identifier
+    /// * `x`, `y` - Position of the cell body
+    pub fn new(id: usize, x: usize, y: usize) -> Self {
+        Self {
+            id,
+            x,
+            y,
+            stages: [0.0; CASCADE_STAGES],
+        }
+    }
+
+    /// Returns the cell ID
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /// Returns the position of the cell body
+    pub fn position(&self) -> (usize, usize) {
+        (self.x, self.y)
+    }
+
+    /// Returns the ganglion cell type (always `GanglionType::Melanopsin`)
+    pub fn cell_type(&self) -> GanglionType {
+        GanglionType::Melanopsin
+    }
+
+    /// Advances the melanopsin response under constant light
+    ///
+    /// # Arguments
+    /// * `excitation` - Melanopsin-weighted light absorbed over the dendritic field
+    /// * `dt_ms` - Time step (ms)
+    pub fn step(&mut self, excitation: f32, dt_ms: f32) {
+        let excitation = excitation.max(0.0);
+        let mut input = excitation / (excitation + HALF_SATURATION_EXCITATION);
+        for stage in self.stages.iter_mut() {
+            let time_constant = if input > *stage { RISE_TIME_CONSTANT_MS } else { DECAY_TIME_CONSTANT_MS };
+            *stage += (input - *stage) * (1.0 - (-dt_ms.max(0.0) / time_constant).exp());
+            input = *stage;
+        }
+    }
+
+    /// Returns the normalized response (0.0 = dark, 1.0 = saturated)
+    pub fn response(&self) -> f32 {
+        self.stages[CASCADE_STAGES - 1]
+    }
+
+    /// Returns the firing rate (Hz)
+    pub fn firing_rate(&self) -> f32 {
+        MAX_FIRING_RATE * self.response()
+    }
+
+    /// Returns the cell to darkness
+    pub fn reset(&mut self) {
+        self.stages = [0.0; CASCADE_STAGES];
+    }
+}
+
+/// A sparse mosaic of ipRGCs tiling the visual field
+#[derive(Debug, Clone, PartialEq)]
+pub struct MelanopsinLayer {
+    cells: Vec<MelanopsinCell>,
+    spacing: usize,
+}
+
+impl MelanopsinLayer {
+    /// Creates a new ipRGC layer covering the visual field
+    ///
+    /// # Arguments
+    /// * `width`, `height` - Dimensions of the visual field
+    pub fn new(width: usize, height: usize) -> Self {
+        let spacing = IPRGC_SPACING.min(width.max(1)).min(height.max(1));
+        let mut cells = Vec::new();
+        for y in (spacing / 2..height).step_by(spacing) {
+            for x in (spacing / 2..width).step_by(spacing) {
+                cells.push(MelanopsinCell::new(cells.len(), x, y));
+            }
+        }
+        Self { cells, spacing }
+    }
+
+    /// Advances every cell by one time step
+    ///
+    /// Each cell pools the melanopsin excitation over the square field
+    /// around its cell body.
+    ///
+    /// # Arguments
+    /// * `excitation` - Melanopsin excitation at each pixel
+    /// * `dt_ms` - Time step (ms)
+    pub fn process(&mut self, excitation: &[Vec<f32>], dt_ms: f32) {
+        let height = excitation.len();
+        let width = excitation.first().map_or(0, Vec::len);
+        let half = self.spacing / 2;
+        for cell in &mut self.cells {
+            let (x0, y0) = (cell.x.saturating_sub(half), cell.y.saturating_sub(half));
+            let (x1, y1) = ((x0 + self.spacing).min(width), (y0 + self.spacing).min(height));
+            let count = (x1.saturating_sub(x0) * y1.saturating_sub(y0)).max(1);
+            let total: f32 = excitation[y0.min(height)..y1].iter().map(|row| row[x0..x1].iter().sum::<f32>()).sum();
+            cell.step(total / count as f32, dt_ms);
+        }
+    }
+
+    /// Returns the mean normalized response of the population (0.0 to 1.0)
+    pub fn mean_response(&self) -> f32 {
+        if self.cells.is_empty() {
+            return 0.0;
+        }
+        self.cells.iter().map(MelanopsinCell::response).sum::<f32>() / self.cells.len() as f32
+    }
+
+    /// Returns the mean firing rate of the population (Hz)
+    pub fn mean_firing_rate(&self) -> f32 {
+        MAX_FIRING_RATE * self.mean_response()
+    }
+
+    /// Returns all cells
+    pub fn cells(&self) -> &[MelanopsinCell] {
+        &self.cells
+    }
+
+    /// Returns every cell to darkness
+    pub fn reset(&mut self) {
+        self.cells.iter_mut().for_each(MelanopsinCell::reset);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::photopigment::{melanopsin_sensitivity, LightSource, LightStimulus, MELANOPSIN_PEAK};
+
+    fn excitation(light: LightStimulus) -> f32 {
+        light.absorbed_intensity(melanopsin_sensitivity)
+    }
+
+    /// Time (ms) until the response first exceeds a level, in 10 ms steps
+    fn time_to_reach(light: LightStimulus, level: f32) -> Option<f32> {
+        let mut cell = MelanopsinCell::new(0, 0, 0);
+        (1..=1000).map(|step| step as f32 * 10.0).find(|_| {
+            cell.step(excitation(light), 10.0);
+            cell.response() > level
+        })
+    }
+
+    #[test]
+    fn test_melanopsin_cell_type() {
+        let cell = MelanopsinCell::new(3, 4, 5);
+        assert_eq!(cell.cell_type(), GanglionType::Melanopsin);
+        assert!(cell.cell_type().is_intrinsically_photosensitive());
+        assert!(!GanglionType::OnCenter.is_intrinsically_photosensitive());
+        assert!(!GanglionType::Melanopsin.is_color_opponent());
+    }
+
+    #[test]
+    fn test_response_peaks_near_480_nm() {
+        assert!((melanopsin_sensitivity(MELANOPSIN_PEAK) - 1.0).abs() < 0.01);
+
+        let steady = |wavelength: f32| {
+            let mut cell = MelanopsinCell::new(0, 0, 0);
+            for _ in 0..1000 {
+                cell.step(excitation(LightStimulus::new(wavelength, 50.0)), 10.0);
+            }
+            cell.response()
+        };
+        assert!(steady(480.0) > steady(420.0));
+        assert!(steady(480.0) > steady(560.0));
+        assert!(steady(480.0) > 3.0 * steady(620.0));
+    }
+
+    #[test]
+    fn test_slow_onset_shortens_with_intensity() {
+        // Nothing in the first 50 ms, even for bright light
+        let bright = LightStimulus::new(480.0, 1000.0);
+        assert!(time_to_reach(bright, 0.01).unwrap() > 50.0);
+
+        let dim = LightStimulus::new(480.0, 10.0);
+        let bright_latency = time_to_reach(bright, 0.2).unwrap();
+        let dim_latency = time_to_reach(dim, 0.2).unwrap();
+        assert!(bright_latency > 200.0 && bright_latency < 1000.0);
+        assert!(dim_latency > 1.5 * bright_latency);
+    }
+
+    #[test]
+    fn test_sustained_response_and_persistence() {
+        let mut cell = MelanopsinCell::new(0, 0, 0);
+        let light = excitation(LightStimulus::new(480.0, 200.0));
+        for _ in 0..300 {
+            cell.step(light, 10.0);
+        }
+        let after_3s = cell.response();
+        for _ in 0..1700 {
+            cell.step(light, 10.0);
+        }
+        // Still (not less) responding after 20 s of steady light
+        assert!(cell.response() >= after_3s);
+        assert!(cell.firing_rate() > 0.8 * MAX_FIRING_RATE * light / (light + HALF_SATURATION_EXCITATION));
+
+        // Slow decay once the light goes off
+        let lit = cell.response();
+        for _ in 0..100 {
+            cell.step(0.0, 10.0);
+        }
+        assert!(cell.response() > 0.5 * lit);
+    }
+
+    #[test]
+    fn test_layer_pools_light() {
+        let mut layer = MelanopsinLayer::new(32, 32);
+        assert_eq!(layer.cells().len(), 4);
+
+        let mut excitation = vec![vec![0.0; 32]; 32];
+        for row in excitation.iter_mut().take(16) {
+            row.iter_mut().take(16).for_each(|value| *value = 100.0);
+        }
+        for _ in 0..200 {
+            layer.process(&excitation, 10.0);
+        }
+        let lit = layer.cells().iter().filter(|cell| cell.response() > 0.5).count();
+        assert_eq!(lit, 1);
+        assert!(layer.mean_firing_rate() > 0.0);
+
+        layer.reset();
+        assert_eq!(layer.mean_response(), 0.0);
+    }
+}
@@ -0,0 +1,456 @@
+//! Cone mosaic layouts
+//!
+//! Real cone mosaics are not periodic: cones are hexagonally packed,
+//! L- and M-cones are assigned nearly at random (with some clumping),
+//! S-cones form a sparse quasi-regular lattice and are absent from the
+//! centre of the fovea (the foveola).
+
+use crate::color_vision::ConePopulation;
+use crate::photopigment::ConeType;
+use crate::rng::SeededRng;
+
+/// Row spacing of a hexagonal lattice with unit cone spacing
+const HEX_ROW_SPACING: f32 = 0.866_025_4; // sqrt(3) / 2
+
+/// Random displacement of S-cone lattice nodes (fraction of lattice spacing)
+const S_LATTICE_JITTER: f32 = 0.2;
+
+/// How cones are packed in the mosaic
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MosaicPacking {
+    /// Square grid (one cone per pixel, no offset)
+    Square,
+    /// Hexagonal packing: odd rows shifted by half a cone
+    Hexagonal,
+}
+
+/// Parameters controlling mosaic generation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MosaicParameters {
+    /// Cone packing geometry
+    pub packing: MosaicPacking,
+    /// Probability that an L/M cone copies the class of a neighbour (0.0 = random)
+    pub clumping: f32,
+    /// Radius of the S-cone-free foveola, in cone spacings (0.0 = none)
+    pub foveola_radius: f32,
+    /// Centre of the foveola in grid coordinates (None = centre of the mosaic)
+    pub foveola_center: Option<(f32, f32)>,
+    /// Seed of the random number generator
+    pub seed: u64,
+}
+
+impl MosaicParameters {
+    /// Hexagonal mosaic with mild L/M clumping and no foveola
+    pub fn new(seed: u64) -> Self {
+        Self {
+            packing: MosaicPacking::Hexagonal,
+            clumping: 0.3,
+            foveola_radius: 0.0,
+            foveola_center: None,
+            seed,
+        }
+    }
+
+    /// Returns a copy with a different packing
+    pub fn with_packing(mut self, packing: MosaicPacking) -> Self {
+        self.packing = packing;
+        self
+    }
+
+    /// Returns a copy with a different L/M clumping probability
+    pub fn with_clumping(mut self, clumping: f32) -> Self {
+        self.clumping = clumping.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Returns a copy with an S-cone-free foveola of the given radius
+    pub fn with_foveola(mut self, radius: f32) -> Self {
+        self.foveola_radius = radius.max(0.0);
+        self
+    }
+}
+
+impl Default for MosaicParameters {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+/// A generated arrangement of cone classes
+#[derive(Debug, Clone)]
+pub struct ConeMosaic {
+    width: usize,
+    height: usize,
+    packing: MosaicPacking,
+    types: Vec<ConeType>,
+}
+
+impl ConeMosaic {
+    /// Generates a mosaic for a cone population
+    ///
+    /// S-cones are placed first on a jittered triangular lattice whose
+    /// spacing gives the population's S fraction, skipping the foveola.
+    /// The remaining cones are then assigned L or M in raster order:
+    /// with probability `clumping` a cone copies an already-assigned
+    /// neighbour, otherwise it is drawn at the population's L:M ratio.
+    ///
+    /// # Arguments
+    /// * `width`, `height` - Number of cones per row and number of rows
+    /// * `population` - Cone classes and their ratio
+    /// * `parameters` - Packing, clumping, foveola and seed
+    pub fn generate(
+        width: usize,
+        height: usize,
+        population: &ConePopulation,
+        parameters: &MosaicParameters,
+    ) -> Self {
+        let mut rng = SeededRng::new(parameters.seed);
+        let mut types: Vec<Option<ConeType>> = vec![None; width * height];
+        let mut mosaic = Self {
+            width,
+            height,
+            packing: parameters.packing,
+            types: Vec::new(),
+        };
+
+        // S-cone lattice
+        let s_fraction = population.fraction(ConeType::S);
+        if s_fraction > 0.0 && width > 0 && height > 0 {
+            let row_spacing = mosaic.row_spacing();
+            let spacing = (row_spacing / s_fraction * 2.0 / 3.0_f32.sqrt()).sqrt();
+            let (center_x, center_y) = parameters.foveola_center.unwrap_or((
+                (width as f32 - 1.0) / 2.0,
+                (height as f32 - 1.0) / 2.0,
+            ));
+            let (center_x, center_y) = mosaic.position_of(center_x, center_y);
+            let phase = (rng.range(0.0, spacing), rng.range(0.0, spacing));
+
+            let lattice_rows = ((height as f32 * row_spacing) / (spacing * HEX_ROW_SPACING)) as i32 + 2;
+            let lattice_cols = (width as f32 / spacing) as i32 + 2;
+
+            for j in -1..lattice_rows {
+                for i in -1..lattice_cols {
+                    let offset = if j.rem_euclid(2) == 1 { spacing / 2.0 } else { 0.0 };
+                    let jitter = S_LATTICE_JITTER * spacing;
+                    let px = phase.0 + i as f32 * spacing + offset + rng.range(-jitter, jitter);
+                    let py = phase.1 + j as f32 * spacing * HEX_ROW_SPACING + rng.range(-jitter, jitter);
+
+                    if ((px - center_x).powi(2) + (py - center_y).powi(2)).sqrt() < parameters.foveola_radius {
+                        continue;
+                    }
+                    if let Some(index) = mosaic.nearest_cone(px, py) {
+                        types[index] = Some(ConeType::S);
+                    }
+                }
+            }
+        }
+
+        // Clumped L/M assignment
+        let l_fraction = population.fraction(ConeType::L);
+        let m_fraction = population.fraction(ConeType::M);
+        let l_probability = if l_fraction + m_fraction > 0.0 {
+            l_fraction / (l_fraction + m_fraction)
+        } else {
+            0.0
+        };
+
+        for y in 0..height {
+            for x in 0..width {
+                let index = y * width + x;
+                if types[index].is_some() {
+                    continue;
+                }
+                if l_fraction + m_fraction == 0.0 {
+                    types[index] = Some(ConeType::S);
+                    continue;
+                }
+
+                let neighbours: Vec<ConeType> = mosaic
+                    .earlier_neighbours(x, y)
+                    .into_iter()
+                    .filter_map(|(nx, ny)| types[ny * width + nx])
+                    .filter(|&cone_type| cone_type != ConeType::S)
+                    .collect();
+
+                let cone_type = if !neighbours.is_empty() && rng.chance(parameters.clumping) {
+                    neighbours[rng.index(neighbours.len())]
+                } else if rng.chance(l_probability) {
+                    ConeType::L
+                } else {
+                    ConeType::M
+                };
+                types[index] = Some(cone_type);
+            }
+        }
+
+        mosaic.types = types.into_iter().map(|t| t.unwrap_or(ConeType::L)).collect();
+        mosaic
+    }
+
+    /// The original square mosaic with a repeating diagonal pattern
+    ///
+    /// Every 10 cones along a diagonal hold 1 S-, 4 M- and 5 L-cones
+    /// (`(x + y) % 10`). This produces visible diagonal S-cone stripes and
+    /// a 10/40/50 ratio; it is kept for comparison with earlier results.
+    pub fn diagonal(width: usize, height: usize) -> Self {
+        let types = (0..height)
+            .flat_map(|y| {
+                (0..width).map(move |x| match (x + y) % 10 {
+                    0 => ConeType::S,
+                    1..=4 => ConeType::M,
+                    _ => ConeType::L,
+                })
+            })
+            .collect();
+
+        Self {
+            width,
+            height,
+            packing: MosaicPacking::Square,
+            types,
+        }
+    }
+
+    /// Returns the mosaic dimensions (cones per row, rows)
+    pub fn dimensions(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    /// Returns the packing geometry
+    pub fn packing(&self) -> MosaicPacking {
+        self.packing
+    }
+
+    /// Returns the class of the cone at grid position (x, y)
+    pub fn cone_type_at(&self, x: usize, y: usize) -> ConeType {
+        self.types[y * self.width + x]
+    }
+
+    /// Returns the physical position of the cone at grid position (x, y)
+    ///
+    /// Positions are in units of cone spacing.
+    pub fn position(&self, x: usize, y: usize) -> (f32, f32) {
+        self.position_of(x as f32, y as f32)
+    }
+
+    /// Returns the number of cones of a class
+    pub fn count(&self, cone_type: ConeType) -> usize {
+        self.types.iter().filter(|&&t| t == cone_type).count()
+    }
+
+    /// Returns the fraction of cones belonging to a class
+    pub fn fraction(&self, cone_type: ConeType) -> f32 {
+        if self.types.is_empty() {
+            return 0.0;
+        }
+        self.count(cone_type) as f32 / self.types.len() as f32
+    }
+
+    /// Renders the mosaic as an sRGB image (L red, M green, S blue)
+    ///
+    /// # Arguments
+    /// * `scale` - Pixels per cone spacing
+    pub fn render(&self, scale: usize) -> Vec<Vec<[f32; 3]>> {
+        let scale = scale.max(1) as f32;
+        let image_width = ((self.width as f32 + 0.5) * scale).ceil() as usize;
+        let image_height = ((self.height as f32 * self.row_spacing() + 0.5) * scale).ceil() as usize;
+        let mut image = vec![vec![[0.0; 3]; image_width]; image_height];
+        let radius = 0.45 * scale;
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let color = match self.cone_type_at(x, y) {
+                    ConeType::L => [0.9, 0.15, 0.1],
+                    ConeType::M => [0.1, 0.8, 0.2],
+                    ConeType::S => [0.15, 0.3, 1.0],
+                };
+                let (px, py) = self.position(x, y);
+                let (cx, cy) = ((px + 0.5) * scale, (py + 0.5) * scale);
+
+                let y_range = (cy - radius).max(0.0) as usize..((cy + radius).ceil() as usize).min(image_height);
+                let x_range = (cx - radius).max(0.0) as usize..((cx + radius).ceil() as usize).min(image_width);
+                for (iy, row) in image.iter_mut().enumerate().take(y_range.end).skip(y_range.start) {
+                    for (ix, pixel) in row.iter_mut().enumerate().take(x_range.end).skip(x_range.start) {
+                        let dx = ix as f32 + 0.5 - cx;
+                        let dy = iy as f32 + 0.5 - cy;
+                        if dx * dx + dy * dy <= radius * radius {
+                            *pixel = color;
+                        }
+                    }
+                }
+            }
+        }
+
+        image
+    }
+
+    fn row_spacing(&self) -> f32 {
+        match self.packing {
+            MosaicPacking::Square => 1.0,
+            MosaicPacking::Hexagonal => HEX_ROW_SPACING,
+        }
+    }
+
+    fn row_offset(&self, y: i64) -> f32 {
+        match self.packing {
+            MosaicPacking::Hexagonal if y.rem_euclid(2) == 1 => 0.5,
+            _ => 0.0,
+        }
+    }
+
+    /// Physical position of a (possibly fractional) grid coordinate
+    fn position_of(&self, x: f32, y: f32) -> (f32, f32) {
+        (x + self.row_offset(y.round() as i64), y * self.row_spacing())
+    }
+
+    /// Index of the cone closest to a physical position, if inside the mosaic
+    fn nearest_cone(&self, px: f32, py: f32) -> Option<usize> {
+        let row = (py / self.row_spacing()).round() as i64;
+        let col = (px - self.row_offset(row)).round() as i64;
+
+        if row < 0 || col < 0 || row >= self.height as i64 || col >= self.width as i64 {
+            return None;
+        }
+        Some(row as usize * self.width + col as usize)
+    }
+
+    /// Neighbours of (x, y) that precede it in raster order
+    fn earlier_neighbours(&self, x: usize, y: usize) -> Vec<(usize, usize)> {
+        let mut neighbours = Vec::with_capacity(3);
+        if x > 0 {
+            neighbours.push((x - 1, y));
+        }
+        if y > 0 {
+            neighbours.push((x, y - 1));
+            // In a hexagonal mosaic the second upper neighbour depends on the row offset
+            let diagonal = match self.packing {
+                MosaicPacking::Hexagonal if y % 2 == 1 => x.checked_add(1),
+                _ => x.checked_sub(1),
+            };
+            if let Some(dx) = diagonal.filter(|&dx| dx < self.width) {
+                neighbours.push((dx, y - 1));
+            }
+        }
+        neighbours
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn same_class_neighbour_fraction(mosaic: &ConeMosaic) -> f32 {
+        let (width, height) = mosaic.dimensions();
+        let mut same = 0;
+        let mut total = 0;
+        for y in 0..height {
+            for x in 1..width {
+                let (a, b) = (mosaic.cone_type_at(x - 1, y), mosaic.cone_type_at(x, y));
+                if a != ConeType::S && b != ConeType::S {
+                    total += 1;
+                    if a == b {
+                        same += 1;
+                    }
+                }
+            }
+        }
+        same as f32 / total as f32
+    }
+
+    #[test]
+    fn test_ratio_matches_population() {
+        let population = ConePopulation::normal();
+        let mosaic = ConeMosaic::generate(100, 100, &population, &MosaicParameters::new(1));
+
+        assert!((mosaic.fraction(ConeType::S) - 0.05).abs() < 0.015);
+        let l = mosaic.count(ConeType::L) as f32;
+        let m = mosaic.count(ConeType::M) as f32;
+        assert!((l / (l + m) - 62.0 / 95.0).abs() < 0.08);
+    }
+
+    #[test]
+    fn test_configurable_l_to_m_ratio() {
+        let population = ConePopulation::normal().with_ratio(1.0, 1.0, 0.0);
+        let mosaic = ConeMosaic::generate(80, 80, &population, &MosaicParameters::new(2));
+
+        assert_eq!(mosaic.count(ConeType::S), 0);
+        assert!((mosaic.fraction(ConeType::L) - 0.5).abs() < 0.08);
+    }
+
+    #[test]
+    fn test_seed_reproducibility() {
+        let population = ConePopulation::normal();
+        let a = ConeMosaic::generate(40, 40, &population, &MosaicParameters::new(9));
+        let b = ConeMosaic::generate(40, 40, &population, &MosaicParameters::new(9));
+        let c = ConeMosaic::generate(40, 40, &population, &MosaicParameters::new(10));
+
+        assert_eq!(a.types, b.types);
+        assert_ne!(a.types, c.types);
+    }
+
+    #[test]
+    fn test_clumping_increases_same_class_neighbours() {
+        let population = ConePopulation::normal().with_ratio(1.0, 1.0, 0.0);
+        let random = ConeMosaic::generate(80, 80, &population, &MosaicParameters::new(3).with_clumping(0.0));
+        let clumped = ConeMosaic::generate(80, 80, &population, &MosaicParameters::new(3).with_clumping(0.7));
+
+        assert!(same_class_neighbour_fraction(&clumped) > same_class_neighbour_fraction(&random) + 0.1);
+    }
+
+    #[test]
+    fn test_foveola_has_no_s_cones() {
+        let parameters = MosaicParameters::new(4).with_foveola(10.0);
+        let mosaic = ConeMosaic::generate(60, 60, &ConePopulation::normal(), &parameters);
+        let center = mosaic.position(30, 30);
+
+        for y in 0..60 {
+            for x in 0..60 {
+                let (px, py) = mosaic.position(x, y);
+                let distance = ((px - center.0).powi(2) + (py - center.1).powi(2)).sqrt();
+                if distance < 9.0 {
+                    assert_ne!(mosaic.cone_type_at(x, y), ConeType::S);
+                }
+            }
+        }
+        assert!(mosaic.count(ConeType::S) > 0);
+    }
+
+    #[test]
+    fn test_s_cones_form_regular_lattice() {
+        let mosaic = ConeMosaic::generate(80, 80, &ConePopulation::normal(), &MosaicParameters::new(5));
+        let s_positions: Vec<(f32, f32)> = (0..80)
+            .flat_map(|y| (0..80).map(move |x| (x, y)))
+            .filter(|&(x, y)| mosaic.cone_type_at(x, y) == ConeType::S)
+            .map(|(x, y)| mosaic.position(x, y))
+            .collect();
+
+        // Nearest-neighbour distances never collapse, unlike a random placement
+        let min_distance = s_positions
+            .iter()
+            .enumerate()
+            .flat_map(|(i, a)| {
+                s_positions[i + 1..]
+                    .iter()
+                    .map(move |b| ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt())
+            })
+            .fold(f32::MAX, f32::min);
+        assert!(min_distance > 1.5);
+    }
+
+    #[test]
+    fn test_diagonal_layout() {
+        let mosaic = ConeMosaic::diagonal(10, 10);
+        assert_eq!(mosaic.cone_type_at(0, 0), ConeType::S);
+        assert_eq!(mosaic.cone_type_at(2, 1), ConeType::M);
+        assert_eq!(mosaic.cone_type_at(5, 0), ConeType::L);
+        assert!((mosaic.fraction(ConeType::S) - 0.1).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_render_dimensions() {
+        let mosaic = ConeMosaic::generate(10, 10, &ConePopulation::normal(), &MosaicParameters::new(6));
+        let image = mosaic.render(4);
+        assert_eq!(image[0].len(), 42);
+        assert!(image.iter().flatten().any(|&pixel| pixel != [0.0; 3]));
+    }
+}
@@ -2,6 +2,7 @@
 
 use crate::neuron::Neuron;
 use crate::neurotransmitter::Neurotransmitter;
+use crate::spike_encoding::{spike_inputs, SpikeTrain};
 
 /// A neural network consisting of interconnected neurons
 pub struct NeuralNetwork {
@@ -115,6 +116,21 @@ impl NeuralNetwork {
         }
     }
 
+    /// Drives the network with retinal spike trains
+    ///
+    /// Each spike depolarizes its target neuron by
+    /// `RETINAL_SPIKE_AMPLITUDE` on the step it occurs. Spike times are
+    /// relative to the current simulation time.
+    ///
+    /// # Arguments
+    /// * `trains` - Spike trains, e.g. from `SpikeEncoder::encode_layer`
+    /// * `targets` - ID of the neuron receiving each train (same order as `trains`)
+    /// * `duration_ms` - Duration to simulate in milliseconds
+    pub fn run_spike_trains(&mut self, trains: &[SpikeTrain], targets: &[usize], duration_ms: u32) {
+        let start = self.time_ms;
+        self.run(duration_ms, |t| spike_inputs(trains, targets, t - start));
+    }
+
     /// Prints the current state of all neurons
     pub fn print_status(&self) {
         println!("\n=== Time: {} ms ===", self.time_ms);
@@ -198,4 +214,23 @@ mod tests {
         
         assert_eq!(network.current_time(), 10);
     }
+
+    #[test]
+    fn test_spike_trains_drive_network() {
+        let mut network = NeuralNetwork::new();
+        let n0 = network.add_neuron();
+        let n1 = network.add_neuron();
+        network.step(&[]);
+
+        let trains = vec![
+            SpikeTrain { cell_id: 0, spike_times: vec![0, 5, 10] },
+            SpikeTrain { cell_id: 1, spike_times: vec![] },
+        ];
+        network.run_spike_trains(&trains, &[n0, n1], 20);
+
+        let spikes: Vec<u32> = network.get_neuron(n0).spike_history().iter().copied().collect();
+        assert_eq!(spikes, vec![1, 6, 11]);
+        assert!(network.get_neuron(n1).spike_history().is_empty());
+        assert_eq!(network.current_time(), 21);
+    }
 }
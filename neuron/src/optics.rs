@@ -0,0 +1,320 @@
+//! Eye optics - Blur and scatter between the cornea and the photoreceptors
+//!
+//! The retinal image is a blurred copy of the scene. Three effects set the
+//! point spread function (PSF):
+//! - Diffraction at the pupil: an Airy pattern whose core narrows as the
+//!   pupil widens (first dark ring at 1.22 λ/d radians)
+//! - Aberrations: residual monochromatic aberrations act like a small,
+//!   fixed defocus, and longitudinal chromatic aberration (LCA) focuses
+//!   short wavelengths in front of the retina. A defocus of ΔD diopters
+//!   spreads a point over a blur circle d·ΔD radians across, so both grow
+//!   with the pupil.
+//! - Intraocular scatter: a fraction of the light is spread into a wide
+//!   veil that lowers contrast at all spatial frequencies.
+//!
+//! The Airy core and the blur circle are each replaced by the Gaussian of
+//! equal variance, so the combined PSF is Gaussian and can be applied with
+//! separable convolution. LCA follows the "chromatic eye" model of Thibos
+//! et al. (1992).
+
+use crate::convolution::{gaussian_blur, gaussian_blur_at, GaussianKernel};
+
+/// Default pupil diameter (mm), typical of indoor photopic light
+const DEFAULT_PUPIL_DIAMETER: f32 = 3.0;
+
+/// Range of pupil diameters the eye can reach (mm)
+const MIN_PUPIL_DIAMETER: f32 = 1.5;
+const MAX_PUPIL_DIAMETER: f32 = 8.0;
+
+/// Default image scale: one pixel per foveal cone (0.5 arcmin)
+pub const DEFAULT_PIXELS_PER_DEGREE: f32 = 120.0;
+
+/// Wavelength the eye brings into focus (nm)
+const DEFAULT_FOCUS_WAVELENGTH: f32 = 555.0;
+
+/// Defocus equivalent to the residual monochromatic aberrations (diopters)
+const DEFAULT_RESIDUAL_DEFOCUS: f32 = 0.15;
+
+/// Fraction of light scattered into the veil by a young eye
+const DEFAULT_SCATTER_FRACTION: f32 = 0.1;
+
+/// Spread of the scattered veil (degrees)
+const DEFAULT_SCATTER_SPREAD: f32 = 1.0;
+
+/// Standard deviation of the Gaussian fitted to the Airy core, in units of λ/d
+const AIRY_GAUSSIAN_SIGMA: f32 = 0.42;
+
+/// Radius of the first dark ring of the Airy pattern, in units of λ/d
+const AIRY_FIRST_ZERO: f32 = 1.22;
+
+/// Chromatic eye constants: D(λ) = p - q / (λ - c), λ in nm
+const LCA_P: f32 = 1.685_24;
+const LCA_Q: f32 = 633.46;
+const LCA_C: f32 = 214.102;
+
+const ARCMIN_PER_RADIAN: f32 = 60.0 * 180.0 / std::f32::consts::PI;
+
+/// Optical properties of the eye
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EyeOptics {
+    pupil_diameter: f32,
+    pixels_per_degree: f32,
+    focus_wavelength: f32,
+    residual_defocus: f32,
+    scatter_fraction: f32,
+    scatter_spread: f32,
+}
+
+impl EyeOptics {
+    /// Creates a new eye with a given pupil
+    ///
+    /// Images are taken to be sampled at 120 pixels per degree (one pixel
+    /// per foveal cone), focused at 555 nm, with a young eye's aberrations
+    /// and scatter.
+    ///
+    /// # Arguments
+    /// * `pupil_diameter` - Pupil diameter (mm), clamped to 1.5-8 mm
+    pub fn new(pupil_diameter: f32) -> Self {
+        Self {
+            pupil_diameter: pupil_diameter.clamp(MIN_PUPIL_DIAMETER, MAX_PUPIL_DIAMETER),
+            pixels_per_degree: DEFAULT_PIXELS_PER_DEGREE,
+            focus_wavelength: DEFAULT_FOCUS_WAVELENGTH,
+            residual_defocus: DEFAULT_RESIDUAL_DEFOCUS,
+            scatter_fraction: DEFAULT_SCATTER_FRACTION,
+            scatter_spread: DEFAULT_SCATTER_SPREAD,
+        }
+    }
+
+    /// Creates an eye with a 3 mm pupil
+    pub fn standard() -> Self {
+        Self::new(DEFAULT_PUPIL_DIAMETER)
+    }
+
+    /// Returns a copy with a different image scale (pixels per degree of visual angle)
+    pub fn with_pixels_per_degree(mut self, pixels_per_degree: f32) -> Self {
+        self.pixels_per_degree = pixels_per_degree.max(f32::EPSILON);
+        self
+    }
+
+    /// Returns a copy focused at a different wavelength (nm)
+    pub fn with_focus_wavelength(mut self, wavelength: f32) -> Self {
+        self.focus_wavelength = wavelength.clamp(380.0, 780.0);
+        self
+    }
+
+    /// Returns a copy with different residual aberrations (equivalent defocus, diopters)
+    pub fn with_residual_defocus(mut self, diopters: f32) -> Self {
+        self.residual_defocus = diopters.abs();
+        self
+    }
+
+    /// Returns a copy with different intraocular scatter
+    ///
+    /// # Arguments
+    /// * `fraction` - Fraction of light scattered (0.0 to 1.0; grows with age)
+    /// * `spread` - Standard deviation of the scattered veil (degrees)
+    pub fn with_scatter(mut self, fraction: f32, spread: f32) -> Self {
+        self.scatter_fraction = fraction.clamp(0.0, 1.0);
+        self.scatter_spread = spread.max(0.0);
+        self
+    }
+
+    /// Returns the pupil diameter (mm)
+    pub fn pupil_diameter(&self) -> f32 {
+        self.pupil_diameter
+    }
+
+    /// Sets the pupil diameter (mm), clamped to 1.5-8 mm
+    pub fn set_pupil_diameter(&mut self, pupil_diameter: f32) {
+        self.pupil_diameter = pupil_diameter.clamp(MIN_PUPIL_DIAMETER, MAX_PUPIL_DIAMETER);
+    }
+
+    /// Returns the light reaching the retina relative to a 3 mm pupil
+    ///
+    /// Retinal illuminance grows with the pupil area, so a constricting
+    /// pupil dims the retinal image as well as sharpening it.
+    pub fn retinal_illuminance(&self) -> f32 {
+        (self.pupil_diameter / DEFAULT_PUPIL_DIAMETER).powi(2)
+    }
+
+    /// Returns the image scale (pixels per degree)
+    pub fn pixels_per_degree(&self) -> f32 {
+        self.pixels_per_degree
+    }
+
+    /// Returns the fraction of light scattered into the veil
+    pub fn scatter_fraction(&self) -> f32 {
+        self.scatter_fraction
+    }
+
+    /// Returns the radius of the first dark ring of the Airy pattern (arcmin)
+    pub fn airy_radius(&self, wavelength: f32) -> f32 {
+        AIRY_FIRST_ZERO * self.diffraction_angle(wavelength) * ARCMIN_PER_RADIAN
+    }
+
+    /// Returns the defocus of a wavelength relative to the focused one (diopters)
+    ///
+    /// Negative values are myopic: shorter wavelengths focus in front of
+    /// the retina.
+    pub fn chromatic_defocus(&self, wavelength: f32) -> f32 {
+        chromatic_eye_refraction(wavelength) - chromatic_eye_refraction(self.focus_wavelength)
+    }
+
+    /// Returns the standard deviation of the PSF core at a wavelength (arcmin)
+    ///
+    /// Combines the diffraction core with the blur circle of the chromatic
+    /// and residual defocus.
+    pub fn psf_sigma(&self, wavelength: f32) -> f32 {
+        let diffraction = AIRY_GAUSSIAN_SIGMA * self.diffraction_angle(wavelength);
+        // Chromatic and residual defocus add in quadrature like independent wavefront errors
+        let defocus = self.chromatic_defocus(wavelength).hypot(self.residual_defocus);
+        // A uniform disc of diameter b has a standard deviation of b/4 along each axis
+        let blur_circle = self.pupil_diameter * 1e-3 * defocus / 4.0;
+        diffraction.hypot(blur_circle) * ARCMIN_PER_RADIAN
+    }
+
+    /// Returns the contrast transmitted at a spatial frequency (0.0 to 1.0)
+    ///
+    /// # Arguments
+    /// * `wavelength` - Wavelength of the light (nm)
+    /// * `cycles_per_degree` - Spatial frequency of a sinusoidal grating
+    pub fn modulation_transfer(&self, wavelength: f32, cycles_per_degree: f32) -> f32 {
+        let gaussian_mtf = |sigma_degrees: f32| {
+            (-2.0 * (std::f32::consts::PI * sigma_degrees * cycles_per_degree).powi(2)).exp()
+        };
+        let core = gaussian_mtf(self.psf_sigma(wavelength) / 60.0);
+        let veil = gaussian_mtf(self.scatter_spread);
+        (1.0 - self.scatter_fraction) * core + self.scatter_fraction * veil
+    }
+
+    /// Forms the retinal image of a whole scene at one wavelength
+    ///
+    /// # Arguments
+    /// * `image` - Light reaching the eye at each pixel
+    /// * `wavelength` - Wavelength of the light (nm)
+    pub fn blur(&self, image: &[Vec<f32>], wavelength: f32) -> Vec<Vec<f32>> {
+        let core = gaussian_blur(image, &self.core_kernel(wavelength));
+        if self.scatter_fraction <= 0.0 {
+            return core;
+        }
+        let veil = gaussian_blur(image, &self.scatter_kernel());
+        core.iter()
+            .zip(&veil)
+            .map(|(core_row, veil_row)| {
+                core_row
+                    .iter()
+                    .zip(veil_row)
+                    .map(|(&c, &v)| (1.0 - self.scatter_fraction) * c + self.scatter_fraction * v)
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Forms the retinal image at one wavelength, only at the given points
+    ///
+    /// Cheaper than `blur` when only the photoreceptor positions are needed.
+    ///
+    /// # Arguments
+    /// * `image` - Light reaching the eye at each pixel
+    /// * `wavelength` - Wavelength of the light (nm)
+    /// * `points` - (x, y) positions to evaluate
+    pub fn blur_at(&self, image: &[Vec<f32>], wavelength: f32, points: &[(usize, usize)]) -> Vec<f32> {
+        let core = gaussian_blur_at(image, &self.core_kernel(wavelength), points);
+        if self.scatter_fraction <= 0.0 {
+            return core;
+        }
+        let veil = gaussian_blur_at(image, &self.scatter_kernel(), points);
+        core.iter()
+            .zip(&veil)
+            .map(|(&c, &v)| (1.0 - self.scatter_fraction) * c + self.scatter_fraction * v)
+            .collect()
+    }
+
+    /// Angle λ/d (radians)
+    fn diffraction_angle(&self, wavelength: f32) -> f32 {
+        wavelength * 1e-9 / (self.pupil_diameter * 1e-3)
+    }
+
+    fn core_kernel(&self, wavelength: f32) -> GaussianKernel {
+        GaussianKernel::new(self.psf_sigma(wavelength) / 60.0 * self.pixels_per_degree)
+    }
+
+    fn scatter_kernel(&self) -> GaussianKernel {
+        GaussianKernel::new(self.scatter_spread * self.pixels_per_degree)
+    }
+}
+
+impl Default for EyeOptics {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+/// Refraction of the chromatic eye at a wavelength (diopters)
+fn chromatic_eye_refraction(wavelength: f32) -> f32 {
+    LCA_P - LCA_Q / (wavelength.clamp(380.0, 780.0) - LCA_C)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pupil_trades_diffraction_against_aberration() {
+        let small = EyeOptics::new(1.5);
+        let medium = EyeOptics::new(3.0);
+        let large = EyeOptics::new(7.0);
+
+        // Diffraction shrinks as the pupil widens
+        assert!(small.airy_radius(555.0) > large.airy_radius(555.0));
+        assert!((medium.airy_radius(555.0) - 0.78).abs() < 0.02);
+        // The sharpest image comes from a medium pupil
+        assert!(medium.psf_sigma(555.0) < small.psf_sigma(555.0));
+        assert!(medium.psf_sigma(555.0) < large.psf_sigma(555.0));
+        assert_eq!(EyeOptics::new(20.0).pupil_diameter(), MAX_PUPIL_DIAMETER);
+
+        // Retinal illuminance follows the pupil area
+        assert_eq!(medium.retinal_illuminance(), 1.0);
+        assert!((EyeOptics::new(6.0).retinal_illuminance() - 4.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_short_wavelengths_are_blurred_more() {
+        let optics = EyeOptics::standard();
+
+        // About 1.2 D of LCA between the focus and 430 nm
+        assert!(optics.chromatic_defocus(555.0).abs() < 1e-5);
+        assert!((optics.chromatic_defocus(430.0) + 1.2).abs() < 0.15);
+        assert!(optics.psf_sigma(430.0) > 3.0 * optics.psf_sigma(560.0));
+    }
+
+    #[test]
+    fn test_modulation_transfer_falls_with_frequency() {
+        let optics = EyeOptics::standard();
+        let mtf = |frequency| optics.modulation_transfer(560.0, frequency);
+
+        assert!((mtf(0.0) - 1.0).abs() < 1e-6);
+        assert!(mtf(5.0) > mtf(30.0) && mtf(30.0) > mtf(60.0));
+        // Scatter caps contrast even at low frequencies
+        assert!(mtf(5.0) < 1.0 - 0.9 * optics.scatter_fraction());
+        let clear = optics.with_scatter(0.0, 1.0);
+        assert!(clear.modulation_transfer(560.0, 5.0) > mtf(5.0));
+    }
+
+    #[test]
+    fn test_blur_conserves_light_and_spreads_points() {
+        let optics = EyeOptics::standard().with_pixels_per_degree(60.0).with_scatter(0.0, 1.0);
+        let mut image = vec![vec![0.0; 15]; 15];
+        image[7][7] = 1.0;
+        let blurred = optics.blur(&image, 560.0);
+
+        let total: f32 = blurred.iter().flatten().sum();
+        assert!((total - 1.0).abs() < 1e-3);
+        assert!(blurred[7][7] < 1.0 && blurred[7][8] > 0.0);
+        let sampled = optics.blur_at(&image, 560.0, &[(7, 7), (8, 7)]);
+        assert!((sampled[0] - blurred[7][7]).abs() < 1e-5 && (sampled[1] - blurred[7][8]).abs() < 1e-5);
+
+        let uniform = optics.with_scatter(0.2, 0.2).blur(&vec![vec![0.4; 10]; 10], 450.0);
+        assert!(uniform.iter().flatten().all(|&v| (v - 0.4).abs() < 1e-5));
+    }
+}
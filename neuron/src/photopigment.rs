@@ -1,5 +1,189 @@
 //! Photopigment types and spectral sensitivity
 
+/// Peak wavelength of rhodopsin, the rod photopigment (in nanometers)
+pub const RHODOPSIN_PEAK: f32 = 498.0;
+
+/// Peak wavelength of melanopsin, the ipRGC photopigment (in nanometers)
+pub const MELANOPSIN_PEAK: f32 = 480.0;
+
+/// Govardovskii et al. (2000) visual pigment template (A1, retinal-based)
+///
+/// Real absorbance spectra are asymmetric: they fall steeply on the long
+/// wavelength side and have a long tail (plus a secondary beta band) on the
+/// short wavelength side. The template captures every vertebrate A1 pigment
+/// with a single parameter, the wavelength of peak absorbance.
+///
+/// # Arguments
+/// * `lambda_max` - Wavelength of peak absorbance in nanometers
+/// * `wavelength` - Light wavelength in nanometers (380-780 nm)
+///
+/// # Returns
+/// Normalized absorbance between 0.0 and 1.0 (1.0 at `lambda_max`)
+pub fn govardovskii_template(lambda_max: f32, wavelength: f32) -> f32 {
+    // Alpha band
+    let x = lambda_max / wavelength;
+    let a = 0.8795 + 0.0459 * (-(lambda_max - 300.0).powi(2) / 11940.0).exp();
+    let alpha = 1.0
+        / ((69.7 * (a - x)).exp()
+            + (28.0 * (0.922 - x)).exp()
+            + (-14.9 * (1.104 - x)).exp()
+            + 0.674);
+
+    // Beta band (cis-peak in the near UV)
+    let beta_peak = 189.0 + 0.315 * lambda_max;
+    let beta_width = -40.5 + 0.195 * lambda_max;
+    let beta = 0.26 * (-((wavelength - beta_peak) / beta_width).powi(2)).exp();
+
+    (alpha + beta).clamp(0.0, 1.0)
+}
+
+/// Calculates the spectral sensitivity of rhodopsin for a given wavelength
+/// Uses the Govardovskii template peaking at 498 nm
+///
+/// # Arguments
+/// * `wavelength` - Light wavelength in nanometers (380-780 nm)
+///
+/// # Returns
+/// Normalized sensitivity value between 0.0 and 1.0
+pub fn rhodopsin_sensitivity(wavelength: f32) -> f32 {
+    govardovskii_template(RHODOPSIN_PEAK, wavelength)
+}
+
+/// Calculates the spectral sensitivity of melanopsin for a given wavelength
+/// Uses the Govardovskii template peaking at 480 nm
+///
+/// # Arguments
+/// * `wavelength` - Light wavelength in nanometers (380-780 nm)
+///
+/// # Returns
+/// Normalized sensitivity value between 0.0 and 1.0
+pub fn melanopsin_sensitivity(wavelength: f32) -> f32 {
+    govardovskii_template(MELANOPSIN_PEAK, wavelength)
+}
+
+/// Light absorption in front of the photoreceptors (lens and macular pigment)
+///
+/// The crystalline lens absorbs strongly at short wavelengths, and the
+/// macular pigment (lutein and zeaxanthin) covers the central retina with a
+/// yellow filter peaking at 460 nm.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PreReceptoralFilter {
+    /// Lens optical density at 400 nm
+    pub lens_density: f32,
+    /// Macular pigment optical density at its 460 nm peak
+    pub macular_density: f32,
+}
+
+impl PreReceptoralFilter {
+    /// Standard young observer with a 2° (foveal) field
+    pub fn standard() -> Self {
+        Self {
+            lens_density: 1.2,
+            macular_density: 0.35,
+        }
+    }
+
+    /// No pre-receptoral filtering (bare photopigment)
+    pub fn none() -> Self {
+        Self {
+            lens_density: 0.0,
+            macular_density: 0.0,
+        }
+    }
+
+    /// Returns the optical density of the lens at a given wavelength
+    pub fn lens_optical_density(&self, wavelength: f32) -> f32 {
+        self.lens_density * (-(wavelength - 400.0) / 35.0).exp()
+    }
+
+    /// Returns the optical density of the macular pigment at a given wavelength
+    pub fn macular_optical_density(&self, wavelength: f32) -> f32 {
+        self.macular_density * (-(wavelength - 460.0).powi(2) / (2.0 * 25.0_f32.powi(2))).exp()
+    }
+
+    /// Returns the fraction of light reaching the photoreceptors
+    pub fn transmittance(&self, wavelength: f32) -> f32 {
+        let density = self.lens_optical_density(wavelength) + self.macular_optical_density(wavelength);
+        10.0_f32.powf(-density)
+    }
+}
+
+impl Default for PreReceptoralFilter {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+/// Spectral sensitivity of a photoreceptor measured at the cornea
+///
+/// Combines a Govardovskii photopigment template, self-screening by the
+/// pigment's axial optical density, and pre-receptoral filtering. The curve
+/// is normalized to 1.0 at its peak, like the Stockman-Sharpe cone
+/// fundamentals.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpectralSensitivity {
+    lambda_max: f32,
+    optical_density: f32,
+    filter: PreReceptoralFilter,
+    normalization: f32,
+}
+
+impl SpectralSensitivity {
+    /// Creates a new spectral sensitivity
+    ///
+    /// # Arguments
+    /// * `lambda_max` - Photopigment peak absorbance in nanometers
+    /// * `optical_density` - Peak axial optical density of the outer segment
+    /// * `filter` - Lens and macular pigment in front of the photoreceptor
+    pub fn new(lambda_max: f32, optical_density: f32, filter: PreReceptoralFilter) -> Self {
+        let mut sensitivity = Self {
+            lambda_max,
+            optical_density,
+            filter,
+            normalization: 1.0,
+        };
+        let (_, peak) = sensitivity.find_peak();
+        sensitivity.normalization = if peak > 0.0 { 1.0 / peak } else { 1.0 };
+        sensitivity
+    }
+
+    /// Returns the photopigment peak absorbance in nanometers
+    pub fn lambda_max(&self) -> f32 {
+        self.lambda_max
+    }
+
+    /// Returns the normalized sensitivity at a given wavelength
+    pub fn at(&self, wavelength: f32) -> f32 {
+        (self.unnormalized(wavelength) * self.normalization).clamp(0.0, 1.0)
+    }
+
+    /// Returns the wavelength of peak corneal sensitivity (1 nm resolution)
+    pub fn peak_wavelength(&self) -> f32 {
+        self.find_peak().0
+    }
+
+    fn unnormalized(&self, wavelength: f32) -> f32 {
+        let absorbance = govardovskii_template(self.lambda_max, wavelength);
+        // Self-screening: absorptance of an outer segment with finite optical density
+        let absorptance = if self.optical_density > 0.0 {
+            (1.0 - 10.0_f32.powf(-self.optical_density * absorbance))
+                / (1.0 - 10.0_f32.powf(-self.optical_density))
+        } else {
+            absorbance
+        };
+        absorptance * self.filter.transmittance(wavelength)
+    }
+
+    fn find_peak(&self) -> (f32, f32) {
+        (380..=780)
+            .map(|nm| {
+                let wavelength = nm as f32;
+                (wavelength, self.unnormalized(wavelength))
+            })
+            .fold((380.0, 0.0), |best, candidate| if candidate.1 > best.1 { candidate } else { best })
+    }
+}
+
 /// Types of cone photopigments with different spectral sensitivities
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ConeType {
@@ -21,17 +205,17 @@ impl ConeType {
         }
     }
 
-    /// Returns the standard deviation of the sensitivity curve
-    fn sensitivity_width(&self) -> f32 {
+    /// Returns the peak axial optical density of the outer segment
+    pub fn optical_density(&self) -> f32 {
         match self {
-            Self::S => 30.0,
-            Self::M => 40.0,
-            Self::L => 40.0,
+            Self::S => 0.4,
+            Self::M => 0.5,
+            Self::L => 0.5,
         }
     }
 
-    /// Calculates the spectral sensitivity for a given wavelength
-    /// Uses a Gaussian approximation of cone spectral sensitivity
+    /// Calculates the photopigment absorbance for a given wavelength
+    /// Uses the Govardovskii template at this cone's peak wavelength
     ///
     /// # Arguments
     /// * `wavelength` - Light wavelength in nanometers (380-780 nm)
@@ -39,12 +223,13 @@ impl ConeType {
     /// # Returns
     /// Normalized sensitivity value between 0.0 and 1.0
     pub fn spectral_sensitivity(&self, wavelength: f32) -> f32 {
-        let peak = self.peak_wavelength();
-        let width = self.sensitivity_width();
-        
-        // Gaussian distribution
-        let exponent = -((wavelength - peak).powi(2)) / (2.0 * width.powi(2));
-        (exponent.exp()).clamp(0.0, 1.0)
+        govardovskii_template(self.peak_wavelength(), wavelength)
+    }
+
+    /// Returns the cone fundamental: sensitivity at the cornea after
+    /// self-screening and pre-receptoral filtering
+    pub fn fundamental(&self, filter: PreReceptoralFilter) -> SpectralSensitivity {
+        SpectralSensitivity::new(self.peak_wavelength(), self.optical_density(), filter)
     }
 
     /// Returns the name of the cone type
@@ -75,6 +260,30 @@ pub struct LightStimulus {
     pub intensity: f32,
 }
 
+/// Light that can be absorbed by a photopigment
+///
+/// Implemented by single-wavelength stimuli and by full spectral power
+/// distributions, so photoreceptors can be driven by either.
+pub trait LightSource {
+    /// Returns the intensity absorbed by a pigment with the given spectral sensitivity
+    ///
+    /// # Arguments
+    /// * `sensitivity` - Normalized sensitivity as a function of wavelength (nm)
+    fn absorbed_intensity<F: Fn(f32) -> f32>(&self, sensitivity: F) -> f32;
+}
+
+impl<T: LightSource> LightSource for &T {
+    fn absorbed_intensity<F: Fn(f32) -> f32>(&self, sensitivity: F) -> f32 {
+        (*self).absorbed_intensity(sensitivity)
+    }
+}
+
+impl LightSource for LightStimulus {
+    fn absorbed_intensity<F: Fn(f32) -> f32>(&self, sensitivity: F) -> f32 {
+        self.intensity * sensitivity(self.wavelength)
+    }
+}
+
 impl LightStimulus {
     /// Creates a new light stimulus
     pub fn new(wavelength: f32, intensity: f32) -> Self {
@@ -92,7 +301,9 @@ impl LightStimulus {
         }
     }
 
-    /// Creates white light (combination of all wavelengths)
+    /// Creates white light approximated by a single 555 nm wavelength
+    ///
+    /// Use `SpectralPowerDistribution::d65` for a true broadband white.
     pub fn white_light(intensity: f32) -> Self {
         Self::new(555.0, intensity) // Use peak sensitivity wavelength
     }
@@ -141,6 +352,66 @@ mod tests {
         assert!(s_at_peak > s_off_peak);
     }
 
+    #[test]
+    fn test_template_asymmetry() {
+        // Long tail on the short-wavelength side, steep fall on the long side
+        for cone_type in [ConeType::S, ConeType::M, ConeType::L] {
+            let peak = cone_type.peak_wavelength();
+            let short = cone_type.spectral_sensitivity(peak - 50.0);
+            let long = cone_type.spectral_sensitivity(peak + 50.0);
+            assert!(short > long, "{} template should be asymmetric", cone_type.name());
+        }
+
+        // The S-cone band is far wider than the old 30 nm Gaussian
+        assert!(ConeType::S.spectral_sensitivity(470.0) > 0.2);
+    }
+
+    #[test]
+    fn test_fundamental_peaks() {
+        // Stockman-Sharpe 2° fundamentals peak near 566, 541 and 441 nm
+        let filter = PreReceptoralFilter::standard();
+        let published = [(ConeType::L, 566.0), (ConeType::M, 541.0), (ConeType::S, 441.0)];
+
+        for (cone_type, expected) in published {
+            let peak = cone_type.fundamental(filter).peak_wavelength();
+            assert!(
+                (peak - expected).abs() <= 8.0,
+                "{} fundamental peaks at {} nm, expected ~{} nm",
+                cone_type.name(),
+                peak,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_fundamental_relative_sensitivities() {
+        let filter = PreReceptoralFilter::standard();
+        let l = ConeType::L.fundamental(filter);
+        let m = ConeType::M.fundamental(filter);
+        let s = ConeType::S.fundamental(filter);
+
+        // Approximate Stockman-Sharpe values: L(650) ~0.16, M(600) ~0.26, S(500) ~0.1
+        assert!((l.at(650.0) - 0.16).abs() < 0.08);
+        assert!((m.at(600.0) - 0.26).abs() < 0.12);
+        assert!(s.at(500.0) < 0.2);
+        assert!(m.at(500.0) > l.at(500.0));
+        assert!(s.at(600.0) < 0.001);
+    }
+
+    #[test]
+    fn test_prereceptoral_filter() {
+        let filter = PreReceptoralFilter::standard();
+        // The lens and macular pigment absorb blue light but pass red light
+        assert!(filter.transmittance(420.0) < 0.5);
+        assert!(filter.transmittance(650.0) > 0.99);
+        assert_eq!(PreReceptoralFilter::none().transmittance(420.0), 1.0);
+
+        // Without filtering, the cone fundamental peaks at the photopigment peak
+        let bare = ConeType::M.fundamental(PreReceptoralFilter::none());
+        assert!((bare.peak_wavelength() - 530.0).abs() <= 2.0);
+    }
+
     #[test]
     fn test_light_stimulus_clamping() {
         let light = LightStimulus::new(1000.0, -5.0);
@@ -159,6 +430,15 @@ mod tests {
         assert!(red.wavelength > 600.0);
     }
 
+    #[test]
+    fn test_rhodopsin_sensitivity() {
+        assert!((rhodopsin_sensitivity(RHODOPSIN_PEAK) - 1.0).abs() < 0.01);
+
+        // Purkinje shift: rods favour blue-green over red light
+        assert!(rhodopsin_sensitivity(500.0) > rhodopsin_sensitivity(620.0));
+        assert!(rhodopsin_sensitivity(498.0) > ConeType::L.spectral_sensitivity(498.0));
+    }
+
     #[test]
     fn test_cone_abundance() {
         let total = ConeType::S.abundance() 
@@ -0,0 +1,238 @@
+//! Photopigment bleaching, regeneration and dark adaptation
+//!
+//! Each absorbed photon isomerizes (bleaches) a pigment molecule, which
+//! must be regenerated through the retinoid cycle before it can catch
+//! another photon. With p the fraction of unbleached pigment (Rushton):
+//!
+//!   dp/dt = -I·p / Q + (1 - p) / τ
+//!
+//! where I is the light intensity, Q the photosensitivity (intensity ×
+//! seconds bleaching all but 1/e of the pigment) and τ the regeneration
+//! time constant. Cone pigment regenerates in about 2 minutes, rhodopsin
+//! in about 7.
+//!
+//! After a bleach, thresholds recover along a two-phase curve: cones
+//! recover first and set the threshold for the first ~10 minutes; rods
+//! recover more slowly but reach a far lower absolute threshold and take
+//! over at the rod-cone break.
+
+use crate::cone::Cone;
+use crate::photopigment::ConeType;
+use crate::rod::Rod;
+
+/// First-order bleaching and regeneration kinetics of a photopigment
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PigmentKinetics {
+    /// Intensity × duration that bleaches all but 1/e of the pigment (intensity·s)
+    pub photosensitivity: f32,
+    /// Time constant of pigment regeneration in darkness (s)
+    pub regeneration_time_constant: f32,
+    /// Threshold elevation per fraction of pigment bleached (log10 units;
+    /// Dowling-Rushton law)
+    pub threshold_elevation: f32,
+    /// Detection threshold of the fully dark-adapted photoreceptor (intensity)
+    pub absolute_threshold: f32,
+}
+
+impl PigmentKinetics {
+    /// Creates new pigment kinetics
+    ///
+    /// # Arguments
+    /// * `photosensitivity` - Intensity·s bleaching all but 1/e of the pigment
+    /// * `regeneration_time_constant` - Regeneration time constant (s)
+    /// * `threshold_elevation` - log10 threshold rise per fraction bleached
+    /// * `absolute_threshold` - Dark-adapted detection threshold (intensity)
+    pub fn new(
+        photosensitivity: f32,
+        regeneration_time_constant: f32,
+        threshold_elevation: f32,
+        absolute_threshold: f32,
+    ) -> Self {
+        Self {
+            photosensitivity: photosensitivity.max(f32::EPSILON),
+            regeneration_time_constant: regeneration_time_constant.max(f32::EPSILON),
+            threshold_elevation: threshold_elevation.max(0.0),
+            absolute_threshold: absolute_threshold.max(f32::EPSILON),
+        }
+    }
+
+    /// Cone opsins: half bleached by steady light 10× brighter than daylight,
+    /// regenerate in ~2 min
+    pub fn cone() -> Self {
+        Self::new(120_000.0, 120.0, 3.0, 1.0)
+    }
+
+    /// Rhodopsin: regenerates in ~7 min, ~3 log units more sensitive than cones
+    /// once dark adapted but far more strongly desensitized by bleaching
+    pub fn rod() -> Self {
+        Self::new(120_000.0, 400.0, 20.0, 0.001)
+    }
+
+    /// Returns the steady intensity that keeps half of the pigment bleached
+    pub fn half_bleach_intensity(&self) -> f32 {
+        self.photosensitivity / self.regeneration_time_constant
+    }
+
+    /// Returns the unbleached fraction reached under a steady intensity
+    pub fn steady_state(&self, intensity: f32) -> f32 {
+        1.0 / (1.0 + intensity.max(0.0) / self.half_bleach_intensity())
+    }
+
+    /// Advances the unbleached fraction under a constant intensity
+    ///
+    /// Uses the exact solution of the first-order kinetics, so any
+    /// duration can be taken in one step.
+    ///
+    /// # Arguments
+    /// * `fraction` - Unbleached fraction at the start (0.0 to 1.0)
+    /// * `intensity` - Light intensity during the interval
+    /// * `duration_s` - Length of the interval (s)
+    ///
+    /// # Returns
+    /// Unbleached fraction at the end of the interval
+    pub fn advance(&self, fraction: f32, intensity: f32, duration_s: f32) -> f32 {
+        let intensity = intensity.max(0.0);
+        let rate = intensity / self.photosensitivity + 1.0 / self.regeneration_time_constant;
+        let target = self.steady_state(intensity);
+        let fraction = target + (fraction.clamp(0.0, 1.0) - target) * (-rate * duration_s.max(0.0)).exp();
+        fraction.clamp(0.0, 1.0)
+    }
+
+    /// Returns the detection threshold at a given unbleached fraction
+    ///
+    /// Fewer pigment molecules catch fewer photons (threshold ∝ 1/p), and
+    /// the bleached pigment itself acts like a background light that
+    /// raises log threshold in proportion to the fraction bleached.
+    pub fn threshold(&self, fraction: f32) -> f32 {
+        let fraction = fraction.clamp(f32::EPSILON, 1.0);
+        self.absolute_threshold / fraction * 10f32.powf(self.threshold_elevation * (1.0 - fraction))
+    }
+}
+
+/// One point of a dark-adaptation curve
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThresholdSample {
+    /// Time in darkness since the end of the bleach (s)
+    pub time_s: f32,
+    /// log10 detection threshold of the cones
+    pub cone_log_threshold: f32,
+    /// log10 detection threshold of the rods
+    pub rod_log_threshold: f32,
+}
+
+impl ThresholdSample {
+    /// Returns the psychophysical threshold: whichever system is more sensitive
+    pub fn log_threshold(&self) -> f32 {
+        self.cone_log_threshold.min(self.rod_log_threshold)
+    }
+
+    /// Returns whether rods set the threshold at this time
+    pub fn is_rod_limited(&self) -> bool {
+        self.rod_log_threshold < self.cone_log_threshold
+    }
+}
+
+/// Measures threshold versus time in darkness after a bleach
+///
+/// A cone and a rod are light adapted to the bleaching light, then left in
+/// darkness; their thresholds are sampled at regular intervals.
+///
+/// # Arguments
+/// * `bleach_intensity` - Intensity of the bleaching light
+/// * `bleach_duration_s` - Duration of the bleach (s)
+/// * `duration_s` - Time to follow recovery in darkness (s)
+/// * `sample_interval_s` - Time between samples (s)
+///
+/// # Returns
+/// Samples from the end of the bleach to `duration_s`
+pub fn dark_adaptation_curve(
+    bleach_intensity: f32,
+    bleach_duration_s: f32,
+    duration_s: f32,
+    sample_interval_s: f32,
+) -> Vec<ThresholdSample> {
+    let mut cone = Cone::new(0, ConeType::L);
+    let mut rod = Rod::new(0);
+    cone.bleach(bleach_intensity, bleach_duration_s);
+    rod.bleach(bleach_intensity, bleach_duration_s);
+
+    let interval = sample_interval_s.max(f32::EPSILON);
+    let samples = (duration_s / interval).floor() as usize;
+    (0..=samples)
+        .map(|index| {
+            if index > 0 {
+                cone.dark_adapt(interval);
+                rod.dark_adapt(interval);
+            }
+            ThresholdSample {
+                time_s: index as f32 * interval,
+                cone_log_threshold: cone.detection_threshold().log10(),
+                rod_log_threshold: rod.detection_threshold().log10(),
+            }
+        })
+        .collect()
+}
+
+/// Returns the time of the rod-cone break: when rods first set the threshold
+pub fn rod_cone_break(curve: &[ThresholdSample]) -> Option<f32> {
+    curve.iter().find(|sample| sample.is_rod_limited()).map(|sample| sample.time_s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bleaching_reaches_steady_state() {
+        let kinetics = PigmentKinetics::cone();
+        let half = kinetics.half_bleach_intensity();
+
+        assert!((kinetics.steady_state(half) - 0.5).abs() < 1e-6);
+        assert!((kinetics.advance(1.0, half, 10_000.0) - 0.5).abs() < 1e-4);
+        assert_eq!(kinetics.advance(0.7, 0.0, 0.0), 0.7);
+
+        // Stepping in pieces matches one long step
+        let mut fraction = 1.0;
+        for _ in 0..100 {
+            fraction = kinetics.advance(fraction, 5000.0, 0.5);
+        }
+        assert!((fraction - kinetics.advance(1.0, 5000.0, 50.0)).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_regeneration_is_first_order() {
+        let kinetics = PigmentKinetics::rod();
+        let tau = kinetics.regeneration_time_constant;
+        let recovered = kinetics.advance(0.0, 0.0, tau);
+
+        assert!((recovered - (1.0 - (-1.0f32).exp())).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_threshold_rises_with_bleaching() {
+        let kinetics = PigmentKinetics::cone();
+        assert!((kinetics.threshold(1.0) - kinetics.absolute_threshold).abs() < 1e-6);
+        assert!(kinetics.threshold(0.5) > 10.0 * kinetics.threshold(1.0));
+    }
+
+    #[test]
+    fn test_two_phase_dark_adaptation_curve() {
+        // Strong bleach, then 40 minutes in darkness
+        let curve = dark_adaptation_curve(1_000_000.0, 30.0, 2400.0, 30.0);
+        let thresholds: Vec<f32> = curve.iter().map(|s| s.log_threshold()).collect();
+
+        // Threshold only falls, by several log units overall
+        assert!(thresholds.windows(2).all(|pair| pair[1] <= pair[0] + 1e-4));
+        assert!(thresholds[0] - thresholds[thresholds.len() - 1] > 4.0);
+
+        // Cones first, then rods after a break at roughly 5-20 minutes
+        assert!(!curve[0].is_rod_limited());
+        let break_time = rod_cone_break(&curve).unwrap();
+        assert!((300.0..1200.0).contains(&break_time));
+        assert!(curve.last().unwrap().is_rod_limited());
+
+        // The cone branch has plateaued by the break
+        let at_break = curve.iter().position(|s| s.time_s == break_time).unwrap();
+        assert!((curve[at_break].cone_log_threshold - curve.last().unwrap().cone_log_threshold).abs() < 0.3);
+    }
+}
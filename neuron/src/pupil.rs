@@ -0,0 +1,173 @@
+//! Pupil - The pupillary light reflex
+//!
+//! ipRGCs project to the pretectum, which drives the iris sphincter
+//! through the Edinger-Westphal nucleus. The loop closes through the
+//! optics: a smaller pupil lets less light reach the retina (retinal
+//! illuminance scales with pupil area) and sharpens the retinal image.
+//!
+//! The reflex is modelled as
+//! - a target diameter that shrinks linearly with the ipRGC drive
+//! - a pure delay of about 250 ms (pretectal and autonomic transmission
+//!   plus the iris muscle)
+//! - first-order movement towards the target, faster for constriction
+//!   than for redilation
+
+use std::collections::VecDeque;
+
+/// Smallest and largest pupil the reflex produces (mm)
+const MIN_DIAMETER: f32 = 2.0;
+const MAX_DIAMETER: f32 = 7.0;
+
+/// Delay between a change in ipRGC drive and pupil movement (ms)
+const REFLEX_LATENCY_MS: f32 = 250.0;
+
+/// Time constant of constriction (ms)
+const CONSTRICTION_TIME_CONSTANT_MS: f32 = 300.0;
+
+/// Time constant of redilation (ms)
+const DILATION_TIME_CONSTANT_MS: f32 = 1500.0;
+
+/// Pupil diameter controlled by the ipRGC drive
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pupil {
+    diameter: f32,
+    // Drive reaching the iris now (after the reflex delay)
+    delayed_drive: f32,
+    // Drive samples still travelling through the reflex arc: (arrival time, drive)
+    pending: VecDeque<(f32, f32)>,
+    time_ms: f32,
+}
+
+impl Pupil {
+    /// Creates a new pupil resting at a given diameter
+    ///
+    /// The pupil stays at this diameter until a different drive has
+    /// travelled through the reflex arc.
+    ///
+    /// # Arguments
+    /// * `diameter` - Initial diameter (mm), clamped to 2-7 mm
+    pub fn new(diameter: f32) -> Self {
+        let diameter = diameter.clamp(MIN_DIAMETER, MAX_DIAMETER);
+        Self {
+            diameter,
+            delayed_drive: (MAX_DIAMETER - diameter) / (MAX_DIAMETER - MIN_DIAMETER),
+            pending: VecDeque::new(),
+            time_ms: 0.0,
+        }
+    }
+
+    /// Creates a dark-adapted (fully dilated) pupil
+    pub fn dark_adapted() -> Self {
+        Self::new(MAX_DIAMETER)
+    }
+
+    /// Returns the current diameter (mm)
+    pub fn diameter(&self) -> f32 {
+        self.diameter
+    }
+
+    /// Returns the diameter the pupil settles at for a steady drive (mm)
+    ///
+    /// # Arguments
+    /// * `drive` - Normalized ipRGC drive (0.0 to 1.0)
+    pub fn steady_state_diameter(drive: f32) -> f32 {
+        MAX_DIAMETER - (MAX_DIAMETER - MIN_DIAMETER) * drive.clamp(0.0, 1.0)
+    }
+
+    /// Advances the reflex by one time step
+    ///
+    /// # Arguments
+    /// * `drive` - Normalized ipRGC drive (0.0 to 1.0)
+    /// * `dt_ms` - Time step (ms)
+    ///
+    /// # Returns
+    /// The new pupil diameter (mm)
+    pub fn step(&mut self, drive: f32, dt_ms: f32) -> f32 {
+        let dt_ms = dt_ms.max(0.0);
+        self.time_ms += dt_ms;
+        self.pending.push_back((self.time_ms + REFLEX_LATENCY_MS, drive.clamp(0.0, 1.0)));
+        while let Some(&(arrival, drive)) = self.pending.front() {
+            if arrival > self.time_ms {
+                break;
+            }
+            self.delayed_drive = drive;
+            self.pending.pop_front();
+        }
+
+        let target = Self::steady_state_diameter(self.delayed_drive);
+        let time_constant = if target < self.diameter {
+            CONSTRICTION_TIME_CONSTANT_MS
+        } else {
+            DILATION_TIME_CONSTANT_MS
+        };
+        self.diameter += (target - self.diameter) * (1.0 - (-dt_ms / time_constant).exp());
+        self.diameter
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::melanopsin::MelanopsinCell;
+    use crate::photopigment::{melanopsin_sensitivity, LightSource, LightStimulus};
+
+    /// Pupil diameter every 10 ms while an ipRGC views a light
+    fn light_reflex(light: LightStimulus, duration_ms: usize) -> Vec<f32> {
+        let mut cell = MelanopsinCell::new(0, 0, 0);
+        let mut pupil = Pupil::dark_adapted();
+        (0..duration_ms / 10)
+            .map(|_| {
+                cell.step(light.absorbed_intensity(melanopsin_sensitivity), 10.0);
+                pupil.step(cell.response(), 10.0)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_constriction_latency() {
+        let trace = light_reflex(LightStimulus::new(480.0, 1000.0), 3000);
+
+        // No movement within the reflex latency
+        assert!(trace[..25].iter().all(|&diameter| diameter == MAX_DIAMETER));
+
+        // Constriction starts within half a second and is well under way by 1.5 s
+        let onset = trace.iter().position(|&diameter| diameter < MAX_DIAMETER - 0.05).unwrap() as f32 * 10.0;
+        assert!(onset > REFLEX_LATENCY_MS && onset < 500.0, "onset at {} ms", onset);
+        assert!(trace[149] < MAX_DIAMETER - 2.0);
+        assert!(trace[299] < 3.0);
+    }
+
+    #[test]
+    fn test_constriction_is_strongest_for_blue_light() {
+        let final_diameter = |wavelength: f32| *light_reflex(LightStimulus::new(wavelength, 30.0), 10_000).last().unwrap();
+        let blue = final_diameter(480.0);
+        let violet = final_diameter(420.0);
+        let red = final_diameter(620.0);
+        assert!(blue < violet && blue < red);
+        assert!(red - blue > 1.5);
+    }
+
+    #[test]
+    fn test_redilation_is_slower_than_constriction() {
+        let mut pupil = Pupil::dark_adapted();
+        let constrict = (0..100).map(|_| pupil.step(1.0, 10.0)).position(|d| d < 4.5).unwrap();
+
+        let mut pupil = Pupil::new(MIN_DIAMETER);
+        let dilate = (0..1000).map(|_| pupil.step(0.0, 10.0)).position(|d| d > 4.5).unwrap();
+        assert!(dilate > 2 * constrict);
+    }
+
+    #[test]
+    fn test_steady_state_diameter() {
+        assert_eq!(Pupil::steady_state_diameter(0.0), MAX_DIAMETER);
+        assert_eq!(Pupil::steady_state_diameter(1.0), MIN_DIAMETER);
+        assert_eq!(Pupil::new(10.0).diameter(), MAX_DIAMETER);
+
+        // A resting pupil holds its size until the drive changes
+        let mut pupil = Pupil::new(4.5);
+        for _ in 0..100 {
+            pupil.step(0.5, 10.0);
+        }
+        assert!((pupil.diameter() - 4.5).abs() < 1e-4);
+    }
+}
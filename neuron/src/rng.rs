@@ -0,0 +1,94 @@
+//! Small seeded pseudo-random number generator
+//!
+//! Stochastic parts of the simulation (mosaic layout, noise, spike
+//! generation) must be reproducible from a seed, so they share this
+//! xorshift64* generator instead of depending on an external crate.
+
+/// Seeded xorshift64* generator
+#[derive(Debug, Clone)]
+pub(crate) struct SeededRng {
+    state: u64,
+}
+
+impl SeededRng {
+    /// Creates a new generator; every seed (including 0) gives a valid stream
+    pub(crate) fn new(seed: u64) -> Self {
+        // SplitMix64 scrambles the seed so nearby seeds give unrelated streams
+        let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+
+        Self { state: if z == 0 { 0x2545_F491_4F6C_DD1D } else { z } }
+    }
+
+    /// Returns the next raw 64-bit value
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// Returns a uniform value in [0, 1)
+    pub(crate) fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    /// Returns a uniform value in [min, max)
+    pub(crate) fn range(&mut self, min: f32, max: f32) -> f32 {
+        min + (max - min) * self.next_f32()
+    }
+
+    /// Returns a uniform index in [0, n)
+    pub(crate) fn index(&mut self, n: usize) -> usize {
+        (self.next_u64() % n.max(1) as u64) as usize
+    }
+
+    /// Returns a standard normal value (Box-Muller transform)
+    pub(crate) fn gaussian(&mut self) -> f32 {
+        let u1 = self.next_f32().max(f32::MIN_POSITIVE);
+        let u2 = self.next_f32();
+        (-2.0 * u1.ln()).sqrt() * (2.0 * std::f32::consts::PI * u2).cos()
+    }
+
+    /// Returns true with the given probability
+    pub(crate) fn chance(&mut self, probability: f32) -> bool {
+        self.next_f32() < probability
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_seed_same_stream() {
+        let mut a = SeededRng::new(42);
+        let mut b = SeededRng::new(42);
+        for _ in 0..10 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+        assert_ne!(SeededRng::new(1).next_u64(), SeededRng::new(2).next_u64());
+    }
+
+    #[test]
+    fn test_uniform_range() {
+        let mut rng = SeededRng::new(0);
+        let samples: Vec<f32> = (0..10_000).map(|_| rng.next_f32()).collect();
+        assert!(samples.iter().all(|&v| (0.0..1.0).contains(&v)));
+
+        let mean = samples.iter().sum::<f32>() / samples.len() as f32;
+        assert!((mean - 0.5).abs() < 0.02);
+    }
+
+    #[test]
+    fn test_gaussian_moments() {
+        let mut rng = SeededRng::new(5);
+        let samples: Vec<f32> = (0..20_000).map(|_| rng.gaussian()).collect();
+        let mean = samples.iter().sum::<f32>() / samples.len() as f32;
+        let variance = samples.iter().map(|v| (v - mean).powi(2)).sum::<f32>() / samples.len() as f32;
+        assert!(mean.abs() < 0.03);
+        assert!((variance - 1.0).abs() < 0.05);
+    }
+}
@@ -0,0 +1,326 @@
+//! Rod photoreceptor implementation for scotopic and mesopic vision
+
+use crate::photopigment::{rhodopsin_sensitivity, LightSource};
+use crate::pigment::PigmentKinetics;
+use crate::temporal::{relaxation_rate, DEFAULT_FRAME_INTERVAL_MS};
+
+// Physiological constants for rods
+const DARK_POTENTIAL: f32 = -40.0; // mV - depolarized in darkness
+const LIGHT_POTENTIAL: f32 = -70.0; // mV - hyperpolarized in light
+const DARK_GLUTAMATE_RELEASE: f32 = 100.0; // High glutamate in darkness
+const LIGHT_GLUTAMATE_RELEASE: f32 = 10.0; // Low glutamate in light
+const CGMP_DARK_LEVEL: f32 = 100.0; // High cGMP in darkness
+const CGMP_LIGHT_LEVEL: f32 = 10.0; // Low cGMP in light
+
+/// Intensity that closes half of the cGMP-gated channels in a dark-adapted rod.
+/// Rods are ~1000x more sensitive than cones (single-photon detection).
+const HALF_SATURATION_INTENSITY: f32 = 0.05;
+
+/// How far light adaptation can shift the half-saturation intensity.
+/// Rods only adapt over ~1 log unit before saturating.
+const ADAPTATION_RANGE: f32 = 10.0;
+
+/// Time constant of the cGMP cascade (ms; rod kinetics are ~3x slower than cones)
+const CGMP_TIME_CONSTANT_MS: f32 = 316.0;
+
+/// Time constant of light adaptation (ms)
+const LIGHT_ADAPTATION_TIME_CONSTANT_MS: f32 = 6650.0;
+
+/// ATP used per second of phototransduction, and regenerated per second of recovery
+const ATP_CONSUMPTION_PER_S: f32 = 3.0;
+const ATP_RECOVERY_PER_S: f32 = 30.0;
+
+/// Represents a rod photoreceptor cell in the retina
+#[derive(Debug)]
+pub struct Rod {
+    id: usize,
+
+    // Anatomical segments
+    outer_segment_pigment: f32, // Rhodopsin concentration
+    inner_segment_atp: f32, // Energy reserves
+
+    // Physiological state
+    membrane_potential: f32,
+    cgmp_level: f32, // Cyclic GMP concentration
+    glutamate_release: f32,
+
+    // Adaptation state
+    adaptation_level: f32, // Light adaptation (0.0 = dark adapted, 1.0 = light adapted)
+
+    // Time represented by one update (ms)
+    frame_interval_ms: f32,
+
+    // Connection to downstream neurons
+    connected_neurons: Vec<usize>, // Rod bipolar cell IDs
+}
+
+impl Rod {
+    /// Creates a new rod photoreceptor
+    ///
+    /// # Arguments
+    /// * `id` - Unique identifier for this rod
+    pub fn new(id: usize) -> Self {
+        Self {
+            id,
+            outer_segment_pigment: 100.0,
+            inner_segment_atp: 100.0,
+            membrane_potential: DARK_POTENTIAL,
+            cgmp_level: CGMP_DARK_LEVEL,
+            glutamate_release: DARK_GLUTAMATE_RELEASE,
+            adaptation_level: 0.0,
+            frame_interval_ms: DEFAULT_FRAME_INTERVAL_MS,
+            connected_neurons: Vec::new(),
+        }
+    }
+
+    /// Returns the time represented by one phototransduction update (ms)
+    pub fn frame_interval_ms(&self) -> f32 {
+        self.frame_interval_ms
+    }
+
+    /// Changes the time represented by one phototransduction update (ms)
+    ///
+    /// The cGMP cascade, light adaptation and rhodopsin bleaching all
+    /// advance by this much per call of `transduce`.
+    pub fn set_frame_interval_ms(&mut self, frame_interval_ms: f32) {
+        self.frame_interval_ms = frame_interval_ms.max(f32::EPSILON);
+    }
+
+    /// Returns the rod's ID
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /// Returns the current membrane potential
+    pub fn membrane_potential(&self) -> f32 {
+        self.membrane_potential
+    }
+
+    /// Returns the current glutamate release rate
+    pub fn glutamate_release(&self) -> f32 {
+        self.glutamate_release
+    }
+
+    /// Returns the cGMP level
+    pub fn cgmp_level(&self) -> f32 {
+        self.cgmp_level
+    }
+
+    /// Connects this rod to a rod bipolar neuron
+    pub fn connect_to_neuron(&mut self, neuron_id: usize) {
+        if !self.connected_neurons.contains(&neuron_id) {
+            self.connected_neurons.push(neuron_id);
+        }
+    }
+
+    /// Phototransduction cascade: converts light into electrical signal
+    ///
+    /// Same cascade as in cones (rhodopsin → transducin → PDE → cGMP),
+    /// but with much higher gain and slower kinetics. The photocurrent
+    /// follows a saturating (Michaelis-Menten) function of intensity, so
+    /// rods are fully saturated at photopic light levels.
+    ///
+    /// # Arguments
+    /// * `light` - The light stimulus (single wavelength or full spectrum)
+    pub fn phototransduction<L: LightSource>(&mut self, light: L) {
+        // Calculate effective light intensity based on rhodopsin sensitivity
+        let effective_intensity = light.absorbed_intensity(rhodopsin_sensitivity);
+
+        self.transduce(effective_intensity);
+    }
+
+    /// Runs the phototransduction cascade from an already-computed excitation
+    ///
+    /// # Arguments
+    /// * `effective_intensity` - Light absorbed by rhodopsin
+    pub fn transduce(&mut self, effective_intensity: f32) {
+        // Only unbleached rhodopsin catches photons
+        let absorbed_intensity = effective_intensity * self.pigment_fraction();
+
+        // Limited light adaptation: shifts the operating range slightly upward
+        let half_saturation = HALF_SATURATION_INTENSITY * (1.0 + ADAPTATION_RANGE * self.adaptation_level);
+        let channel_closure = absorbed_intensity / (absorbed_intensity + half_saturation);
+
+        // Phototransduction cascade
+        // More light → less cGMP
+        let target_cgmp = CGMP_DARK_LEVEL - (CGMP_DARK_LEVEL - CGMP_LIGHT_LEVEL) * channel_closure;
+
+        // cGMP changes gradually (slower than in cones)
+        self.cgmp_level += (target_cgmp - self.cgmp_level) * relaxation_rate(CGMP_TIME_CONSTANT_MS, self.frame_interval_ms);
+        self.cgmp_level = self.cgmp_level.clamp(CGMP_LIGHT_LEVEL, CGMP_DARK_LEVEL);
+
+        // cGMP-gated channels: more cGMP → more open channels → more depolarized
+        let channel_opening = (self.cgmp_level - CGMP_LIGHT_LEVEL) / (CGMP_DARK_LEVEL - CGMP_LIGHT_LEVEL);
+        self.membrane_potential = LIGHT_POTENTIAL + (DARK_POTENTIAL - LIGHT_POTENTIAL) * channel_opening;
+
+        // Glutamate release is proportional to depolarization
+        self.glutamate_release = LIGHT_GLUTAMATE_RELEASE
+            + (DARK_GLUTAMATE_RELEASE - LIGHT_GLUTAMATE_RELEASE) * channel_opening;
+
+        // Light adaptation: gradually adapt to sustained light
+        let adaptation_rate = relaxation_rate(LIGHT_ADAPTATION_TIME_CONSTANT_MS, self.frame_interval_ms);
+        self.adaptation_level += (channel_closure - self.adaptation_level) * adaptation_rate;
+
+        // Absorbed photons bleach rhodopsin while regeneration continues
+        let step_s = self.frame_interval_ms / 1000.0;
+        self.advance_pigment(effective_intensity, step_s);
+
+        // Energy consumption (ATP usage)
+        self.inner_segment_atp = (self.inner_segment_atp - ATP_CONSUMPTION_PER_S * step_s).max(20.0);
+    }
+
+    /// Regenerates rhodopsin and ATP (one frame interval of recovery in darkness)
+    pub fn metabolic_recovery(&mut self) {
+        // Rhodopsin regenerates more slowly than cone pigment
+        let step_s = self.frame_interval_ms / 1000.0;
+        self.advance_pigment(0.0, step_s);
+
+        // Regenerate ATP
+        self.inner_segment_atp = (self.inner_segment_atp + ATP_RECOVERY_PER_S * step_s).min(100.0);
+    }
+
+    /// Returns the fraction of unbleached rhodopsin (1.0 = fully dark adapted)
+    ///
+    /// Sensitivity scales with this fraction: bleached rhodopsin catches no photons.
+    pub fn pigment_fraction(&self) -> f32 {
+        self.outer_segment_pigment / 100.0
+    }
+
+    /// Exposes the rod to a steady light, bleaching its rhodopsin
+    ///
+    /// # Arguments
+    /// * `effective_intensity` - Light absorbed by rhodopsin
+    /// * `duration_s` - Exposure time (s)
+    pub fn bleach(&mut self, effective_intensity: f32, duration_s: f32) {
+        self.advance_pigment(effective_intensity, duration_s);
+    }
+
+    /// Regenerates rhodopsin during a period of darkness
+    ///
+    /// # Arguments
+    /// * `duration_s` - Time in darkness (s)
+    pub fn dark_adapt(&mut self, duration_s: f32) {
+        self.advance_pigment(0.0, duration_s);
+    }
+
+    /// Returns the intensity needed to detect a flash at the current rhodopsin level
+    pub fn detection_threshold(&self) -> f32 {
+        PigmentKinetics::rod().threshold(self.pigment_fraction())
+    }
+
+    /// Updates the rhodopsin level under a constant intensity
+    fn advance_pigment(&mut self, intensity: f32, duration_s: f32) {
+        let fraction = PigmentKinetics::rod().advance(self.pigment_fraction(), intensity, duration_s);
+        self.outer_segment_pigment = fraction * 100.0;
+    }
+
+    /// Returns signals to be transmitted to connected neurons
+    ///
+    /// # Returns
+    /// Vector of (neuron_id, signal_strength) tuples
+    /// Note: Signal is inhibitory when glutamate is high (darkness)
+    pub fn transmit_to_neurons(&self) -> Vec<(usize, f32)> {
+        self.connected_neurons
+            .iter()
+            .map(|&neuron_id| {
+                let signal = -self.glutamate_release / 10.0 + 10.0; // Inverted signal
+                (neuron_id, signal)
+            })
+            .collect()
+    }
+
+    /// Returns the adaptation level
+    pub fn adaptation_level(&self) -> f32 {
+        self.adaptation_level
+    }
+
+    /// Returns the rod's response level (0.0 = dark, 1.0 = saturated)
+    ///
+    /// Rods need no extra amplification: their high gain already maps
+    /// dim light onto the full response range.
+    pub fn response_level(&self) -> f32 {
+        ((DARK_POTENTIAL - self.membrane_potential) / (DARK_POTENTIAL - LIGHT_POTENTIAL)).clamp(0.0, 1.0)
+    }
+
+    /// Returns whether the rod is saturated (no remaining dynamic range)
+    pub fn is_saturated(&self) -> bool {
+        self.response_level() > 0.95
+    }
+
+    /// Returns energy status (ATP percentage)
+    pub fn energy_level(&self) -> f32 {
+        self.inner_segment_atp
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cone::Cone;
+    use crate::photopigment::{ConeType, LightStimulus};
+
+    #[test]
+    fn test_rod_creation() {
+        let rod = Rod::new(3);
+        assert_eq!(rod.id(), 3);
+        assert_eq!(rod.membrane_potential(), DARK_POTENTIAL);
+        assert_eq!(rod.cgmp_level(), CGMP_DARK_LEVEL);
+    }
+
+    #[test]
+    fn test_rod_responds_to_dim_light() {
+        let mut rod = Rod::new(0);
+        let mut cone = Cone::new(1, ConeType::M);
+
+        // Starlight-level stimulus: rods respond, cones stay silent
+        for _ in 0..50 {
+            rod.phototransduction(LightStimulus::new(500.0, 0.05));
+            cone.phototransduction(LightStimulus::new(500.0, 0.05));
+        }
+
+        assert!(rod.response_level() > 0.3);
+        assert!(cone.response_level() < 0.1);
+    }
+
+    #[test]
+    fn test_rod_saturates_in_daylight() {
+        let mut rod = Rod::new(0);
+        for _ in 0..50 {
+            rod.phototransduction(LightStimulus::white_light(100.0));
+        }
+
+        assert!(rod.is_saturated());
+        assert!(rod.glutamate_release() < 20.0);
+    }
+
+    #[test]
+    fn test_rod_kinetics_slower_than_cone() {
+        let mut rod = Rod::new(0);
+        let mut cone = Cone::new(1, ConeType::M);
+
+        // Both dark adapted, then driven to saturation; compare the
+        // fraction of cGMP change after one step
+        rod.phototransduction(LightStimulus::darkness());
+        cone.phototransduction(LightStimulus::darkness());
+        rod.phototransduction(LightStimulus::new(530.0, 1000.0));
+        cone.phototransduction(LightStimulus::new(530.0, 1000.0));
+
+        let rod_drop = CGMP_DARK_LEVEL - rod.cgmp_level();
+        let cone_drop = CGMP_DARK_LEVEL - cone.cgmp_level();
+        assert!(rod_drop < cone_drop);
+    }
+
+    #[test]
+    fn test_purkinje_shift() {
+        let mut rod_blue = Rod::new(0);
+        let mut rod_red = Rod::new(1);
+
+        for _ in 0..30 {
+            rod_blue.phototransduction(LightStimulus::new(500.0, 0.02));
+            rod_red.phototransduction(LightStimulus::new(620.0, 0.02));
+        }
+
+        // At night, blue-green appears brighter than red of equal energy
+        assert!(rod_blue.response_level() > rod_red.response_level() * 2.0);
+    }
+}
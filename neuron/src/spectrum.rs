@@ -0,0 +1,315 @@
+//! Spectral power distributions for broadband light stimuli
+
+use std::ops::Add;
+
+use crate::photopigment::{LightSource, LightStimulus};
+
+/// Shortest sampled wavelength in nanometers
+pub const MIN_WAVELENGTH: f32 = 380.0;
+
+/// Longest sampled wavelength in nanometers
+pub const MAX_WAVELENGTH: f32 = 780.0;
+
+/// Sampling interval in nanometers
+pub const WAVELENGTH_STEP: f32 = 5.0;
+
+/// Number of spectral samples (380-780 nm in 5 nm bins)
+pub const SAMPLE_COUNT: usize = 81;
+
+/// CIE standard illuminant D65 (average daylight), 380-780 nm in 10 nm steps
+const D65_10NM: [f32; 41] = [
+    49.98, 54.65, 82.75, 91.49, 93.43, 86.68, 104.87, 117.01, 117.81, 114.86, 115.92, 108.81,
+    109.35, 107.80, 104.79, 107.69, 104.41, 104.05, 100.00, 96.33, 95.79, 88.69, 90.01, 89.60,
+    87.70, 83.29, 83.70, 80.03, 80.21, 82.28, 78.28, 69.72, 71.61, 74.35, 61.60, 69.89, 75.09,
+    63.59, 46.42, 66.81, 63.38,
+];
+
+/// Colour temperature of CIE standard illuminant A (incandescent) in kelvin
+const ILLUMINANT_A_TEMPERATURE: f32 = 2856.0;
+
+/// Photopic luminous efficiency V(λ)
+///
+/// Gaussian fit of the CIE 1924 curve, peaking at 555-559 nm
+pub fn luminous_efficiency(wavelength: f32) -> f32 {
+    let microns = wavelength / 1000.0;
+    (1.019 * (-285.4 * (microns - 0.559).powi(2)).exp()).min(1.0)
+}
+
+/// Spectral power distribution sampled from 380 to 780 nm in 5 nm bins
+///
+/// Each sample is the spectral power per nanometer (arbitrary units), so the
+/// total power is the sum of the samples times the bin width.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpectralPowerDistribution {
+    samples: [f32; SAMPLE_COUNT],
+}
+
+impl SpectralPowerDistribution {
+    /// Creates a distribution from raw samples (power per nm at each 5 nm bin)
+    pub fn new(samples: [f32; SAMPLE_COUNT]) -> Self {
+        Self {
+            samples: samples.map(|s| s.max(0.0)),
+        }
+    }
+
+    /// Creates a distribution by evaluating a function at every sampled wavelength
+    pub fn from_fn<F: Fn(f32) -> f32>(power: F) -> Self {
+        let mut samples = [0.0; SAMPLE_COUNT];
+        for (i, sample) in samples.iter_mut().enumerate() {
+            *sample = power(Self::wavelength_at(i)).max(0.0);
+        }
+        Self { samples }
+    }
+
+    /// Creates darkness (no light at any wavelength)
+    pub fn darkness() -> Self {
+        Self {
+            samples: [0.0; SAMPLE_COUNT],
+        }
+    }
+
+    /// Creates an equal-energy spectrum with the given luminance
+    pub fn equal_energy(intensity: f32) -> Self {
+        Self::from_fn(|_| 1.0).with_luminance(intensity)
+    }
+
+    /// Creates narrowband light concentrated at a single wavelength
+    ///
+    /// The power is split between the two nearest bins so that the absorbed
+    /// intensity matches the equivalent `LightStimulus`.
+    pub fn monochromatic(wavelength: f32, intensity: f32) -> Self {
+        let mut samples = [0.0; SAMPLE_COUNT];
+        let position = (wavelength.clamp(MIN_WAVELENGTH, MAX_WAVELENGTH) - MIN_WAVELENGTH)
+            / WAVELENGTH_STEP;
+        let lower = (position.floor() as usize).min(SAMPLE_COUNT - 1);
+        let upper = (lower + 1).min(SAMPLE_COUNT - 1);
+        let fraction = position - lower as f32;
+        let power = intensity.max(0.0) / WAVELENGTH_STEP;
+
+        samples[lower] += power * (1.0 - fraction);
+        samples[upper] += power * fraction;
+
+        Self { samples }
+    }
+
+    /// CIE standard illuminant D65 (average daylight) with the given luminance
+    pub fn d65(intensity: f32) -> Self {
+        Self::from_fn(|wavelength| {
+            let position = (wavelength - MIN_WAVELENGTH) / 10.0;
+            let lower = (position.floor() as usize).min(D65_10NM.len() - 1);
+            let upper = (lower + 1).min(D65_10NM.len() - 1);
+            let fraction = position - lower as f32;
+            D65_10NM[lower] * (1.0 - fraction) + D65_10NM[upper] * fraction
+        })
+        .with_luminance(intensity)
+    }
+
+    /// CIE standard illuminant A (tungsten filament lamp) with the given luminance
+    pub fn illuminant_a(intensity: f32) -> Self {
+        Self::blackbody(ILLUMINANT_A_TEMPERATURE, intensity)
+    }
+
+    /// Planckian (blackbody) radiator at the given temperature with the given luminance
+    pub fn blackbody(temperature_kelvin: f32, intensity: f32) -> Self {
+        // Second radiation constant c2 = hc/k in nm·K
+        const C2: f32 = 1.4388e7;
+        Self::from_fn(|wavelength| {
+            let relative = 560.0 / wavelength;
+            relative.powi(5) * ((C2 / (560.0 * temperature_kelvin)).exp() - 1.0)
+                / ((C2 / (wavelength * temperature_kelvin)).exp() - 1.0)
+        })
+        .with_luminance(intensity)
+    }
+
+    /// Phosphor-converted white LED (blue pump at 450 nm plus broad yellow phosphor)
+    pub fn led_white(intensity: f32) -> Self {
+        Self::from_fn(|wavelength| {
+            let pump = (-(wavelength - 450.0).powi(2) / (2.0 * 10.0_f32.powi(2))).exp();
+            let phosphor = 0.6 * (-(wavelength - 560.0).powi(2) / (2.0 * 50.0_f32.powi(2))).exp();
+            pump + phosphor
+        })
+        .with_luminance(intensity)
+    }
+
+    /// Returns the wavelength of sample `index` in nanometers
+    pub fn wavelength_at(index: usize) -> f32 {
+        MIN_WAVELENGTH + index as f32 * WAVELENGTH_STEP
+    }
+
+    /// Returns the raw samples (power per nm)
+    pub fn samples(&self) -> &[f32; SAMPLE_COUNT] {
+        &self.samples
+    }
+
+    /// Returns the spectral power at a given wavelength (linear interpolation)
+    pub fn power_at(&self, wavelength: f32) -> f32 {
+        if !(MIN_WAVELENGTH..=MAX_WAVELENGTH).contains(&wavelength) {
+            return 0.0;
+        }
+        let position = (wavelength - MIN_WAVELENGTH) / WAVELENGTH_STEP;
+        let lower = (position.floor() as usize).min(SAMPLE_COUNT - 1);
+        let upper = (lower + 1).min(SAMPLE_COUNT - 1);
+        let fraction = position - lower as f32;
+        self.samples[lower] * (1.0 - fraction) + self.samples[upper] * fraction
+    }
+
+    /// Integrates the spectrum against a spectral weighting function
+    pub fn integrate<F: Fn(f32) -> f32>(&self, weighting: F) -> f32 {
+        self.samples
+            .iter()
+            .enumerate()
+            .map(|(i, &power)| power * weighting(Self::wavelength_at(i)))
+            .sum::<f32>()
+            * WAVELENGTH_STEP
+    }
+
+    /// Returns the total radiant power
+    pub fn total_power(&self) -> f32 {
+        self.integrate(|_| 1.0)
+    }
+
+    /// Returns the photopic luminance (power weighted by V(λ))
+    pub fn luminance(&self) -> f32 {
+        self.integrate(luminous_efficiency)
+    }
+
+    /// Returns the wavelength with the most power
+    pub fn peak_wavelength(&self) -> f32 {
+        let (index, _) = self
+            .samples
+            .iter()
+            .enumerate()
+            .fold((0, f32::MIN), |best, (i, &p)| if p > best.1 { (i, p) } else { best });
+        Self::wavelength_at(index)
+    }
+
+    /// Returns a copy scaled by a constant factor
+    pub fn scaled(&self, factor: f32) -> Self {
+        Self::new(self.samples.map(|s| s * factor))
+    }
+
+    /// Returns a copy rescaled to the given photopic luminance
+    pub fn with_luminance(&self, intensity: f32) -> Self {
+        let luminance = self.luminance();
+        if luminance > 0.0 {
+            self.scaled(intensity / luminance)
+        } else {
+            self.clone()
+        }
+    }
+
+    /// Returns a copy filtered by a wavelength-dependent transmittance or reflectance
+    ///
+    /// # Arguments
+    /// * `transmittance` - Fraction of light passed at each wavelength (0.0 to 1.0)
+    pub fn filtered<F: Fn(f32) -> f32>(&self, transmittance: F) -> Self {
+        let mut samples = self.samples;
+        for (i, sample) in samples.iter_mut().enumerate() {
+            *sample *= transmittance(Self::wavelength_at(i)).clamp(0.0, 1.0);
+        }
+        Self { samples }
+    }
+}
+
+impl Default for SpectralPowerDistribution {
+    fn default() -> Self {
+        Self::darkness()
+    }
+}
+
+impl Add for SpectralPowerDistribution {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        let mut samples = self.samples;
+        for (sample, &extra) in samples.iter_mut().zip(other.samples.iter()) {
+            *sample += extra;
+        }
+        Self { samples }
+    }
+}
+
+impl From<LightStimulus> for SpectralPowerDistribution {
+    fn from(light: LightStimulus) -> Self {
+        Self::monochromatic(light.wavelength, light.intensity)
+    }
+}
+
+impl LightSource for SpectralPowerDistribution {
+    fn absorbed_intensity<F: Fn(f32) -> f32>(&self, sensitivity: F) -> f32 {
+        self.integrate(sensitivity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::photopigment::ConeType;
+
+    #[test]
+    fn test_sampling_grid() {
+        assert_eq!(SpectralPowerDistribution::wavelength_at(0), MIN_WAVELENGTH);
+        assert_eq!(SpectralPowerDistribution::wavelength_at(SAMPLE_COUNT - 1), MAX_WAVELENGTH);
+    }
+
+    #[test]
+    fn test_monochromatic_matches_light_stimulus() {
+        let light = LightStimulus::new(532.0, 80.0);
+        let spd = SpectralPowerDistribution::from(light);
+
+        for cone_type in [ConeType::S, ConeType::M, ConeType::L] {
+            let narrowband = light.absorbed_intensity(|w| cone_type.spectral_sensitivity(w));
+            let spectral = spd.absorbed_intensity(|w| cone_type.spectral_sensitivity(w));
+            assert!((narrowband - spectral).abs() < 1.0);
+        }
+    }
+
+    #[test]
+    fn test_illuminant_presets_luminance() {
+        for spd in [
+            SpectralPowerDistribution::d65(100.0),
+            SpectralPowerDistribution::illuminant_a(100.0),
+            SpectralPowerDistribution::led_white(100.0),
+            SpectralPowerDistribution::equal_energy(100.0),
+        ] {
+            assert!((spd.luminance() - 100.0).abs() < 0.1);
+        }
+    }
+
+    #[test]
+    fn test_illuminant_colour_balance() {
+        let d65 = SpectralPowerDistribution::d65(100.0);
+        let a = SpectralPowerDistribution::illuminant_a(100.0);
+
+        // Incandescent light is much redder than daylight
+        assert!(a.power_at(450.0) / a.power_at(650.0) < d65.power_at(450.0) / d65.power_at(650.0));
+        assert_eq!(SpectralPowerDistribution::led_white(100.0).peak_wavelength(), 450.0);
+    }
+
+    #[test]
+    fn test_white_light_excites_all_cones() {
+        let d65 = SpectralPowerDistribution::d65(100.0);
+        let s = d65.absorbed_intensity(|w| ConeType::S.spectral_sensitivity(w));
+        let m = d65.absorbed_intensity(|w| ConeType::M.spectral_sensitivity(w));
+        let l = d65.absorbed_intensity(|w| ConeType::L.spectral_sensitivity(w));
+
+        // A 555 nm stand-in would leave S-cones nearly silent
+        assert!(s > 10.0);
+        assert!(m > 10.0);
+        assert!(l > 10.0);
+    }
+
+    #[test]
+    fn test_magenta_is_broadband() {
+        let magenta = SpectralPowerDistribution::monochromatic(440.0, 50.0)
+            + SpectralPowerDistribution::monochromatic(620.0, 50.0);
+
+        let s = magenta.absorbed_intensity(|w| ConeType::S.spectral_sensitivity(w));
+        let m = magenta.absorbed_intensity(|w| ConeType::M.spectral_sensitivity(w));
+        let l = magenta.absorbed_intensity(|w| ConeType::L.spectral_sensitivity(w));
+
+        // Magenta drives S and L cones but spares M: no single wavelength can do that
+        assert!(s > m);
+        assert!(l > m);
+    }
+}
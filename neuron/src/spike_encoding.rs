@@ -0,0 +1,275 @@
+//! Spike encoding - Converting ganglion cell drive into spike trains
+//!
+//! Ganglion cells are the only retinal neurons that fire action
+//! potentials; everything upstream signals with graded potentials. This
+//! stage turns the analog ganglion firing rate into spike times over a
+//! time window, in 1 ms bins to match `NeuralNetwork::step`:
+//! - Poisson: each bin fires independently with probability rate × dt
+//! - Leaky integrate-and-fire: the drive charges a leaky membrane that
+//!   fires and resets at threshold, giving regular spike trains
+//! - Latency: one spike whose delay shrinks as the drive grows
+//!   (first-spike coding)
+
+use crate::ganglion::GanglionLayer;
+use crate::rng::SeededRng;
+
+/// Highest firing rate a ganglion cell can reach (Hz)
+const DEFAULT_MAX_RATE: f32 = 200.0;
+
+/// Membrane time constant of the LIF encoder (ms)
+const DEFAULT_MEMBRANE_TIME_CONSTANT: f32 = 10.0;
+
+/// Input current at the maximum rate, in units of the firing threshold
+const LIF_INPUT_GAIN: f32 = 3.0;
+
+/// Absolute refractory period of the LIF encoder (ms)
+const LIF_REFRACTORY_MS: u32 = 2;
+
+/// Depolarization delivered to a network neuron by one retinal spike (mV)
+pub const RETINAL_SPIKE_AMPLITUDE: f32 = 25.0;
+
+/// Scheme used to turn a firing rate into spike times
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpikeCoding {
+    /// Independent spikes with probability rate × dt per bin
+    Poisson,
+    /// Leaky integrate-and-fire membrane driven by the rate
+    LeakyIntegrateAndFire,
+    /// A single spike, earlier for stronger drive
+    Latency,
+}
+
+/// Spike times of one ganglion cell
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpikeTrain {
+    /// ID of the ganglion cell that fired
+    pub cell_id: usize,
+    /// Spike times (ms from the start of the window), in increasing order
+    pub spike_times: Vec<u32>,
+}
+
+impl SpikeTrain {
+    /// Returns the number of spikes in the train
+    pub fn spike_count(&self) -> usize {
+        self.spike_times.len()
+    }
+
+    /// Returns the time of the first spike, if any
+    pub fn first_spike(&self) -> Option<u32> {
+        self.spike_times.first().copied()
+    }
+
+    /// Returns whether the cell fired at a given time
+    pub fn fires_at(&self, time_ms: u32) -> bool {
+        self.spike_times.binary_search(&time_ms).is_ok()
+    }
+
+    /// Returns the mean firing rate over a window (Hz)
+    pub fn mean_rate(&self, window_ms: u32) -> f32 {
+        if window_ms == 0 {
+            return 0.0;
+        }
+        self.spike_count() as f32 * 1000.0 / window_ms as f32
+    }
+}
+
+/// Encoder turning ganglion firing rates into spike trains
+#[derive(Debug, Clone)]
+pub struct SpikeEncoder {
+    coding: SpikeCoding,
+    window_ms: u32,
+    max_rate: f32,
+    time_constant: f32,
+    rng: SeededRng,
+}
+
+impl SpikeEncoder {
+    /// Creates a new encoder
+    ///
+    /// Ganglion firing rates are read as spikes per second and capped at
+    /// 200 Hz.
+    ///
+    /// # Arguments
+    /// * `coding` - Poisson, LIF or latency coding
+    /// * `window_ms` - Length of the encoded spike trains (ms)
+    pub fn new(coding: SpikeCoding, window_ms: u32) -> Self {
+        Self {
+            coding,
+            window_ms,
+            max_rate: DEFAULT_MAX_RATE,
+            time_constant: DEFAULT_MEMBRANE_TIME_CONSTANT,
+            rng: SeededRng::new(0),
+        }
+    }
+
+    /// Returns a copy with a different maximum rate (Hz)
+    pub fn with_max_rate(mut self, max_rate: f32) -> Self {
+        self.max_rate = max_rate.max(f32::EPSILON);
+        self
+    }
+
+    /// Returns a copy with a different LIF membrane time constant (ms)
+    pub fn with_time_constant(mut self, time_constant: f32) -> Self {
+        self.time_constant = time_constant.max(1.0);
+        self
+    }
+
+    /// Returns a copy whose Poisson spikes are drawn from a different seed
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = SeededRng::new(seed);
+        self
+    }
+
+    /// Returns the coding scheme
+    pub fn coding(&self) -> SpikeCoding {
+        self.coding
+    }
+
+    /// Returns the length of the encoded window (ms)
+    pub fn window_ms(&self) -> u32 {
+        self.window_ms
+    }
+
+    /// Returns the maximum rate (Hz)
+    pub fn max_rate(&self) -> f32 {
+        self.max_rate
+    }
+
+    /// Encodes one firing rate as spike times within the window
+    ///
+    /// # Arguments
+    /// * `rate` - Firing rate (Hz); negative values are treated as 0
+    ///
+    /// # Returns
+    /// Spike times in ms from the start of the window
+    pub fn encode_rate(&mut self, rate: f32) -> Vec<u32> {
+        let rate = rate.clamp(0.0, self.max_rate);
+        if rate <= 0.0 {
+            return Vec::new();
+        }
+
+        match self.coding {
+            SpikeCoding::Poisson => {
+                let probability = rate / 1000.0;
+                (0..self.window_ms).filter(|_| self.rng.chance(probability)).collect()
+            }
+            SpikeCoding::LeakyIntegrateAndFire => {
+                let current = LIF_INPUT_GAIN * rate / self.max_rate;
+                let mut potential = 0.0;
+                let mut refractory = 0;
+                let mut spikes = Vec::new();
+                for time in 0..self.window_ms {
+                    if refractory > 0 {
+                        refractory -= 1;
+                        continue;
+                    }
+                    potential += (current - potential) / self.time_constant;
+                    if potential >= 1.0 {
+                        spikes.push(time);
+                        potential = 0.0;
+                        refractory = LIF_REFRACTORY_MS;
+                    }
+                }
+                spikes
+            }
+            SpikeCoding::Latency => {
+                let last = self.window_ms.saturating_sub(1) as f32;
+                let delay = (last * (1.0 - rate / self.max_rate)).round() as u32;
+                if self.window_ms > 0 {
+                    vec![delay]
+                } else {
+                    Vec::new()
+                }
+            }
+        }
+    }
+
+    /// Encodes the current firing rate of every cell in a ganglion layer
+    ///
+    /// # Returns
+    /// One spike train per cell, in the layer's cell order
+    pub fn encode_layer(&mut self, layer: &GanglionLayer) -> Vec<SpikeTrain> {
+        layer
+            .cells()
+            .iter()
+            .map(|cell| SpikeTrain {
+                cell_id: cell.id(),
+                spike_times: self.encode_rate(cell.firing_rate()),
+            })
+            .collect()
+    }
+}
+
+/// Returns the network inputs produced by the spikes at one time
+///
+/// # Arguments
+/// * `trains` - Spike trains to deliver
+/// * `targets` - Network neuron receiving each train (same order as `trains`)
+/// * `time_ms` - Time within the spike trains' window
+pub fn spike_inputs(trains: &[SpikeTrain], targets: &[usize], time_ms: u32) -> Vec<(usize, f32)> {
+    trains
+        .iter()
+        .zip(targets)
+        .filter(|(train, _)| train.fires_at(time_ms))
+        .map(|(_, &target)| (target, RETINAL_SPIKE_AMPLITUDE))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_poisson_rate_matches_drive() {
+        let mut encoder = SpikeEncoder::new(SpikeCoding::Poisson, 10_000).with_seed(7);
+        let spikes = encoder.encode_rate(50.0);
+        let train = SpikeTrain { cell_id: 0, spike_times: spikes };
+
+        assert!((train.mean_rate(10_000) - 50.0).abs() < 5.0);
+        assert!(encoder.encode_rate(0.0).is_empty());
+        assert!(train.spike_times.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_poisson_is_reproducible_from_seed() {
+        let mut a = SpikeEncoder::new(SpikeCoding::Poisson, 500).with_seed(3);
+        let mut b = SpikeEncoder::new(SpikeCoding::Poisson, 500).with_seed(3);
+        assert_eq!(a.encode_rate(80.0), b.encode_rate(80.0));
+    }
+
+    #[test]
+    fn test_lif_fires_regularly_and_faster_for_stronger_drive() {
+        let mut encoder = SpikeEncoder::new(SpikeCoding::LeakyIntegrateAndFire, 1000);
+        let weak = encoder.encode_rate(100.0);
+        let strong = encoder.encode_rate(200.0);
+
+        assert!(encoder.encode_rate(50.0).is_empty()); // below rheobase
+        assert!(strong.len() > weak.len() && !weak.is_empty());
+
+        let intervals: Vec<u32> = strong.windows(2).map(|pair| pair[1] - pair[0]).collect();
+        assert!(intervals.iter().all(|&interval| interval == intervals[0]));
+    }
+
+    #[test]
+    fn test_latency_coding_fires_once_earlier_for_stronger_drive() {
+        let mut encoder = SpikeEncoder::new(SpikeCoding::Latency, 50);
+        let weak = encoder.encode_rate(20.0);
+        let strong = encoder.encode_rate(180.0);
+
+        assert_eq!(weak.len(), 1);
+        assert!(strong[0] < weak[0]);
+        assert_eq!(encoder.encode_rate(1000.0), vec![0]);
+    }
+
+    #[test]
+    fn test_spike_inputs_target_mapped_neurons() {
+        let trains = vec![
+            SpikeTrain { cell_id: 0, spike_times: vec![1, 4] },
+            SpikeTrain { cell_id: 1, spike_times: vec![4] },
+        ];
+
+        assert_eq!(spike_inputs(&trains, &[10, 11], 1), vec![(10, RETINAL_SPIKE_AMPLITUDE)]);
+        assert_eq!(spike_inputs(&trains, &[10, 11], 4).len(), 2);
+        assert!(spike_inputs(&trains, &[10, 11], 2).is_empty());
+    }
+}
@@ -0,0 +1,233 @@
+//! Temporal filtering - Biphasic impulse responses for dynamic stimuli
+//!
+//! Visual neurons do not respond to light instantaneously: their impulse
+//! response rises to a peak after tens of milliseconds, then swings below
+//! baseline before settling. This biphasic shape makes them band-pass in
+//! time, so they respond most strongly to change.
+//!
+//! The impulse response is modelled as the difference between a fast and
+//! a slow low-pass filter, each a cascade of first-order exponential
+//! stages (a gamma-shaped response). The slow lobe is subtracted with a
+//! rebound weight and the result is scaled to unit DC gain, so a static
+//! sequence settles to the response to a single static image.
+
+/// Number of exponential stages in each low-pass cascade
+const FILTER_STAGES: usize = 3;
+
+/// Default frame interval (ms), 30 frames per second
+pub const DEFAULT_FRAME_INTERVAL_MS: f32 = 1000.0 / 30.0;
+
+/// Shape of a biphasic temporal impulse response
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TemporalProfile {
+    /// Time constant of each stage of the fast (positive) lobe (ms)
+    pub fast_time_constant: f32,
+    /// Time constant of each stage of the slow (negative) lobe (ms)
+    pub slow_time_constant: f32,
+    /// Weight of the slow lobe (0.0 = monophasic, towards 1.0 = purely transient)
+    pub rebound: f32,
+}
+
+impl TemporalProfile {
+    /// Creates a new temporal profile
+    ///
+    /// # Arguments
+    /// * `fast_time_constant` - Stage time constant of the positive lobe (ms)
+    /// * `slow_time_constant` - Stage time constant of the negative lobe (ms)
+    /// * `rebound` - Weight of the negative lobe (0.0 to 0.95)
+    pub fn new(fast_time_constant: f32, slow_time_constant: f32, rebound: f32) -> Self {
+        let fast_time_constant = fast_time_constant.max(0.0);
+        Self {
+            fast_time_constant,
+            slow_time_constant: slow_time_constant.max(fast_time_constant),
+            rebound: rebound.clamp(0.0, 0.95),
+        }
+    }
+
+    /// Retinal ganglion cells: peak after ~30 ms, rebound at ~80 ms
+    pub fn ganglion() -> Self {
+        Self::new(15.0, 40.0, 0.5)
+    }
+
+    /// V1 neurons: slower and slightly more sustained than the retina
+    pub fn v1() -> Self {
+        Self::new(25.0, 60.0, 0.4)
+    }
+
+    /// V2 neurons: the slowest and most sustained of the three
+    pub fn v2() -> Self {
+        Self::new(35.0, 80.0, 0.3)
+    }
+
+    /// Returns the response to a one-frame pulse of unit height
+    ///
+    /// # Arguments
+    /// * `frame_interval_ms` - Time between frames (ms)
+    /// * `frames` - Number of frames to return
+    pub fn impulse_response(&self, frame_interval_ms: f32, frames: usize) -> Vec<f32> {
+        let mut filter = TemporalFilter::new(*self, frame_interval_ms);
+        (0..frames)
+            .map(|frame| {
+                let input = if frame == 0 { 1.0 } else { 0.0 };
+                filter.filter(&[vec![input]])[0][0]
+            })
+            .collect()
+    }
+}
+
+/// Biphasic temporal filter applied independently at every pixel of a map
+#[derive(Debug, Clone)]
+pub struct TemporalFilter {
+    profile: TemporalProfile,
+    frame_interval_ms: f32,
+    // Per-stage state of the fast and slow cascades
+    fast: Vec<Vec<Vec<f32>>>,
+    slow: Vec<Vec<Vec<f32>>>,
+}
+
+impl TemporalFilter {
+    /// Creates a new filter in its resting (zero) state
+    ///
+    /// # Arguments
+    /// * `profile` - Shape of the impulse response
+    /// * `frame_interval_ms` - Time between successive frames (ms)
+    pub fn new(profile: TemporalProfile, frame_interval_ms: f32) -> Self {
+        Self {
+            profile,
+            frame_interval_ms: frame_interval_ms.max(f32::EPSILON),
+            fast: Vec::new(),
+            slow: Vec::new(),
+        }
+    }
+
+    /// Returns the impulse response profile
+    pub fn profile(&self) -> TemporalProfile {
+        self.profile
+    }
+
+    /// Returns the time between frames (ms)
+    pub fn frame_interval_ms(&self) -> f32 {
+        self.frame_interval_ms
+    }
+
+    /// Changes the time between frames (ms); the state is kept
+    pub fn set_frame_interval_ms(&mut self, frame_interval_ms: f32) {
+        self.frame_interval_ms = frame_interval_ms.max(f32::EPSILON);
+    }
+
+    /// Filters one frame and advances the state by one frame interval
+    ///
+    /// The state restarts from rest if the map size changes.
+    ///
+    /// # Arguments
+    /// * `input` - Map of values for this frame
+    ///
+    /// # Returns
+    /// Filtered map (may be negative during the rebound lobe)
+    pub fn filter(&mut self, input: &[Vec<f32>]) -> Vec<Vec<f32>> {
+        let height = input.len();
+        let width = if height > 0 { input[0].len() } else { 0 };
+        let state_shape = self.fast.first().map(|stage| (stage.len(), stage.first().map_or(0, Vec::len)));
+        if state_shape != Some((height, width)) {
+            self.fast = vec![vec![vec![0.0; width]; height]; FILTER_STAGES];
+            self.slow = vec![vec![vec![0.0; width]; height]; FILTER_STAGES];
+        }
+
+        let fast_rate = self.stage_rate(self.profile.fast_time_constant);
+        let slow_rate = self.stage_rate(self.profile.slow_time_constant);
+        let rebound = self.profile.rebound;
+        let mut output = vec![vec![0.0; width]; height];
+
+        for (y, row) in input.iter().enumerate() {
+            for (x, &value) in row.iter().enumerate() {
+                let fast = cascade(&mut self.fast, x, y, value, fast_rate);
+                let slow = cascade(&mut self.slow, x, y, value, slow_rate);
+                output[y][x] = (fast - rebound * slow) / (1.0 - rebound);
+            }
+        }
+
+        output
+    }
+
+    /// Returns the filter to its resting state
+    pub fn reset(&mut self) {
+        self.fast.clear();
+        self.slow.clear();
+    }
+
+    /// Fraction of the remaining difference one stage closes per frame
+    fn stage_rate(&self, time_constant: f32) -> f32 {
+        relaxation_rate(time_constant, self.frame_interval_ms)
+    }
+}
+
+/// Fraction of the way a first-order process relaxes towards its target in a time step
+///
+/// # Arguments
+/// * `time_constant_ms` - Time constant of the process (ms; 0.0 = instantaneous)
+/// * `interval_ms` - Length of the time step (ms)
+pub fn relaxation_rate(time_constant_ms: f32, interval_ms: f32) -> f32 {
+    if time_constant_ms <= f32::EPSILON {
+        return 1.0;
+    }
+    1.0 - (-interval_ms.max(0.0) / time_constant_ms).exp()
+}
+
+/// Passes a value through every stage of a cascade at one pixel
+fn cascade(stages: &mut [Vec<Vec<f32>>], x: usize, y: usize, input: f32, rate: f32) -> f32 {
+    let mut signal = input;
+    for stage in stages.iter_mut() {
+        let state = &mut stage[y][x];
+        *state += (signal - *state) * rate;
+        signal = *state;
+    }
+    signal
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_impulse_response_is_biphasic() {
+        let response = TemporalProfile::ganglion().impulse_response(5.0, 80);
+        let peak = response.iter().cloned().fold(f32::MIN, f32::max);
+        let trough = response.iter().cloned().fold(f32::MAX, f32::min);
+        let peak_frame = response.iter().position(|&v| v == peak).unwrap();
+        let trough_frame = response.iter().position(|&v| v == trough).unwrap();
+
+        assert!(peak > 0.0 && trough < 0.0);
+        assert!(peak_frame < trough_frame);
+        // Unit DC gain: the lobes sum to one pulse
+        assert!((response.iter().sum::<f32>() - 1.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_step_overshoots_then_settles() {
+        let mut filter = TemporalFilter::new(TemporalProfile::v1(), DEFAULT_FRAME_INTERVAL_MS);
+        let frame = vec![vec![1.0, 0.0]];
+        let outputs: Vec<f32> = (0..40).map(|_| filter.filter(&frame)[0][0]).collect();
+
+        assert!(outputs.iter().cloned().fold(0.0, f32::max) > 1.1);
+        assert!((outputs[39] - 1.0).abs() < 0.01);
+        assert_eq!(filter.filter(&frame)[0][1], 0.0);
+    }
+
+    #[test]
+    fn test_monophasic_profile_never_rebounds() {
+        let response = TemporalProfile::new(10.0, 10.0, 0.0).impulse_response(5.0, 40);
+        assert!(response.iter().all(|&v| v >= 0.0));
+    }
+
+    #[test]
+    fn test_reset_and_resize_restart_from_rest() {
+        let mut filter = TemporalFilter::new(TemporalProfile::v2(), 10.0);
+        let first = filter.filter(&[vec![1.0]])[0][0];
+        filter.filter(&[vec![1.0]]);
+        filter.reset();
+        assert_eq!(filter.filter(&[vec![1.0]])[0][0], first);
+
+        let resized = filter.filter(&vec![vec![1.0; 3]; 2]);
+        assert!(resized.iter().flatten().all(|&v| v == first));
+    }
+}
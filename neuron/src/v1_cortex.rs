@@ -1,7 +1,48 @@
 //! V1 Cortex - Primary visual cortex with orientation-selective neurons
+//!
+//! Simple cells have Gabor receptive fields: a sinusoidal carrier under an
+//! elongated Gaussian envelope. The carrier's wavelength sets the preferred
+//! spatial frequency, its phase whether the field is even (bar-like, ON or
+//! OFF centre) or odd (edge-like), and the envelope's sigma and aspect
+//! ratio set the size and elongation. Each column holds even and odd simple
+//! cells of both polarities (phases 0°, 90°, 180° and 270°) and a complex
+//! cell, which pools the energy of quadrature pairs over a small
+//! neighbourhood and so responds regardless of the stimulus phase.
+//!
+//! The cortex tiles orientation with evenly spaced channels (four by
+//! default). Reading out all channels at a location as a population vector
+//! gives a continuous orientation and a coherence, finer than the spacing
+//! of the channels.
 
+use crate::temporal::{relaxation_rate, DEFAULT_FRAME_INTERVAL_MS};
+use std::collections::HashMap;
 use std::f32::consts::PI;
 
+/// Fatigue accumulated per unit of sustained activation; at equilibrium a
+/// neuron driven to activation 20 responds at half strength
+const ADAPTATION_STRENGTH: f32 = 0.05;
+
+/// Time constant with which fatigue moves towards its equilibrium (ms);
+/// cortical adaptation builds up and recovers over seconds
+const ADAPTATION_TIME_CONSTANT_MS: f32 = 650.0;
+
+/// Gain applied to the Gabor output; with positive lobes summing to 1, an
+/// input matching the lobes at level v drives the neuron to about 2.5 v
+const GABOR_GAIN: f32 = 2.5;
+
+/// Default envelope elongation (width across / length along the axis)
+const DEFAULT_ASPECT_RATIO: f32 = 0.5;
+
+/// Number of orientation channels of a default cortex (every 45 degrees)
+const DEFAULT_ORIENTATION_COUNT: usize = 4;
+
+/// Column activation below which a location counts as silent
+const ACTIVE_THRESHOLD: f32 = 0.1;
+
+/// Distance between the quadrature-pair subunits a complex cell pools
+/// (pixels); the subunits form a 3 × 3 grid around the cell
+const COMPLEX_SUBUNIT_SPACING: i32 = 2;
+
 /// Orientation preference of a V1 neuron (in degrees)
 #[derive(Debug, Clone, Copy)]
 pub struct Orientation(f32);
@@ -40,12 +81,226 @@ impl Orientation {
     }
 }
 
+/// Orientation read out from a population of orientation channels
+///
+/// Each channel votes with its activation on a doubled-angle circle, so
+/// 0° and 180° count as the same orientation. The vector sum gives a
+/// continuous orientation between the channels' preferred orientations.
+#[derive(Debug, Clone, Copy)]
+pub struct OrientationEstimate {
+    /// Orientation of the population vector
+    pub orientation: Orientation,
+
+    /// Length of the population vector relative to the total activation
+    /// (0.0 = no orientation preferred, 1.0 = a single channel active)
+    pub coherence: f32,
+
+    /// Total activation of the channels
+    pub strength: f32,
+}
+
+impl OrientationEstimate {
+    /// Decodes the population vector of a tuning curve
+    ///
+    /// # Arguments
+    /// * `tuning` - Activation of each orientation channel
+    ///
+    /// # Returns
+    /// The estimate, or `None` if the channels are silent or cancel out
+    pub fn from_tuning(tuning: &[(Orientation, f32)]) -> Option<Self> {
+        let (mut x, mut y, mut strength) = (0.0f32, 0.0f32, 0.0f32);
+        for &(orientation, activation) in tuning {
+            let activation = activation.max(0.0);
+            x += activation * (2.0 * orientation.radians()).cos();
+            y += activation * (2.0 * orientation.radians()).sin();
+            strength += activation;
+        }
+        let length = x.hypot(y);
+        if length <= f32::EPSILON * strength.max(1.0) {
+            return None;
+        }
+        Some(Self {
+            orientation: Orientation::new((y.atan2(x).to_degrees() / 2.0).rem_euclid(180.0)),
+            coherence: (length / strength).min(1.0),
+            strength,
+        })
+    }
+}
+
+/// Gabor receptive field of a V1 neuron
+///
+/// With x' across and y' along the preferred orientation, the weight at an
+/// offset is exp(-(x'² + γ²y'²) / 2σ²) · cos(2πx'/λ + φ). The DC component
+/// is removed, so uniform input gives no response, and the weights are
+/// scaled so the positive lobes sum to 1.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GaborReceptiveField {
+    orientation: f32,
+    wavelength: f32,
+    phase: f32,
+    sigma: f32,
+    aspect_ratio: f32,
+}
+
+impl GaborReceptiveField {
+    /// Creates a new Gabor receptive field
+    ///
+    /// # Arguments
+    /// * `orientation` - Preferred orientation (of the bars/edges)
+    /// * `wavelength` - Carrier wavelength (pixels per cycle)
+    /// * `phase` - Carrier phase in degrees (0 = even, ON centre; 90 = odd; 180 = even, OFF centre)
+    /// * `sigma` - Envelope standard deviation across the axis (pixels)
+    /// * `aspect_ratio` - Envelope width across divided by length along the axis
+    pub fn new(orientation: Orientation, wavelength: f32, phase: f32, sigma: f32, aspect_ratio: f32) -> Self {
+        Self {
+            orientation: orientation.degrees(),
+            wavelength: wavelength.max(2.0),
+            phase: phase.rem_euclid(360.0),
+            sigma: sigma.max(0.5),
+            aspect_ratio: aspect_ratio.clamp(0.1, 1.0),
+        }
+    }
+
+    /// Creates the default field for a receptive field radius
+    ///
+    /// The envelope sigma is half the radius and the wavelength twice the
+    /// radius (about two octaves of bandwidth).
+    pub fn for_radius(orientation: Orientation, radius: usize, phase: f32) -> Self {
+        let radius = radius.max(1) as f32;
+        Self::new(orientation, 2.0 * radius, phase, radius / 2.0, DEFAULT_ASPECT_RATIO)
+    }
+
+    /// Returns a copy with a different carrier wavelength (pixels)
+    pub fn with_wavelength(mut self, wavelength: f32) -> Self {
+        self.wavelength = wavelength.max(2.0);
+        self
+    }
+
+    /// Returns a copy with a different carrier phase (degrees)
+    pub fn with_phase(mut self, phase: f32) -> Self {
+        self.phase = phase.rem_euclid(360.0);
+        self
+    }
+
+    /// Returns a copy with a different envelope sigma (pixels)
+    pub fn with_sigma(mut self, sigma: f32) -> Self {
+        self.sigma = sigma.max(0.5);
+        self
+    }
+
+    /// Returns a copy with a different envelope aspect ratio
+    pub fn with_aspect_ratio(mut self, aspect_ratio: f32) -> Self {
+        self.aspect_ratio = aspect_ratio.clamp(0.1, 1.0);
+        self
+    }
+
+    /// Returns the preferred orientation
+    pub fn orientation(&self) -> Orientation {
+        Orientation::new(self.orientation)
+    }
+
+    /// Returns the carrier wavelength (pixels per cycle)
+    pub fn wavelength(&self) -> f32 {
+        self.wavelength
+    }
+
+    /// Returns the preferred spatial frequency (cycles per pixel)
+    pub fn spatial_frequency(&self) -> f32 {
+        1.0 / self.wavelength
+    }
+
+    /// Returns the carrier phase (degrees, 0 to 360)
+    pub fn phase(&self) -> f32 {
+        self.phase
+    }
+
+    /// Returns the envelope standard deviation across the axis (pixels)
+    pub fn sigma(&self) -> f32 {
+        self.sigma
+    }
+
+    /// Returns the envelope aspect ratio
+    pub fn aspect_ratio(&self) -> f32 {
+        self.aspect_ratio
+    }
+
+    /// Returns the unnormalized weight at an offset from the field centre
+    pub fn weight(&self, dx: f32, dy: f32) -> f32 {
+        let (envelope, carrier) = self.envelope_and_carrier(dx, dy);
+        envelope * carrier
+    }
+
+    /// Builds the zero-DC, normalized kernel over a disc
+    ///
+    /// # Returns
+    /// (dx, dy, weight) for every offset within the radius
+    pub fn kernel(&self, radius: usize) -> Vec<(i32, i32, f32)> {
+        let r = radius as i32;
+        let mut taps = Vec::new();
+        for dy in -r..=r {
+            for dx in -r..=r {
+                if ((dx * dx + dy * dy) as f32).sqrt() <= radius as f32 {
+                    let (envelope, carrier) = self.envelope_and_carrier(dx as f32, dy as f32);
+                    taps.push((dx, dy, envelope, carrier));
+                }
+            }
+        }
+
+        // Remove the DC: subtract the envelope-weighted mean of the carrier
+        let envelope_sum: f32 = taps.iter().map(|tap| tap.2).sum();
+        let dc = taps.iter().map(|tap| tap.2 * tap.3).sum::<f32>() / envelope_sum.max(f32::EPSILON);
+        let mut kernel: Vec<(i32, i32, f32)> =
+            taps.iter().map(|&(dx, dy, envelope, carrier)| (dx, dy, envelope * (carrier - dc))).collect();
+
+        let positive: f32 = kernel.iter().map(|tap| tap.2.max(0.0)).sum();
+        if positive > 0.0 {
+            kernel.iter_mut().for_each(|tap| tap.2 /= positive);
+        }
+        kernel
+    }
+
+    /// Response to a sinusoidal grating (for tuning curves)
+    ///
+    /// # Arguments
+    /// * `orientation` - Orientation of the grating's stripes
+    /// * `wavelength` - Grating period (pixels)
+    /// * `phase` - Grating phase at the field centre (degrees)
+    /// * `radius` - Radius of the kernel the grating is applied to
+    ///
+    /// # Returns
+    /// The linear (unrectified) response of the normalized kernel to a
+    /// unit-amplitude grating
+    pub fn grating_response(&self, orientation: Orientation, wavelength: f32, phase: f32, radius: usize) -> f32 {
+        let angle = orientation.radians();
+        let (cos_angle, sin_angle) = (angle.cos(), angle.sin());
+        let frequency = 2.0 * PI / wavelength.max(f32::EPSILON);
+        self.kernel(radius)
+            .iter()
+            .map(|&(dx, dy, weight)| {
+                let across = -(dx as f32) * sin_angle + dy as f32 * cos_angle;
+                weight * (frequency * across + phase.to_radians()).cos()
+            })
+            .sum()
+    }
+
+    fn envelope_and_carrier(&self, dx: f32, dy: f32) -> (f32, f32) {
+        let angle = self.orientation.to_radians();
+        let (cos_angle, sin_angle) = (angle.cos(), angle.sin());
+        let along = dx * cos_angle + dy * sin_angle;
+        let across = -dx * sin_angle + dy * cos_angle;
+        let envelope = (-(across.powi(2) + (self.aspect_ratio * along).powi(2)) / (2.0 * self.sigma.powi(2))).exp();
+        let carrier = (2.0 * PI * across / self.wavelength + self.phase.to_radians()).cos();
+        (envelope, carrier)
+    }
+}
+
 /// Type of V1 neuron
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum V1NeuronType {
     /// Simple cells: Respond to specific position and orientation
     Simple,
     /// Complex cells: Respond to orientation regardless of exact position
+    /// (energy of quadrature pairs of simple-cell subunits)
     Complex,
 }
 
@@ -60,13 +315,26 @@ pub struct V1Neuron {
     y: usize,
     preferred_orientation: Orientation,
     receptive_field_size: usize,
-    
+    receptive_field: GaborReceptiveField,
+    // Normalized Gabor weights over the receptive field: (dx, dy, weight)
+    kernel: Vec<(i32, i32, f32)>,
+    // Weights of the field 90° out of phase (complex cells only)
+    quadrature_kernel: Vec<(i32, i32, f32)>,
+
     // Response
     activation: f32,
+
+    // Divisive fatigue from the neuron's own recent activity
+    fatigue: f32,
+    adaptation_enabled: bool,
+    frame_interval_ms: f32,
 }
 
 impl V1Neuron {
     /// Creates a new V1 neuron
+    ///
+    /// The neuron gets the default even-phase Gabor field for its
+    /// receptive field size (see `GaborReceptiveField::for_radius`).
     pub fn new(
         id: usize,
         neuron_type: V1NeuronType,
@@ -75,6 +343,7 @@ impl V1Neuron {
         preferred_orientation: Orientation,
         receptive_field_size: usize,
     ) -> Self {
+        let receptive_field = GaborReceptiveField::for_radius(preferred_orientation, receptive_field_size, 0.0);
         Self {
             id,
             neuron_type,
@@ -82,10 +351,28 @@ impl V1Neuron {
             y,
             preferred_orientation,
             receptive_field_size,
+            receptive_field,
+            kernel: receptive_field.kernel(receptive_field_size),
+            quadrature_kernel: quadrature_kernel(neuron_type, &receptive_field, receptive_field_size),
             activation: 0.0,
+            fatigue: 0.0,
+            adaptation_enabled: true,
+            frame_interval_ms: DEFAULT_FRAME_INTERVAL_MS,
         }
     }
 
+    /// Returns a copy with a different Gabor receptive field
+    ///
+    /// The field keeps the neuron's receptive field radius; its
+    /// orientation becomes the neuron's preferred orientation.
+    pub fn with_receptive_field(mut self, receptive_field: GaborReceptiveField) -> Self {
+        self.preferred_orientation = receptive_field.orientation();
+        self.kernel = receptive_field.kernel(self.receptive_field_size);
+        self.quadrature_kernel = quadrature_kernel(self.neuron_type, &receptive_field, self.receptive_field_size);
+        self.receptive_field = receptive_field;
+        self
+    }
+
     /// Returns the neuron ID
     pub fn id(&self) -> usize {
         self.id
@@ -101,76 +388,116 @@ impl V1Neuron {
         self.preferred_orientation
     }
 
+    /// Returns the preferred spatial frequency (cycles per pixel)
+    pub fn preferred_spatial_frequency(&self) -> f32 {
+        self.receptive_field.spatial_frequency()
+    }
+
+    /// Returns the preferred phase (degrees: 0 = even, 90 = odd)
+    pub fn preferred_phase(&self) -> f32 {
+        self.receptive_field.phase()
+    }
+
+    /// Returns the Gabor receptive field
+    pub fn receptive_field(&self) -> &GaborReceptiveField {
+        &self.receptive_field
+    }
+
+    /// Returns the position of the receptive field centre
+    pub fn position(&self) -> (usize, usize) {
+        (self.x, self.y)
+    }
+
     /// Returns the current activation
     pub fn activation(&self) -> f32 {
         self.activation
     }
 
-    /// Computes the Gabor-like filter response
+    /// Returns the response gain left by adaptation (1.0 = unadapted)
+    pub fn adaptation_gain(&self) -> f32 {
+        1.0 / (1.0 + self.fatigue)
+    }
+
+    /// Clears the neuron's adaptation state
+    pub fn reset_adaptation(&mut self) {
+        self.fatigue = 0.0;
+    }
+
+    /// Returns whether responses fatigue the neuron
+    pub fn adaptation_enabled(&self) -> bool {
+        self.adaptation_enabled
+    }
+
+    /// Sets whether responses fatigue the neuron (on by default)
     ///
-    /// Simple cells respond to oriented edges at specific positions
-    /// Complex cells pool over positions but maintain orientation selectivity
-    pub fn compute_response(&mut self, edge_map: &[Vec<f32>]) {
-        if edge_map.is_empty() {
-            return;
-        }
+    /// While disabled the neuron responds at full gain and its fatigue
+    /// is left as it is.
+    pub fn set_adaptation_enabled(&mut self, enabled: bool) {
+        self.adaptation_enabled = enabled;
+    }
 
-        let height = edge_map.len();
-        let width = edge_map[0].len();
+    /// Sets the time between successive calls of `compute_response` (ms)
+    pub fn set_frame_interval_ms(&mut self, frame_interval_ms: f32) {
+        self.frame_interval_ms = frame_interval_ms.max(0.0);
+    }
 
-        let mut response = 0.0;
-        let mut count = 0;
-
-        let angle = self.preferred_orientation.radians();
-        let cos_angle = angle.cos();
-        let sin_angle = angle.sin();
-
-        // Sample the receptive field
-        let rf_radius = self.receptive_field_size as i32;
-        
-        for dy in -rf_radius..=rf_radius {
-            for dx in -rf_radius..=rf_radius {
-                let px = self.x as i32 + dx;
-                let py = self.y as i32 + dy;
-
-                if px < 0 || py < 0 || px >= width as i32 || py >= height as i32 {
-                    continue;
-                }
+    /// Computes the Gabor filter response
+    ///
+    /// Simple cells half-wave rectify the Gabor output, so they respond
+    /// to one polarity at one position. Complex cells follow the energy
+    /// model: each subunit combines a quadrature pair (the field and its
+    /// 90° shifted partner) as sqrt(even² + odd²), which does not depend on
+    /// where a bar or edge falls within the field, and the cell averages
+    /// its subunits over a small neighbourhood.
+    ///
+    /// # Arguments
+    /// * `input` - Ganglion or LGN output (may be signed)
+    pub fn compute_response(&mut self, input: &[Vec<f32>]) {
+        if input.is_empty() {
+            return;
+        }
 
-                let distance = ((dx * dx + dy * dy) as f32).sqrt();
-                if distance > self.receptive_field_size as f32 {
-                    continue;
+        self.activation = match self.neuron_type {
+            V1NeuronType::Simple => self.filter(input, &self.kernel, 0, 0).max(0.0),
+            V1NeuronType::Complex => {
+                let offsets = [-COMPLEX_SUBUNIT_SPACING, 0, COMPLEX_SUBUNIT_SPACING];
+                let mut energy = 0.0;
+                for dy in offsets {
+                    for dx in offsets {
+                        let even = self.filter(input, &self.kernel, dx, dy);
+                        let odd = self.filter(input, &self.quadrature_kernel, dx, dy);
+                        energy += even.hypot(odd);
+                    }
                 }
-
-                // Gabor-like orientation filtering
-                // Project position onto preferred orientation axis
-                let projected = (dx as f32 * cos_angle + dy as f32 * sin_angle).abs();
-                let perpendicular = (-dx as f32 * sin_angle + dy as f32 * cos_angle).abs();
-
-                // Elongated receptive field along preferred orientation
-                let orientation_weight = if perpendicular < 2.0 && projected < rf_radius as f32 {
-                    (-perpendicular.powi(2) / 2.0).exp()
-                } else {
-                    0.0
-                };
-
-                let edge_strength = edge_map[py as usize][px as usize];
-                response += edge_strength * orientation_weight;
-                count += 1;
+                energy / (offsets.len() * offsets.len()) as f32
             }
+        };
+
+        // Response adaptation: sustained firing fatigues the neuron, so
+        // only neurons tuned to the adapting pattern lose sensitivity
+        if !self.adaptation_enabled {
+            return;
         }
+        let drive = self.activation;
+        self.activation *= self.adaptation_gain();
+        let rate = relaxation_rate(ADAPTATION_TIME_CONSTANT_MS, self.frame_interval_ms);
+        self.fatigue += (drive * ADAPTATION_STRENGTH - self.fatigue) * rate;
+    }
 
-        self.activation = if count > 0 {
-            // Amplify by 10x for better visualization
-            ((response / count as f32) * 10.0).max(0.0)
-        } else {
-            0.0
-        };
+    /// Applies a kernel centred at an offset from the neuron's position
+    fn filter(&self, input: &[Vec<f32>], kernel: &[(i32, i32, f32)], offset_x: i32, offset_y: i32) -> f32 {
+        let height = input.len() as i32;
+        let width = input.first().map_or(0, Vec::len) as i32;
 
-        // Complex cells have broader tuning (less position-specific)
-        if self.neuron_type == V1NeuronType::Complex {
-            self.activation *= 1.2; // Slight boost for complex cells
+        let mut response = 0.0;
+        for &(dx, dy, weight) in kernel {
+            let px = self.x as i32 + offset_x + dx;
+            let py = self.y as i32 + offset_y + dy;
+            if px >= 0 && py >= 0 && px < width && py < height {
+                response += input[py as usize][px as usize] * weight;
+            }
         }
+        response * GABOR_GAIN
     }
 
     /// Returns whether this neuron is significantly activated
@@ -179,6 +506,23 @@ impl V1Neuron {
     }
 }
 
+/// Builds the quadrature partner of a complex cell's field
+///
+/// The 90° shifted kernel is scaled to the same norm as the field's own
+/// kernel, so the pair responds equally strongly to its preferred grating.
+/// Simple cells need no partner and get an empty kernel.
+fn quadrature_kernel(neuron_type: V1NeuronType, receptive_field: &GaborReceptiveField, radius: usize) -> Vec<(i32, i32, f32)> {
+    if neuron_type == V1NeuronType::Simple {
+        return Vec::new();
+    }
+    let norm = |kernel: &[(i32, i32, f32)]| kernel.iter().map(|tap| tap.2 * tap.2).sum::<f32>().sqrt();
+    let target = norm(&receptive_field.kernel(radius));
+    let mut quadrature = receptive_field.with_phase(receptive_field.phase() + 90.0).kernel(radius);
+    let scale = target / norm(&quadrature).max(f32::EPSILON);
+    quadrature.iter_mut().for_each(|tap| tap.2 *= scale);
+    quadrature
+}
+
 /// V1 cortical column - group of neurons with similar orientation preference
 pub struct V1Column {
     neurons: Vec<V1Neuron>,
@@ -196,24 +540,17 @@ impl V1Column {
     ) -> Self {
         let mut neurons = Vec::new();
 
-        // Each column has both simple and complex cells
-        neurons.push(V1Neuron::new(
-            start_id,
-            V1NeuronType::Simple,
-            x,
-            y,
-            orientation,
-            rf_size,
-        ));
+        // Simple cells come in even (0°, 180°) and odd (90°, 270°) phases,
+        // each with its sign-inverted partner, plus one complex cell
+        for (offset, phase) in [(0, 0.0), (1, 90.0), (2, 180.0), (3, 270.0)] {
+            let neuron = V1Neuron::new(start_id + offset, V1NeuronType::Simple, x, y, orientation, rf_size);
+            let receptive_field = neuron.receptive_field().with_phase(phase);
+            neurons.push(neuron.with_receptive_field(receptive_field));
+        }
 
-        neurons.push(V1Neuron::new(
-            start_id + 1,
-            V1NeuronType::Complex,
-            x,
-            y,
-            orientation,
-            rf_size + 2, // Larger RF for complex cells
-        ));
+        // The complex cell pools quadrature pairs of the same fields around
+        // the column, so its overall field is larger than a simple cell's
+        neurons.push(V1Neuron::new(start_id + 4, V1NeuronType::Complex, x, y, orientation, rf_size));
 
         Self {
             neurons,
@@ -221,6 +558,32 @@ impl V1Column {
         }
     }
 
+    /// Returns a copy whose neurons take their Gabor fields from a template
+    ///
+    /// Every neuron keeps the column's orientation and its own phase, and
+    /// takes the template's wavelength, sigma and aspect ratio.
+    ///
+    /// # Arguments
+    /// * `template` - Field whose wavelength, sigma and aspect ratio apply
+    pub fn with_receptive_field(mut self, template: GaborReceptiveField) -> Self {
+        let field = GaborReceptiveField::new(
+            self.orientation,
+            template.wavelength(),
+            0.0,
+            template.sigma(),
+            template.aspect_ratio(),
+        );
+        self.neurons = self
+            .neurons
+            .into_iter()
+            .map(|neuron| {
+                let phase = neuron.preferred_phase();
+                neuron.with_receptive_field(field.with_phase(phase))
+            })
+            .collect();
+        self
+    }
+
     /// Process input through this column
     pub fn process(&mut self, edge_map: &[Vec<f32>]) {
         for neuron in &mut self.neurons {
@@ -240,33 +603,75 @@ impl V1Column {
     pub fn orientation(&self) -> Orientation {
         self.orientation
     }
+
+    /// Returns the neurons of this column
+    pub fn neurons(&self) -> &[V1Neuron] {
+        &self.neurons
+    }
+
+    /// Clears the adaptation state of every neuron in the column
+    pub fn reset_adaptation(&mut self) {
+        for neuron in &mut self.neurons {
+            neuron.reset_adaptation();
+        }
+    }
+
+    /// Sets whether responses fatigue the neurons of the column
+    pub fn set_adaptation_enabled(&mut self, enabled: bool) {
+        for neuron in &mut self.neurons {
+            neuron.set_adaptation_enabled(enabled);
+        }
+    }
+
+    /// Sets the time between successive frames for every neuron (ms)
+    pub fn set_frame_interval_ms(&mut self, frame_interval_ms: f32) {
+        for neuron in &mut self.neurons {
+            neuron.set_frame_interval_ms(frame_interval_ms);
+        }
+    }
 }
 
 /// V1 cortex layer with multiple orientation columns
 pub struct V1Cortex {
     columns: Vec<V1Column>,
+    orientations: Vec<Orientation>,
     width: usize,
     height: usize,
 }
 
 impl V1Cortex {
-    /// Creates a new V1 cortex
+    /// Creates a new V1 cortex with four orientation channels (every 45°)
     ///
     /// # Arguments
     /// * `width`, `height` - Dimensions of visual field
     /// * `spacing` - Distance between column centers
     /// * `rf_size` - Receptive field size
     pub fn new(width: usize, height: usize, spacing: usize, rf_size: usize) -> Self {
+        Self::with_orientation_channels(width, height, spacing, rf_size, DEFAULT_ORIENTATION_COUNT)
+    }
+
+    /// Creates a new V1 cortex with evenly spaced orientation channels
+    ///
+    /// # Arguments
+    /// * `width`, `height` - Dimensions of visual field
+    /// * `spacing` - Distance between column centers
+    /// * `rf_size` - Receptive field size
+    /// * `orientation_count` - Number of orientation channels (e.g. 8
+    ///   gives one every 22.5°)
+    pub fn with_orientation_channels(
+        width: usize,
+        height: usize,
+        spacing: usize,
+        rf_size: usize,
+        orientation_count: usize,
+    ) -> Self {
         let mut columns = Vec::new();
         let mut id = 0;
 
-        // Standard orientations (every 45 degrees)
-        let orientations = vec![
-            Orientation::horizontal(),
-            Orientation::diagonal_right(),
-            Orientation::vertical(),
-            Orientation::diagonal_left(),
-        ];
+        let orientation_count = orientation_count.max(1);
+        let orientations: Vec<Orientation> = (0..orientation_count)
+            .map(|index| Orientation::new(180.0 * index as f32 / orientation_count as f32))
+            .collect();
 
         // Create columns at regular intervals
         for y in (rf_size..height - rf_size).step_by(spacing) {
@@ -281,11 +686,38 @@ impl V1Cortex {
 
         Self {
             columns,
+            orientations,
             width,
             height,
         }
     }
 
+    /// Returns a copy in which every neuron takes its Gabor field from a template
+    ///
+    /// By default the fields follow the receptive field size (see
+    /// `GaborReceptiveField::for_radius`). The template sets the
+    /// wavelength, and so the preferred spatial frequency, the envelope
+    /// sigma and the aspect ratio of every column; each neuron keeps its
+    /// orientation and phase. Kernels still end at the receptive field
+    /// size, so wavelengths much beyond twice that size are cut off.
+    ///
+    /// # Arguments
+    /// * `template` - Field whose wavelength, sigma and aspect ratio apply
+    pub fn with_receptive_field(mut self, template: GaborReceptiveField) -> Self {
+        self.columns = self.columns.into_iter().map(|column| column.with_receptive_field(template)).collect();
+        self
+    }
+
+    /// Returns the preferred orientations of the channels
+    pub fn orientations(&self) -> &[Orientation] {
+        &self.orientations
+    }
+
+    /// Returns the dimensions of the field the cortex covers
+    pub fn dimensions(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
     /// Process the entire edge map through V1
     pub fn process_edges(&mut self, edge_map: &[Vec<f32>]) {
         for column in &mut self.columns {
@@ -298,29 +730,59 @@ impl V1Cortex {
         &self.columns
     }
 
-    /// Get dominant orientation at each location
-    pub fn orientation_map(&self) -> Vec<Vec<Option<Orientation>>> {
-        let mut map = vec![vec![None; self.width]; self.height];
-
-        // Group columns by position
-        let mut position_map: std::collections::HashMap<(usize, usize), Vec<&V1Column>> =
-            std::collections::HashMap::new();
-
+    /// Returns the summed column activation for each preferred orientation
+    ///
+    /// This is the population tuning curve of the cortex: how strongly the
+    /// current input drives each orientation channel.
+    pub fn orientation_tuning(&self) -> Vec<(Orientation, f32)> {
+        let mut tuning: Vec<(Orientation, f32)> = Vec::new();
         for column in &self.columns {
-            // Get position from first neuron
-            if let Some(neuron) = column.neurons.first() {
-                let pos = (neuron.x, neuron.y);
-                position_map.entry(pos).or_insert_with(Vec::new).push(column);
+            let degrees = column.orientation().degrees();
+            match tuning.iter_mut().find(|(orientation, _)| orientation.degrees() == degrees) {
+                Some((_, total)) => *total += column.max_activation(),
+                None => tuning.push((column.orientation(), column.max_activation())),
             }
         }
+        tuning
+    }
+
+    /// Clears the adaptation state of every neuron
+    pub fn reset_adaptation(&mut self) {
+        for column in &mut self.columns {
+            column.reset_adaptation();
+        }
+    }
+
+    /// Sets whether responses fatigue the neurons (on by default)
+    pub fn set_adaptation_enabled(&mut self, enabled: bool) {
+        for column in &mut self.columns {
+            column.set_adaptation_enabled(enabled);
+        }
+    }
+
+    /// Sets the time between successive frames for every neuron (ms)
+    pub fn set_frame_interval_ms(&mut self, frame_interval_ms: f32) {
+        for column in &mut self.columns {
+            column.set_frame_interval_ms(frame_interval_ms);
+        }
+    }
+
+    /// Returns the orientation decoded from the whole population
+    pub fn orientation_estimate(&self) -> Option<OrientationEstimate> {
+        OrientationEstimate::from_tuning(&self.orientation_tuning())
+    }
+
+    /// Get dominant orientation at each location
+    pub fn orientation_map(&self) -> Vec<Vec<Option<Orientation>>> {
+        let mut map = vec![vec![None; self.width]; self.height];
 
         // Find dominant orientation at each position
-        for ((x, y), columns) in position_map {
+        for ((x, y), columns) in self.columns_by_position() {
             if let Some(dominant) = columns
                 .iter()
                 .max_by(|a, b| a.max_activation().partial_cmp(&b.max_activation()).unwrap())
             {
-                if dominant.max_activation() > 0.1 {
+                if dominant.max_activation() > ACTIVE_THRESHOLD {
                     map[y][x] = Some(dominant.orientation());
                 }
             }
@@ -328,6 +790,40 @@ impl V1Cortex {
 
         map
     }
+
+    /// Decodes a continuous orientation at each location
+    ///
+    /// Unlike `orientation_map`, which picks the most active channel, this
+    /// reads out the population vector of all channels at a location, so
+    /// the estimate falls between channels and comes with a coherence.
+    pub fn orientation_estimates(&self) -> Vec<Vec<Option<OrientationEstimate>>> {
+        let mut map = vec![vec![None; self.width]; self.height];
+
+        for ((x, y), columns) in self.columns_by_position() {
+            if columns.iter().all(|column| column.max_activation() <= ACTIVE_THRESHOLD) {
+                continue;
+            }
+            let tuning: Vec<(Orientation, f32)> =
+                columns.iter().map(|column| (column.orientation(), column.max_activation())).collect();
+            map[y][x] = OrientationEstimate::from_tuning(&tuning);
+        }
+
+        map
+    }
+
+    /// Groups the columns by their position in the visual field
+    fn columns_by_position(&self) -> HashMap<(usize, usize), Vec<&V1Column>> {
+        let mut position_map: HashMap<(usize, usize), Vec<&V1Column>> = HashMap::new();
+
+        for column in &self.columns {
+            // Get position from first neuron
+            if let Some(neuron) = column.neurons.first() {
+                position_map.entry(neuron.position()).or_default().push(column);
+            }
+        }
+
+        position_map
+    }
 }
 
 #[cfg(test)]
@@ -384,4 +880,237 @@ mod tests {
         let cortex = V1Cortex::new(50, 50, 10, 5);
         assert!(!cortex.columns().is_empty());
     }
+
+    #[test]
+    fn test_adaptation_is_orientation_specific() {
+        let mut edge_map = vec![vec![0.0; 20]; 20];
+        edge_map[10] = vec![20.0; 20]; // a line of ganglion firing
+        let mut adapted = V1Neuron::new(0, V1NeuronType::Simple, 10, 10, Orientation::horizontal(), 5);
+        let mut orthogonal = V1Neuron::new(1, V1NeuronType::Simple, 10, 10, Orientation::vertical(), 5);
+
+        adapted.compute_response(&edge_map);
+        let fresh = adapted.activation();
+        for _ in 0..100 {
+            adapted.compute_response(&edge_map);
+            orthogonal.compute_response(&edge_map);
+        }
+
+        // The driven neuron fatigues far more than the orthogonal one
+        assert!(adapted.activation() < fresh * 0.9);
+        assert!(adapted.adaptation_gain() < orthogonal.adaptation_gain());
+
+        adapted.reset_adaptation();
+        adapted.compute_response(&edge_map);
+        assert_eq!(adapted.activation(), fresh);
+    }
+
+    /// Best response over grating phases (a simple cell sees some phase of a drifting grating)
+    fn peak_grating_response(field: &GaborReceptiveField, orientation: f32, wavelength: f32) -> f32 {
+        (0..12)
+            .map(|step| field.grating_response(Orientation::new(orientation), wavelength, step as f32 * 30.0, 8))
+            .fold(0.0, f32::max)
+    }
+
+    #[test]
+    fn test_gabor_orientation_tuning() {
+        let field = GaborReceptiveField::new(Orientation::new(45.0), 8.0, 0.0, 3.0, 0.5);
+        let preferred = peak_grating_response(&field, 45.0, 8.0);
+        assert!(preferred > 0.5);
+        assert!(peak_grating_response(&field, 75.0, 8.0) < 0.7 * preferred);
+        assert!(peak_grating_response(&field, 135.0, 8.0) < 0.1 * preferred);
+    }
+
+    #[test]
+    fn test_gabor_spatial_frequency_tuning() {
+        let fine = GaborReceptiveField::new(Orientation::vertical(), 4.0, 0.0, 3.0, 0.5);
+        let coarse = fine.with_wavelength(12.0);
+        assert_eq!(coarse.spatial_frequency(), 1.0 / 12.0);
+
+        assert!(peak_grating_response(&fine, 90.0, 4.0) > 2.0 * peak_grating_response(&fine, 90.0, 12.0));
+        assert!(peak_grating_response(&coarse, 90.0, 12.0) > 2.0 * peak_grating_response(&coarse, 90.0, 4.0));
+
+        // Uniform input (zero spatial frequency) gives no response
+        let sum: f32 = fine.kernel(8).iter().map(|&(_, _, weight)| weight).sum();
+        assert!(sum.abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_cortex_receptive_field_template() {
+        let cortex = |wavelength: f32| {
+            let template = GaborReceptiveField::new(Orientation::horizontal(), wavelength, 0.0, 4.0, 0.5);
+            V1Cortex::new(48, 48, 8, 8).with_receptive_field(template)
+        };
+
+        // Every neuron takes the template's wavelength but keeps its orientation and phase
+        let coarse = cortex(14.0);
+        for column in coarse.columns() {
+            for (neuron, phase) in column.neurons().iter().zip([0.0, 90.0, 180.0, 270.0, 0.0]) {
+                assert_eq!(neuron.preferred_spatial_frequency(), 1.0 / 14.0);
+                assert_eq!(neuron.preferred_orientation().degrees(), column.orientation().degrees());
+                assert_eq!(neuron.preferred_phase(), phase);
+            }
+        }
+
+        // Summed response of a fresh cortex to a vertical grating
+        let respond = |wavelength: f32, period: f32| -> f32 {
+            let mut cortex = cortex(wavelength);
+            cortex.process_edges(&crate::visual_pathway::test_patterns::sine_grating(48, 48, period, 90.0, 0.0, 1.0));
+            cortex.orientation_tuning().iter().map(|(_, activation)| activation).sum()
+        };
+        assert!(respond(5.0, 5.0) > respond(5.0, 14.0));
+        assert!(respond(14.0, 14.0) > respond(14.0, 5.0));
+    }
+
+    #[test]
+    fn test_even_and_odd_phase() {
+        let even = GaborReceptiveField::for_radius(Orientation::horizontal(), 5, 0.0);
+        let odd = even.with_phase(90.0);
+        assert_eq!(odd.phase(), 90.0);
+
+        // A bright line through the centre drives the even field, not the odd one
+        let mut line = vec![vec![0.0; 21]; 21];
+        line[10] = vec![1.0; 21];
+        let mut even_cell = V1Neuron::new(0, V1NeuronType::Simple, 10, 10, Orientation::horizontal(), 5);
+        let mut odd_cell = V1Neuron::new(1, V1NeuronType::Simple, 10, 10, Orientation::horizontal(), 5)
+            .with_receptive_field(odd);
+        even_cell.compute_response(&line);
+        odd_cell.compute_response(&line);
+        assert!(even_cell.activation() > 1.0);
+        assert!(odd_cell.activation() < 0.05 * even_cell.activation());
+
+        // A luminance step through the centre drives the odd field of one polarity
+        let step: Vec<Vec<f32>> = (0..21).map(|y| vec![if y < 10 { 1.0 } else { 0.0 }; 21]).collect();
+        let mut opposite = V1Neuron::new(2, V1NeuronType::Simple, 10, 10, Orientation::horizontal(), 5)
+            .with_receptive_field(odd.with_phase(270.0));
+        odd_cell.compute_response(&step);
+        opposite.compute_response(&step);
+        assert!(odd_cell.activation().max(opposite.activation()) > 0.5);
+        assert_eq!(odd_cell.activation().min(opposite.activation()), 0.0);
+    }
+
+    #[test]
+    fn test_column_covers_all_phases() {
+        let column = V1Column::new(0, 10, 10, Orientation::vertical(), 5);
+        let phases: Vec<f32> = column
+            .neurons()
+            .iter()
+            .filter(|neuron| neuron.neuron_type() == V1NeuronType::Simple)
+            .map(V1Neuron::preferred_phase)
+            .collect();
+        assert_eq!(phases, vec![0.0, 90.0, 180.0, 270.0]);
+        assert!(column.neurons().iter().all(|neuron| neuron.preferred_spatial_frequency() > 0.0));
+    }
+
+    #[test]
+    fn test_configurable_orientation_channels() {
+        let default: Vec<f32> = V1Cortex::new(40, 40, 10, 5).orientations().iter().map(Orientation::degrees).collect();
+        assert_eq!(default, vec![0.0, 45.0, 90.0, 135.0]);
+
+        let cortex = V1Cortex::with_orientation_channels(40, 40, 10, 5, 8);
+        assert_eq!(cortex.orientations().len(), 8);
+        assert_eq!(cortex.orientations()[1].degrees(), 22.5);
+        assert_eq!(cortex.columns().len(), 2 * V1Cortex::new(40, 40, 10, 5).columns().len());
+        assert_eq!(cortex.orientation_tuning().len(), 8);
+    }
+
+    #[test]
+    fn test_population_vector() {
+        let channel = |degrees: f32, activation: f32| (Orientation::new(degrees), activation);
+
+        // Between two equally active channels, with partial coherence
+        let estimate = OrientationEstimate::from_tuning(&[channel(0.0, 1.0), channel(45.0, 1.0)]).unwrap();
+        assert!((estimate.orientation.degrees() - 22.5).abs() < 1e-3);
+        assert!((estimate.coherence - (PI / 4.0).cos()).abs() < 1e-3);
+        assert_eq!(estimate.strength, 2.0);
+
+        // 170° and 10° average to horizontal, not 90°
+        let estimate = OrientationEstimate::from_tuning(&[channel(170.0, 1.0), channel(10.0, 1.0)]).unwrap();
+        assert!(estimate.orientation.degrees().min(180.0 - estimate.orientation.degrees()) < 1e-3);
+
+        // Untuned or silent populations have no orientation
+        let flat: Vec<_> = [0.0, 45.0, 90.0, 135.0].iter().map(|&degrees| channel(degrees, 1.0)).collect();
+        assert!(OrientationEstimate::from_tuning(&flat).is_none());
+        assert!(OrientationEstimate::from_tuning(&[channel(0.0, 0.0)]).is_none());
+    }
+
+    #[test]
+    fn test_continuous_orientation_between_channels() {
+        let grating = crate::visual_pathway::test_patterns::sine_grating(64, 64, 10.0, 30.0, 0.0, 1.0);
+        let mut cortex = V1Cortex::with_orientation_channels(64, 64, 8, 5, 8);
+        cortex.process_edges(&grating);
+
+        let estimates: Vec<OrientationEstimate> = cortex.orientation_estimates().into_iter().flatten().flatten().collect();
+        assert!(!estimates.is_empty());
+        let mean_error = estimates.iter().map(|estimate| (estimate.orientation.degrees() - 30.0).abs()).sum::<f32>()
+            / estimates.len() as f32;
+        assert!(mean_error < 5.0, "mean error {}", mean_error);
+        assert!(estimates.iter().all(|estimate| estimate.coherence > 0.3));
+
+        // The argmax map can only report a channel's orientation
+        let winners: Vec<f32> = cortex.orientation_map().into_iter().flatten().flatten().map(|o| o.degrees()).collect();
+        assert!(winners.iter().all(|&degrees| degrees == 22.5 || degrees == 45.0));
+
+        let global = cortex.orientation_estimate().unwrap();
+        assert!((global.orientation.degrees() - 30.0).abs() < 5.0);
+    }
+
+    /// Activation of a fresh neuron to each frame of a drifting grating
+    fn drifting_grating_responses(neuron_type: V1NeuronType, degrees: f32) -> Vec<f32> {
+        let mut neuron = V1Neuron::new(0, neuron_type, 16, 16, Orientation::horizontal(), 5);
+        (0..10)
+            .map(|phase| {
+                let grating = crate::visual_pathway::test_patterns::sine_grating(32, 32, 10.0, degrees, phase as f32, 1.0);
+                neuron.reset_adaptation();
+                neuron.compute_response(&grating);
+                neuron.activation()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_complex_cells_are_phase_invariant() {
+        let simple = drifting_grating_responses(V1NeuronType::Simple, 0.0);
+        let complex = drifting_grating_responses(V1NeuronType::Complex, 0.0);
+        let range = |responses: &[f32]| {
+            let max = responses.iter().copied().fold(f32::MIN, f32::max);
+            let min = responses.iter().copied().fold(f32::MAX, f32::min);
+            (min, max)
+        };
+
+        // The simple cell follows the grating's phase, silent half the cycle
+        let (simple_min, simple_max) = range(&simple);
+        assert!(simple_max > 0.5);
+        assert_eq!(simple_min, 0.0);
+
+        // The complex cell responds almost equally at every phase
+        let (complex_min, complex_max) = range(&complex);
+        assert!(complex_min > 0.5 * simple_max);
+        assert!(complex_max - complex_min < 0.1 * complex_max, "complex cell modulates {:?}", complex);
+
+        // ... but stays orientation selective
+        let orthogonal = drifting_grating_responses(V1NeuronType::Complex, 90.0);
+        assert!(range(&orthogonal).1 < 0.2 * complex_min);
+    }
+
+    #[test]
+    fn test_complex_cells_ignore_contrast_polarity() {
+        let line = |value: f32| {
+            let mut map = vec![vec![0.0; 21]; 21];
+            map[11] = vec![value; 21];
+            map
+        };
+        let response = |neuron_type: V1NeuronType, map: &[Vec<f32>]| {
+            let mut neuron = V1Neuron::new(0, neuron_type, 10, 10, Orientation::horizontal(), 5);
+            neuron.compute_response(map);
+            neuron.activation()
+        };
+
+        // Bright and dark lines beside the centre: the even ON simple cell
+        // prefers one, the complex cell responds equally to both
+        let (bright, dark) = (line(1.0), line(-1.0));
+        assert!(response(V1NeuronType::Simple, &bright) > 2.0 * response(V1NeuronType::Simple, &dark));
+        let complex_bright = response(V1NeuronType::Complex, &bright);
+        assert!(complex_bright > 0.0);
+        assert!((complex_bright - response(V1NeuronType::Complex, &dark)).abs() < 1e-4);
+    }
 }
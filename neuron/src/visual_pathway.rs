@@ -1,22 +1,88 @@
 //! Complete visual processing pathway from photoreceptors to cortex
 
+use crate::amacrine::{AmacrineLayer, MotionDirection};
+use crate::bipolar::{BipolarLayer, BipolarType};
+use crate::color_vision::{excitation_weights, ConePopulation};
 use crate::cone::Cone;
-use crate::ganglion::GanglionLayer;
-use crate::photopigment::{ConeType, LightStimulus};
-use crate::v1_cortex::{Orientation, V1Cortex};
+use crate::eye_movements::{retinal_frame, Eye, GazeSample};
+use crate::foveation::{CorticalGrid, Foveation, SamplingBlock};
+use crate::ganglion::{GanglionLayer, GanglionType};
+use crate::horizontal_cell::{HorizontalCellLayer, HorizontalCellType};
+use crate::lgn::{LateralGeniculateNucleus, LgnLayerType, LgnResponse};
+use crate::image_utils::{linear_rgb_to_rod, srgb_to_linear};
+use crate::mosaic::{ConeMosaic, MosaicParameters};
+use crate::melanopsin::MelanopsinLayer;
+use crate::optics::EyeOptics;
+use crate::photopigment::{
+    melanopsin_sensitivity, rhodopsin_sensitivity, ConeType, LightSource, LightStimulus,
+    PreReceptoralFilter, SpectralSensitivity, MELANOPSIN_PEAK, RHODOPSIN_PEAK,
+};
+use crate::pupil::Pupil;
+use crate::rod::Rod;
+use crate::spike_encoding::{SpikeEncoder, SpikeTrain};
+use crate::temporal::{TemporalFilter, TemporalProfile, DEFAULT_FRAME_INTERVAL_MS};
+use crate::v1_cortex::{Orientation, OrientationEstimate, V1Cortex};
 use crate::v2_cortex::{V2Cortex, V2Response};
 use crate::v4_cortex::V4Cortex;
 
+/// Intensity used for a white pixel in grayscale images (photopic daylight)
+const PHOTOPIC_INTENSITY: f32 = 100.0;
+
+/// Mean L+M cone excitation below which vision is purely scotopic
+const SCOTOPIC_LIMIT: f32 = 0.1;
+
+/// Mean L+M cone excitation above which rod signals are shunted (photopic)
+const PHOTOPIC_LIMIT: f32 = 10.0;
+
+/// Fraction of the horizontal cell signal fed back onto cones
+const HORIZONTAL_FEEDBACK_GAIN: f32 = 0.5;
+
+/// Extra discount on the diagonal bucket: diagonal channels also respond
+/// to horizontal and vertical edges met at an angle
+const DIAGONAL_DISCOUNT: f32 = 1.125;
+
 /// Complete visual system simulation
 pub struct VisualPathway {
     // Retinal layers
     cones: Vec<Cone>,
+    rods: Vec<Rod>,
+    cone_population: ConePopulation,
+    mosaic: ConeMosaic,
+    // Patch of the visual field each cone (and its rod) reports for
+    cone_blocks: Vec<SamplingBlock>,
+    // Point of the image each cone samples (its position in the mosaic)
+    cone_positions: Vec<(f32, f32)>,
+    cone_type_map: Vec<Vec<ConeType>>,
+    foveation: Option<Foveation>,
+    // Log-polar map the cortical areas work on (foveated pathways only)
+    cortical_grid: Option<CorticalGrid>,
+    // Blur and scatter in front of the retina (None = perfect focus)
+    optics: Option<EyeOptics>,
+    // ipRGCs and the pupil they control (None = fixed pupil)
+    pupil_reflex: Option<PupillaryReflex>,
+    horizontal_layer: HorizontalCellLayer,
+    bipolar_layer: BipolarLayer,
+    amacrine_layer: AmacrineLayer,
     ganglion_layer: GanglionLayer,
+    parasol_layer: GanglionLayer,
+    direction_layer: GanglionLayer,
+    opponent_layer: GanglionLayer,
+
+    // Thalamic relay
+    lgn: LateralGeniculateNucleus,
+    v1_stream: LgnLayerType,
     
     // Cortical processing
     v1_cortex: V1Cortex,
+    v1_adaptation: bool, // Whether V1 adaptation carries over between static images
     v2_cortex: V2Cortex,
     v4_cortex: V4Cortex,
+
+    // Temporal integration of frame sequences
+    ganglion_filter: TemporalFilter,
+    v1_filter: TemporalFilter,
+    v2_filter: TemporalFilter,
+    elapsed_ms: f32,
     
     // Image dimensions
     width: usize,
@@ -28,43 +94,192 @@ impl VisualPathway {
     ///
     /// # Arguments
     /// * `width`, `height` - Dimensions of visual field
+    ///
+    /// Cones of a normal trichromat are laid out by `ConeMosaic::generate`
+    /// with default `MosaicParameters` (hexagonal packing, clumped L/M
+    /// cones, an S-cone lattice).
     pub fn new(width: usize, height: usize) -> Self {
-        // Create cone mosaic (simplified - one cone per pixel)
-        let mut cones = Vec::new();
-        let mut cone_id = 0;
+        Self::with_cone_population(width, height, ConePopulation::normal())
+    }
 
-        for y in 0..height {
-            for x in 0..width {
-                // Distribute cone types according to retinal abundance
-                let cone_type = match (x + y) % 10 {
-                    0 => ConeType::S,      // ~10% S-cones
-                    1..=4 => ConeType::M,  // ~40% M-cones
-                    _ => ConeType::L,      // ~50% L-cones
-                };
-                
-                cones.push(Cone::new(cone_id, cone_type));
-                cone_id += 1;
+    /// Creates a new visual pathway with a custom cone population
+    ///
+    /// Use this to model colour vision deficiencies: remove a cone class,
+    /// shift an opsin's peak or change the L:M:S ratio. Cones are laid out
+    /// by `ConeMosaic::generate` with default `MosaicParameters`.
+    ///
+    /// # Arguments
+    /// * `width`, `height` - Dimensions of visual field
+    /// * `cone_population` - Cone classes, ratios and opsin peaks of the mosaic
+    pub fn with_cone_population(width: usize, height: usize, cone_population: ConePopulation) -> Self {
+        let mosaic = ConeMosaic::generate(width, height, &cone_population, &MosaicParameters::default());
+        Self::with_mosaic(mosaic, cone_population)
+    }
+
+    /// Creates a new visual pathway from a pre-generated cone mosaic
+    ///
+    /// The visual field has the mosaic's dimensions, with one cone per pixel.
+    /// Each cone reports for its pixel but samples the image at its own
+    /// position in the mosaic: in a hexagonal mosaic every other row is
+    /// shifted by half a pixel. The reported `cone_population` has the
+    /// ratio of the cones actually placed.
+    ///
+    /// # Arguments
+    /// * `mosaic` - Arrangement of cone classes
+    /// * `cone_population` - Opsin peaks used for each cone class
+    pub fn with_mosaic(mosaic: ConeMosaic, cone_population: ConePopulation) -> Self {
+        Self::assemble(mosaic, cone_population, None)
+    }
+
+    /// Creates a new foveated visual pathway
+    ///
+    /// Cone density, ganglion cell spacing and receptive field size fall off
+    /// with eccentricity from the fixation point: one cone per pixel at
+    /// fixation, one cone per block of pixels in the periphery. V1, V2 and
+    /// V4 work on the log-polar cortical map of the field (see
+    /// `Foveation::cortical_grid`), which has about one pixel per sampling
+    /// block, so the cost of large images stays low after the retina too.
+    /// Cones are laid out as in `new`.
+    ///
+    /// # Arguments
+    /// * `width`, `height` - Dimensions of visual field
+    /// * `foveation` - Fixation point and eccentricity scaling
+    pub fn foveated(width: usize, height: usize, foveation: Foveation) -> Self {
+        let population = ConePopulation::normal();
+        let mosaic = ConeMosaic::generate(width, height, &population, &MosaicParameters::default());
+        Self::foveated_with_mosaic(mosaic, population, foveation)
+    }
+
+    /// Creates a new foveated visual pathway from a pre-generated cone mosaic
+    ///
+    /// Each sampling block holds the cone the mosaic has at its centre.
+    ///
+    /// # Arguments
+    /// * `mosaic` - Arrangement of cone classes over the visual field
+    /// * `cone_population` - Opsin peaks used for each cone class
+    /// * `foveation` - Fixation point and eccentricity scaling
+    pub fn foveated_with_mosaic(mosaic: ConeMosaic, cone_population: ConePopulation, foveation: Foveation) -> Self {
+        Self::assemble(mosaic, cone_population, Some(foveation))
+    }
+
+    fn assemble(mosaic: ConeMosaic, cone_population: ConePopulation, foveation: Option<Foveation>) -> Self {
+        let (width, height) = mosaic.dimensions();
+        // Create cone mosaic (simplified - one cone per pixel, or per
+        // sampling block in a foveated retina)
+        // Rods interleave with the cones, so every cone also gets a rod
+        let cone_blocks: Vec<SamplingBlock> = match &foveation {
+            Some(foveation) => foveation.sampling_blocks(width, height, 1.0),
+            None => (0..height)
+                .flat_map(|y| (0..width).map(move |x| SamplingBlock { x, y, width: 1, height: 1 }))
+                .collect(),
+        };
+        let mut cones = Vec::with_capacity(cone_blocks.len());
+        let mut cone_positions = Vec::with_capacity(cone_blocks.len());
+        let mut cone_type_map = vec![vec![ConeType::L; width]; height];
+        let mut rods = Vec::with_capacity(cone_blocks.len());
+        let filter = PreReceptoralFilter::standard();
+        let fundamental = |cone_type: ConeType| {
+            SpectralSensitivity::new(cone_population.peak(cone_type), cone_type.optical_density(), filter)
+        };
+        let (s_fundamental, m_fundamental, l_fundamental) = (
+            fundamental(ConeType::S),
+            fundamental(ConeType::M),
+            fundamental(ConeType::L),
+        );
+
+        for (cone_id, block) in cone_blocks.iter().enumerate() {
+            let (x, y) = block.center();
+            let cone_type = mosaic.cone_type_at(x, y);
+            let fundamental = match cone_type {
+                ConeType::S => s_fundamental,
+                ConeType::M => m_fundamental,
+                ConeType::L => l_fundamental,
+            };
+
+            cones.push(Cone::with_sensitivity(cone_id, cone_type, fundamental));
+            cone_positions.push((mosaic.position(x, y).0, y as f32));
+            rods.push(Rod::new(cone_id));
+            for row in cone_type_map.iter_mut().skip(block.y).take(block.height) {
+                for pixel_type in row.iter_mut().skip(block.x).take(block.width) {
+                    *pixel_type = cone_type;
+                }
             }
         }
 
-        // Create ganglion layer (center-surround edge detection)
-        let ganglion_layer = GanglionLayer::new(width, height, 4, 1.5, 4.0);
+        // Report the ratio of the cones actually placed, with the configured opsins
+        let placed = |cone_type: ConeType| cones.iter().filter(|cone| cone.cone_type() == cone_type).count() as f32;
+        let cone_population = cone_population.with_ratio(placed(ConeType::L), placed(ConeType::M), placed(ConeType::S));
+
+        // Create horizontal cell layer (coupled network, lateral feedback to cones)
+        let horizontal_layer = HorizontalCellLayer::new(width, height, 2, 2, 4.0, HORIZONTAL_FEEDBACK_GAIN);
+
+        // Create bipolar layer (ON/OFF, sustained/transient cells under every cone)
+        let bipolar_layer = BipolarLayer::new(cones.len(), &BipolarType::ALL);
+        bipolar_layer.connect_cones(&mut cones);
+
+        // Create amacrine layer (starburst cells for direction selectivity)
+        let amacrine_layer = AmacrineLayer::new(width, height, 1, 2);
+
+        // Create midget and parasol ganglion layers (center-surround edge detection)
+        let (ganglion_layer, parasol_layer) = match &foveation {
+            Some(foveation) => (
+                GanglionLayer::foveated_midget(width, height, foveation),
+                GanglionLayer::foveated_parasol(width, height, foveation),
+            ),
+            None => (GanglionLayer::midget(width, height), GanglionLayer::parasol(width, height)),
+        };
+
+        // Create ON-OFF direction-selective ganglion cells (retinal motion)
+        let direction_layer = GanglionLayer::direction_selective(width, height, 4, 1.5);
+
+        // Create colour-opponent ganglion cells (red-green midget, blue-yellow bistratified)
+        let opponent_layer = GanglionLayer::color_opponent(width, height, 4, 1.5, 4.0);
+
+        // Create LGN (parvo-, magno- and koniocellular relay layers)
+        let lgn = LateralGeniculateNucleus::new(width, height);
+
+        // Cortical areas cover the visual field, or its log-polar map in a foveated pathway
+        let cortical_grid = foveation.map(|foveation| foveation.cortical_grid(width, height, 1.0));
+        let (cortex_width, cortex_height) = cortical_grid.map_or((width, height), |grid| grid.dimensions());
 
         // Create V1 cortex (orientation detection)
-        let v1_cortex = V1Cortex::new(width, height, 8, 5);
+        let v1_cortex = V1Cortex::new(cortex_width, cortex_height, 8, 5);
         
         // Create V2 cortex (corners and contours) - smaller spacing and larger RF
-        let v2_cortex = V2Cortex::new(width, height, 4); // spacing reduced from 8 to 4
+        let v2_cortex = V2Cortex::new(cortex_width, cortex_height, 4); // spacing reduced from 8 to 4
 
         // Create V4 cortex (shape detection)
-        let v4_cortex = V4Cortex::new(width, height, 8);
+        let v4_cortex = V4Cortex::new(cortex_width, cortex_height, 8);
 
         Self {
             cones,
+            rods,
+            cone_population,
+            mosaic,
+            cone_blocks,
+            cone_positions,
+            cone_type_map,
+            foveation,
+            cortical_grid,
+            optics: None,
+            pupil_reflex: None,
+            horizontal_layer,
+            bipolar_layer,
+            amacrine_layer,
             ganglion_layer,
+            parasol_layer,
+            direction_layer,
+            opponent_layer,
+            lgn,
+            v1_stream: LgnLayerType::Parvocellular,
             v1_cortex,
+            v1_adaptation: false,
             v2_cortex,
             v4_cortex,
+            ganglion_filter: TemporalFilter::new(TemporalProfile::ganglion(), DEFAULT_FRAME_INTERVAL_MS),
+            v1_filter: TemporalFilter::new(TemporalProfile::v1(), DEFAULT_FRAME_INTERVAL_MS),
+            v2_filter: TemporalFilter::new(TemporalProfile::v2(), DEFAULT_FRAME_INTERVAL_MS),
+            elapsed_ms: 0.0,
             width,
             height,
         }
@@ -73,138 +288,1079 @@ impl VisualPathway {
     /// Process a light stimulus pattern through the entire visual pathway
     ///
     /// # Arguments
-    /// * `light_pattern` - 2D array of light stimuli (wavelength and intensity)
+    /// * `light_pattern` - 2D array of light stimuli, either single wavelengths
+    ///   (`LightStimulus`) or full spectra (`SpectralPowerDistribution`)
     ///
     /// # Returns
     /// Processed visual information at each stage
-    pub fn process_scene(&mut self, light_pattern: &[Vec<LightStimulus>]) -> VisualResponse {
-        // Stage 1: Phototransduction (cones convert light to neural signals)
-        let cone_responses = self.process_phototransduction(light_pattern);
+    pub fn process_scene<L: LightSource>(&mut self, light_pattern: &[Vec<L>]) -> VisualResponse {
+        self.process_retinal_input(light_pattern, false)
+    }
+
+    /// Process a colour image through the entire visual pathway
+    ///
+    /// Each pixel is converted from sRGB to linear RGB and then to cone
+    /// excitations, so every cone in the mosaic is driven by the excitation
+    /// of its own class (including shifted opsins of anomalous trichromats).
+    ///
+    /// # Arguments
+    /// * `image` - 2D array of sRGB-encoded [R, G, B] values (0.0 to 1.0)
+    pub fn process_color_image(&mut self, image: &[Vec<[f32; 3]>]) -> VisualResponse {
+        let melanopsin_weights = excitation_weights(MELANOPSIN_PEAK);
+        let excitations: Vec<Vec<PhotoreceptorExcitation>> = image
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|pixel| {
+                        let linear = pixel.map(srgb_to_linear);
+                        let melanopsin = (0..3).map(|i| melanopsin_weights[i] * linear[i]).sum::<f32>().max(0.0);
+                        PhotoreceptorExcitation {
+                            linear_rgb: linear.map(|e| e * PHOTOPIC_INTENSITY),
+                            rod: linear_rgb_to_rod(linear) * PHOTOPIC_INTENSITY,
+                            melanopsin: melanopsin * PHOTOPIC_INTENSITY,
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        self.process_retinal_input(&excitations, false)
+    }
+
+    /// Process one frame of a grayscale movie under daylight illumination
+    ///
+    /// Unlike `process_grayscale_image`, ganglion, V1 and V2 responses
+    /// pass through biphasic temporal filters that advance by one frame
+    /// interval per call: they overshoot at stimulus onset, rebound at
+    /// offset and settle to the static response for an unchanging scene.
+    /// Photoreceptor, bipolar and amacrine state advances one step per
+    /// frame as it does for every processed image, and V1 adaptation
+    /// always builds up over successive frames. This is also the only
+    /// method that advances the pupillary light reflex, if one is set up
+    /// (see `with_pupil_reflex`).
+    ///
+    /// # Arguments
+    /// * `frame` - 2D array of reflectances (0.0 = black, 1.0 = white)
+    pub fn process_frame(&mut self, frame: &[Vec<f32>]) -> VisualResponse {
+        let light_pattern = grayscale_light_pattern(frame, PHOTOPIC_INTENSITY);
+        let response = self.process_retinal_input(&light_pattern, true);
+        self.elapsed_ms += self.frame_interval_ms();
+        response
+    }
+
+    /// Process a sequence of grayscale frames
+    ///
+    /// # Arguments
+    /// * `frames` - Frames in presentation order, one frame interval apart
+    ///
+    /// # Returns
+    /// The response to each frame
+    pub fn process_sequence(&mut self, frames: &[Vec<Vec<f32>>]) -> Vec<VisualResponse> {
+        frames.iter().map(|frame| self.process_frame(frame)).collect()
+    }
+
+    /// View a static grayscale scene through a moving eye
+    ///
+    /// Each frame, the eye moves for one frame interval and the part of
+    /// the scene around its gaze is imaged on the retina, so fixational
+    /// drift, microsaccades and saccades keep refreshing the input that
+    /// adapting and transient cells would otherwise fade.
+    ///
+    /// # Arguments
+    /// * `scene` - Grayscale scene (reflectances), any size
+    /// * `eye` - Eye to move; its gaze is in scene pixels
+    /// * `frames` - Number of frames to process
+    ///
+    /// # Returns
+    /// The gaze and the response for each frame
+    pub fn process_through_eye(
+        &mut self,
+        scene: &[Vec<f32>],
+        eye: &mut Eye,
+        frames: usize,
+    ) -> Vec<(GazeSample, VisualResponse)> {
+        (0..frames)
+            .map(|_| {
+                let gaze = eye.advance(self.frame_interval_ms());
+                let frame = retinal_frame(scene, &gaze, self.width, self.height);
+                (gaze, self.process_frame(&frame))
+            })
+            .collect()
+    }
 
-        // Stage 2: Ganglion cells detect edges and contrasts
-        self.ganglion_layer.process_image(&cone_responses);
-        let edge_map = self.ganglion_layer.create_edge_map();
+    /// Returns a copy that presents frames at a different interval (ms)
+    pub fn with_frame_interval(mut self, frame_interval_ms: f32) -> Self {
+        self.set_frame_interval(frame_interval_ms);
+        self
+    }
 
-        // Stage 3: V1 cortex extracts oriented features
-        self.v1_cortex.process_edges(&edge_map);
+    /// Changes the time between frames (ms)
+    ///
+    /// Every stage with dynamics (photoreceptor pigment and adaptation,
+    /// bipolar and amacrine cells, ganglion contrast gain, LGN T-channels,
+    /// V1 adaptation and the temporal filters) advances by this much per
+    /// frame, so their time courses do not depend on the frame rate.
+    pub fn set_frame_interval(&mut self, frame_interval_ms: f32) {
+        for cone in &mut self.cones {
+            cone.set_frame_interval_ms(frame_interval_ms);
+        }
+        for rod in &mut self.rods {
+            rod.set_frame_interval_ms(frame_interval_ms);
+        }
+        self.bipolar_layer.set_frame_interval_ms(frame_interval_ms);
+        self.amacrine_layer.set_frame_interval_ms(frame_interval_ms);
+        for layer in [
+            &mut self.ganglion_layer,
+            &mut self.parasol_layer,
+            &mut self.direction_layer,
+            &mut self.opponent_layer,
+        ] {
+            layer.set_frame_interval_ms(frame_interval_ms);
+        }
+        self.lgn.set_frame_interval_ms(frame_interval_ms);
+        self.v1_cortex.set_frame_interval_ms(frame_interval_ms);
+        for filter in [&mut self.ganglion_filter, &mut self.v1_filter, &mut self.v2_filter] {
+            filter.set_frame_interval_ms(frame_interval_ms);
+        }
+    }
+
+    /// Returns the time between frames (ms)
+    pub fn frame_interval_ms(&self) -> f32 {
+        self.ganglion_filter.frame_interval_ms()
+    }
+
+    /// Returns the time presented so far by `process_frame` (ms)
+    pub fn elapsed_ms(&self) -> f32 {
+        self.elapsed_ms
+    }
+
+    /// Returns the temporal filters to rest and the clock to zero
+    pub fn reset_temporal_filters(&mut self) {
+        self.ganglion_filter.reset();
+        self.v1_filter.reset();
+        self.v2_filter.reset();
+        self.elapsed_ms = 0.0;
+    }
+
+    /// Runs every stage of the pathway on photoreceptor input
+    ///
+    /// With `temporal` set, ganglion, V1 and V2 inputs are passed through
+    /// their temporal filters (movie frames); otherwise they are used as is.
+    fn process_retinal_input<I: RetinalInput>(&mut self, input: &[Vec<I>], temporal: bool) -> VisualResponse {
+        // Stage 1: Phototransduction (cones and rods convert light to neural signals)
+        let (cone_signals, rod_responses, rod_contribution) =
+            self.process_phototransduction(input);
+
+        // ipRGCs adjust the pupil for the next frame (movies only: the reflex needs time)
+        if temporal {
+            self.process_pupil_reflex(input);
+        }
+
+        // Stage 2: Horizontal cells form the surround by feedback onto cones
+        let cone_responses = self.process_horizontal_cells(&cone_signals, &rod_responses, rod_contribution);
+        let horizontal_activations = self.horizontal_layer.activation_map(HorizontalCellType::H1);
+
+        // Stage 3: Bipolar cells split the cone signal into ON and OFF channels
+        self.process_bipolar_cells(rod_contribution);
+        let on_bipolar_activations = self.bipolar_activations(BipolarType::OnSustained);
+        let off_bipolar_activations = self.bipolar_activations(BipolarType::OffSustained);
+        let on_transient = self.bipolar_activations(BipolarType::OnTransient);
+        let off_transient = self.bipolar_activations(BipolarType::OffTransient);
+
+        // Stage 4: Ganglion cells detect edges and contrasts
+        // Midget cells read sustained bipolars, parasol cells transient ones
+        self.ganglion_layer.process_bipolar(&on_bipolar_activations, &off_bipolar_activations);
+        let mut edge_map = self.ganglion_layer.create_edge_map();
+        if temporal {
+            edge_map = rectify(self.ganglion_filter.filter(&edge_map));
+        }
+        self.parasol_layer.process_bipolar(&on_transient, &off_transient);
+        let parasol_map = self.parasol_layer.create_resampled_edge_map(self.ganglion_layer.spacing());
+
+        // Direction-selective cells compare this frame with the previous one
+        let retinal_motion = self.process_retinal_motion(&on_transient, &off_transient);
+
+        // Colour-opponent cells compare cone classes
+        self.opponent_layer.process_cones(&cone_responses, &self.cone_type_map);
+        let red_green_map = self.opponent_layer.red_green_map();
+        let blue_yellow_map = self.opponent_layer.blue_yellow_map();
+
+        // Stage 5: LGN relays the midget, parasol and blue-yellow streams
+        let lgn = self.lgn.process(&edge_map, &parasol_map, &blue_yellow_map);
+        let projected_input;
+        let cortical_input = match &self.cortical_grid {
+            Some(grid) => {
+                projected_input = grid.resample(lgn.layer(self.v1_stream));
+                &projected_input
+            }
+            None => lgn.layer(self.v1_stream),
+        };
+
+        // V1 reads the ON and OFF relay cells of its stream together: their
+        // difference is a dense, signed centre-surround map, so Gabor fields
+        // can tell light bars from dark ones
+        let (on_map, off_map) = match self.v1_stream {
+            LgnLayerType::Parvocellular => (
+                self.ganglion_layer.create_rate_map(GanglionType::OnCenter),
+                self.ganglion_layer.create_rate_map(GanglionType::OffCenter),
+            ),
+            LgnLayerType::Magnocellular => (
+                self.parasol_layer.create_rate_map(GanglionType::OnCenter),
+                self.parasol_layer.create_rate_map(GanglionType::OffCenter),
+            ),
+            LgnLayerType::Koniocellular => (
+                blue_yellow_map.iter().map(|row| row.iter().map(|v| v.max(0.0)).collect()).collect(),
+                blue_yellow_map.iter().map(|row| row.iter().map(|v| (-v).max(0.0)).collect()).collect(),
+            ),
+        };
+        let relayed_signed = self.lgn.relay_on_off(self.v1_stream, &on_map, &off_map);
+        let signed_input = match &self.cortical_grid {
+            Some(grid) => grid.resample(&relayed_signed),
+            None => relayed_signed,
+        };
+
+        // Stage 6: V1 cortex extracts oriented features from the selected stream
+        let v1_input = if temporal {
+            self.v1_filter.filter(&signed_input)
+        } else {
+            signed_input
+        };
+        self.v1_cortex.set_adaptation_enabled(temporal || self.v1_adaptation);
+        self.v1_cortex.process_edges(&v1_input);
         let orientation_map = self.v1_cortex.orientation_map();
+        let orientation_estimates = self.v1_cortex.orientation_estimates();
         
-        // Stage 4: V2 cortex detects corners and contours
-        let v2_features = self.v2_cortex.process(&orientation_map, &edge_map);
+        // Stage 7: V2 cortex detects corners and contours
+        let v2_filtered;
+        let v2_input = if temporal {
+            v2_filtered = rectify(self.v2_filter.filter(cortical_input));
+            &v2_filtered
+        } else {
+            cortical_input
+        };
+        let v2_features = self.v2_cortex.process(&orientation_map, v2_input);
 
-        // Stage 5: V4 cortex detects shapes
+        // Stage 8: V4 cortex detects shapes
         let v4_features = self.v4_cortex.process(&v2_features);
 
-        // Stage 6: Compute feature statistics
+        // Stage 9: Compute feature statistics
         let features = self.extract_features();
 
+        // Retinotopic map onto V1 (foveated mode only)
+        let cortical_map = self.foveation.zip(self.cortical_grid).map(|(foveation, grid)| {
+            let (cortex_width, cortex_height) = grid.dimensions();
+            foveation.cortical_map(&edge_map, cortex_width, cortex_height)
+        });
+
         VisualResponse {
             cone_activations: cone_responses,
+            horizontal_activations,
+            on_bipolar_activations,
+            off_bipolar_activations,
+            rod_activations: rod_responses,
+            rod_contribution,
             edge_map,
+            red_green_map,
+            blue_yellow_map,
+            retinal_motion,
+            lgn,
             orientation_map,
+            orientation_estimates,
             v2_features,
             v4_features,
             features,
+            cortical_map,
+            pupil_diameter: self.pupil_reflex.as_ref().map(|reflex| reflex.pupil.diameter()),
+            melanopsin_firing_rate: self.pupil_reflex.as_ref().map(|reflex| reflex.iprgcs.mean_firing_rate()),
         }
     }
 
-    /// Process simple grayscale image (intensity only)
+    /// Process simple grayscale image (intensity only) under daylight illumination
     pub fn process_grayscale_image(&mut self, image: &[Vec<f32>]) -> VisualResponse {
-        // Convert grayscale to light stimuli (using mid-spectrum wavelength)
-        let light_pattern: Vec<Vec<LightStimulus>> = image
-            .iter()
-            .map(|row| {
-                row.iter()
-                    .map(|&intensity| LightStimulus::white_light(intensity * 100.0))
-                    .collect()
-            })
-            .collect();
+        self.process_grayscale_image_at_luminance(image, PHOTOPIC_INTENSITY)
+    }
 
+    /// Process a grayscale image viewed under a given illumination level
+    ///
+    /// # Arguments
+    /// * `image` - 2D array of reflectances (0.0 = black, 1.0 = white)
+    /// * `luminance` - Intensity of a white pixel (100.0 = daylight, 0.01 = starlight)
+    pub fn process_grayscale_image_at_luminance(
+        &mut self,
+        image: &[Vec<f32>],
+        luminance: f32,
+    ) -> VisualResponse {
+        let light_pattern = grayscale_light_pattern(image, luminance);
         self.process_scene(&light_pattern)
     }
 
     /// Stage 1: Phototransduction
-    fn process_phototransduction(
+    ///
+    /// Cones and rods transduce the scene in parallel. Each cone adapts
+    /// divisively to the mean excitation of its own class over the visual
+    /// field (von Kries adaptation), so cone signals depend on contrast
+    /// rather than on absolute light level. Rod signals reach the
+    /// cone pathway (via rod-cone gap junctions and AII amacrine cells) with a
+    /// weight that depends on the overall light level: full weight in scotopic
+    /// conditions, none in photopic conditions, and a log-linear blend in the
+    /// mesopic range in between. The light level is judged by the mean
+    /// excitation of L and M cones: once they are driven well above their
+    /// threshold, rod-cone coupling is switched off, however strongly the
+    /// light would still excite the (saturating) rods.
+    ///
+    /// # Returns
+    /// (cone hyperpolarization, rod activations, rod contribution weight)
+    fn process_phototransduction<I: RetinalInput>(
         &mut self,
-        light_pattern: &[Vec<LightStimulus>],
+        light_pattern: &[Vec<I>],
+    ) -> (Vec<Vec<f32>>, Vec<Vec<f32>>, f32) {
+        let mut cone_signals = vec![vec![0.0; self.width]; self.height];
+        let mut rod_activations = vec![vec![0.0; self.width]; self.height];
+        // Light reaching each cone and rod, blurred by the optics if modelled
+        let (excitations, rod_excitations) = match &self.optics {
+            Some(optics) => self.retinal_image(light_pattern, optics),
+            None => {
+                let (image_height, image_width) = (light_pattern.len(), light_pattern.first().map_or(0, Vec::len));
+                let sample = |position: (f32, f32), excitation: &dyn Fn(&I) -> f32| {
+                    bilinear_taps(position, image_width, image_height).map(|taps| {
+                        taps.iter()
+                            .map(|&((x, y), weight)| weight * excitation(&light_pattern[y][x]))
+                            .sum::<f32>()
+                    })
+                };
+                let cones = self
+                    .cones
+                    .iter()
+                    .zip(&self.cone_positions)
+                    .map(|(cone, &position)| sample(position, &|light| light.cone_excitation(cone)))
+                    .collect();
+                let rods = self
+                    .cone_positions
+                    .iter()
+                    .map(|&position| sample(position, &|light| light.rod_excitation()))
+                    .collect();
+                (cones, rods)
+            }
+        };
+
+        // Mean excitation of each cone class: the background each cone adapts to
+        let mut class_totals = [(0.0, 0usize); 3];
+        for ((cone, block), excitation) in self.cones.iter().zip(&self.cone_blocks).zip(&excitations) {
+            if let Some(excitation) = excitation {
+                let total = &mut class_totals[cone_class_index(cone.cone_type())];
+                total.0 += excitation * block.area() as f32;
+                total.1 += block.area();
+            }
+        }
+        let class_means = class_totals.map(|(sum, count)| if count > 0 { sum / count as f32 } else { 0.0 });
+
+        for ((((cone, rod), block), excitation), rod_excitation) in self
+            .cones
+            .iter_mut()
+            .zip(self.rods.iter_mut())
+            .zip(&self.cone_blocks)
+            .zip(excitations)
+            .zip(rod_excitations)
+        {
+            if let (Some(excitation), Some(rod_excitation)) = (excitation, rod_excitation) {
+                cone.transduce_on_background(excitation, class_means[cone_class_index(cone.cone_type())]);
+                rod.transduce(rod_excitation);
+
+                // Fill the whole patch the photoreceptors sample
+                let (cone_level, rod_level) = (cone.hyperpolarization(), rod.response_level());
+                for py in block.y..(block.y + block.height).min(self.height) {
+                    for px in block.x..(block.x + block.width).min(self.width) {
+                        cone_signals[py][px] = cone_level;
+                        rod_activations[py][px] = rod_level;
+                    }
+                }
+            }
+        }
+
+        // The photopic light level (L and M cones) decides how far rod
+        // signals are shunted out of the cone pathway
+        let (photopic_total, photopic_count) = [ConeType::M, ConeType::L]
+            .iter()
+            .map(|&cone_type| class_totals[cone_class_index(cone_type)])
+            .fold((0.0, 0), |(total, count), (sum, samples)| (total + sum, count + samples));
+        let mean_photopic = if photopic_count > 0 {
+            photopic_total / photopic_count as f32
+        } else {
+            0.0
+        };
+        let rod_contribution = mesopic_rod_weight(mean_photopic);
+
+        (cone_signals, rod_activations, rod_contribution)
+    }
+
+    /// Forms the retinal image each photoreceptor class sees through the optics
+    ///
+    /// The excitation pattern of each cone class (computed with the first
+    /// cone of the class) is blurred with the point spread function at the
+    /// class's peak wavelength, so S-cones see the most chromatically
+    /// defocused image. Rods see the image at the rhodopsin peak. The
+    /// blurred image is only evaluated around the cone positions, and is
+    /// scaled by the retinal illuminance the pupil admits.
+    ///
+    /// # Returns
+    /// (cone excitations, rod excitations), None for cones outside the image
+    fn retinal_image<I: RetinalInput>(
+        &self,
+        light_pattern: &[Vec<I>],
+        optics: &EyeOptics,
+    ) -> (Vec<Option<f32>>, Vec<Option<f32>>) {
+        let (image_height, image_width) = (light_pattern.len(), light_pattern.first().map_or(0, Vec::len));
+        let illuminance = optics.retinal_illuminance();
+        let taps: Vec<_> = self
+            .cone_positions
+            .iter()
+            .map(|&position| bilinear_taps(position, image_width, image_height))
+            .collect();
+
+        let mut cone_excitations = vec![None; self.cones.len()];
+        for cone_type in [ConeType::S, ConeType::M, ConeType::L] {
+            let members: Vec<usize> = (0..self.cones.len())
+                .filter(|&i| self.cones[i].cone_type() == cone_type && taps[i].is_some())
+                .collect();
+            let Some(&first) = members.first() else {
+                continue;
+            };
+            let representative = &self.cones[first];
+            let excitation_map: Vec<Vec<f32>> = light_pattern
+                .iter()
+                .map(|row| row.iter().map(|light| illuminance * light.cone_excitation(representative)).collect())
+                .collect();
+            let member_taps: Vec<&[((usize, usize), f32)]> = members.iter().filter_map(|&i| taps[i].as_deref()).collect();
+            let blurred = interpolate_blurred(optics, &excitation_map, representative.peak_wavelength(), &member_taps);
+            for (&i, excitation) in members.iter().zip(blurred) {
+                cone_excitations[i] = Some(excitation.max(0.0));
+            }
+        }
+
+        let rod_map: Vec<Vec<f32>> = light_pattern
+            .iter()
+            .map(|row| row.iter().map(|light| illuminance * light.rod_excitation()).collect())
+            .collect();
+        let rod_members: Vec<usize> = (0..taps.len()).filter(|&i| taps[i].is_some()).collect();
+        let rod_taps: Vec<&[((usize, usize), f32)]> = rod_members.iter().filter_map(|&i| taps[i].as_deref()).collect();
+        let mut rod_excitations = vec![None; taps.len()];
+        for (&i, excitation) in rod_members.iter().zip(interpolate_blurred(optics, &rod_map, RHODOPSIN_PEAK, &rod_taps)) {
+            rod_excitations[i] = Some(excitation.max(0.0));
+        }
+
+        (cone_excitations, rod_excitations)
+    }
+
+    /// Non-image-forming pathway: ipRGCs drive the pupillary light reflex
+    ///
+    /// ipRGCs pool melanopsin excitation (scaled by the current retinal
+    /// illuminance) over one frame interval, the pupil moves towards the
+    /// diameter their drive calls for, and the optics take on the new
+    /// pupil, which changes both the blur and the light admitted on the
+    /// next frame.
+    fn process_pupil_reflex<I: RetinalInput>(&mut self, light_pattern: &[Vec<I>]) {
+        let dt_ms = self.frame_interval_ms();
+        let Some(reflex) = self.pupil_reflex.as_mut() else {
+            return;
+        };
+        let illuminance = self.optics.as_ref().map_or(1.0, EyeOptics::retinal_illuminance);
+        let excitation: Vec<Vec<f32>> = light_pattern
+            .iter()
+            .map(|row| row.iter().map(|light| illuminance * light.melanopsin_excitation()).collect())
+            .collect();
+
+        reflex.iprgcs.process(&excitation, dt_ms);
+        let diameter = reflex.pupil.step(reflex.iprgcs.mean_response(), dt_ms);
+        if let Some(optics) = self.optics.as_mut() {
+            optics.set_pupil_diameter(diameter);
+        }
+    }
+
+    /// Stage 2: Horizontal cells (outer plexiform layer)
+    ///
+    /// Horizontal cells pool cone hyperpolarization, spread it through
+    /// their gap junctions and feed it back onto the cone terminals, so
+    /// each cone's synaptic output is its own signal minus a blurred
+    /// surround. L/M cones receive H1 feedback (an L+M surround); S-cones
+    /// receive H2 feedback, dominated by L+M in most places, which makes
+    /// their surround colour-opponent (blue-yellow).
+    ///
+    /// # Returns
+    /// Retinal activations passed to the inner retina (cone output plus
+    /// mixed-in rod signal)
+    fn process_horizontal_cells(
+        &mut self,
+        cone_signals: &[Vec<f32>],
+        rod_activations: &[Vec<f32>],
+        rod_contribution: f32,
     ) -> Vec<Vec<f32>> {
+        self.horizontal_layer.process(cone_signals, &self.cone_type_map);
+
+        let mut activations = vec![vec![0.0; self.width]; self.height];
+        for (cone, block) in self.cones.iter_mut().zip(&self.cone_blocks) {
+            let (x, y) = block.center();
+            cone.apply_horizontal_feedback(self.horizontal_layer.feedback_at(cone.cone_type(), x, y));
+
+            // Use synaptic output (0 = dark, 1 = bright) as activation
+            let cone_level = cone.synaptic_output();
+            for row in activations.iter_mut().take((block.y + block.height).min(self.height)).skip(block.y) {
+                for activation in row.iter_mut().take((block.x + block.width).min(self.width)).skip(block.x) {
+                    *activation = cone_level;
+                }
+            }
+        }
+
+        if rod_contribution > 0.0 {
+            for (row, rod_row) in activations.iter_mut().zip(rod_activations) {
+                for (activation, &rod_activation) in row.iter_mut().zip(rod_row) {
+                    *activation = (*activation + rod_activation * rod_contribution).min(1.0);
+                }
+            }
+        }
+
+        activations
+    }
+
+    /// Stage 3: Bipolar cells (inner plexiform layer)
+    ///
+    /// Every cone sends its glutamate to the bipolar cells on its
+    /// connection list. Rod signals reach the cone terminals through
+    /// rod-cone gap junctions, hyperpolarizing them and lowering glutamate
+    /// release in proportion to the mesopic rod weight.
+    fn process_bipolar_cells(&mut self, rod_contribution: f32) {
+        for (cone, rod) in self.cones.iter().zip(&self.rods) {
+            let rod_input = rod.response_level() * rod_contribution;
+            let transmissions: Vec<(usize, f32)> = cone
+                .transmit_glutamate()
+                .into_iter()
+                .map(|(id, glutamate)| (id, (glutamate - rod_input).max(0.0)))
+                .collect();
+            self.bipolar_layer.receive(&transmissions);
+        }
+        self.bipolar_layer.process();
+    }
+
+    /// Starburst amacrine cells and ON-OFF direction-selective ganglion cells
+    ///
+    /// Both are driven by transient bipolar cells and keep state between
+    /// calls, so successive images processed by the pathway act as the
+    /// frames of a movie.
+    fn process_retinal_motion(&mut self, on_transient: &[Vec<f32>], off_transient: &[Vec<f32>]) -> RetinalMotion {
+        self.amacrine_layer.process(on_transient, off_transient);
+        self.direction_layer
+            .process_direction_selective(on_transient, off_transient, &self.amacrine_layer);
+
+        RetinalMotion {
+            direction_responses: self.direction_layer.direction_responses(),
+        }
+    }
+
+    /// Returns the output of one bipolar cell type at each pixel
+    ///
+    /// Each cone's bipolar cells report for the patch the cone samples.
+    pub fn bipolar_activations(&self, cell_type: BipolarType) -> Vec<Vec<f32>> {
         let mut activations = vec![vec![0.0; self.width]; self.height];
+        let outputs = self.bipolar_layer.outputs(cell_type);
+
+        for (&output, block) in outputs.iter().zip(&self.cone_blocks) {
+            for row in activations.iter_mut().take((block.y + block.height).min(self.height)).skip(block.y) {
+                for activation in row.iter_mut().take((block.x + block.width).min(self.width)).skip(block.x) {
+                    *activation = output;
+                }
+            }
+        }
+
+        activations
+    }
+
+    /// Extract high-level features from V1 responses
+    fn extract_features(&self) -> VisualFeatures {
+        let orientation_histogram = self.field_orientation_tuning();
+        let total_activation = orientation_histogram.iter().map(|(_, activation)| activation).sum();
+
+        // Horizontal, vertical and diagonal buckets: (summed activation, channels)
+        let mut buckets = [(0.0f32, 0usize); 3];
+        for &(orientation, activation) in &orientation_histogram {
+            let orientation_deg = orientation.degrees();
+
+            // A column's orientation is that of the bars and edges its
+            // Gabor fields prefer (the carrier varies across it)
+            let bucket = if orientation_deg < 22.5 || orientation_deg > 157.5 {
+                0
+            } else if (67.5..=112.5).contains(&orientation_deg) {
+                1
+            } else {
+                2
+            };
+            buckets[bucket].0 += activation;
+            buckets[bucket].1 += 1;
+        }
+
+        // Average over the channels in each bucket for a fair comparison
+        // (four channels give two diagonal ones but one of each other)
+        let mean = |(total, channels): (f32, usize)| total / channels.max(1) as f32;
+
+        VisualFeatures {
+            horizontal_strength: mean(buckets[0]),
+            vertical_strength: mean(buckets[1]),
+            diagonal_strength: mean(buckets[2]) / DIAGONAL_DISCOUNT,
+            total_activation,
+            orientation_histogram,
+        }
+    }
+
+    /// Returns the V1 population tuning over orientations in the visual field
+    ///
+    /// In a foveated pathway V1 sees the log-polar cortical map, so each
+    /// column's orientation on the map is converted back to the visual
+    /// field and credited to the nearest orientation channel.
+    fn field_orientation_tuning(&self) -> Vec<(Orientation, f32)> {
+        let Some(grid) = &self.cortical_grid else {
+            return self.v1_cortex.orientation_tuning();
+        };
+        let channels = self.v1_cortex.orientations();
+        let mut tuning: Vec<(Orientation, f32)> = channels.iter().map(|&orientation| (orientation, 0.0)).collect();
+        let separation = |a: f32, b: f32| {
+            let difference = (a - b).rem_euclid(180.0);
+            difference.min(180.0 - difference)
+        };
+
+        for column in self.v1_cortex.columns() {
+            let Some((x, y)) = column.neurons().first().map(|neuron| neuron.position()) else {
+                continue;
+            };
+            let field_degrees = grid.field_orientation(x, y, column.orientation().degrees());
+            let nearest = (0..channels.len()).min_by(|&a, &b| {
+                separation(channels[a].degrees(), field_degrees).total_cmp(&separation(channels[b].degrees(), field_degrees))
+            });
+            if let Some(nearest) = nearest {
+                tuning[nearest].1 += column.max_activation();
+            }
+        }
+        tuning
+    }
+
+    /// Returns the cone population of the mosaic
+    ///
+    /// The L:M:S ratio is that of the cones actually placed, which can
+    /// differ slightly from the population the mosaic was generated for.
+    pub fn cone_population(&self) -> &ConePopulation {
+        &self.cone_population
+    }
+
+    /// Returns the cones of the retina (row-major, one per sampling block)
+    pub fn cones(&self) -> &[Cone] {
+        &self.cones
+    }
+
+    /// Returns the bipolar cell layer
+    pub fn bipolar_layer(&self) -> &BipolarLayer {
+        &self.bipolar_layer
+    }
+
+    /// Returns the starburst amacrine layer
+    pub fn amacrine_layer(&self) -> &AmacrineLayer {
+        &self.amacrine_layer
+    }
+
+    /// Returns the direction-selective ganglion cells
+    pub fn direction_selective_layer(&self) -> &GanglionLayer {
+        &self.direction_layer
+    }
+
+    /// Returns a copy in which V1 receives a different LGN stream
+    ///
+    /// By default V1 reads the parvocellular (midget) stream. Selecting
+    /// the magnocellular stream feeds V1 from large, transient parasol
+    /// cells instead, e.g. to compare motion and form processing.
+    pub fn with_v1_stream(mut self, stream: LgnLayerType) -> Self {
+        self.v1_stream = stream;
+        self
+    }
+
+    /// Selects the LGN stream V1 receives
+    pub fn set_v1_stream(&mut self, stream: LgnLayerType) {
+        self.v1_stream = stream;
+    }
+
+    /// Returns the LGN stream V1 receives
+    pub fn v1_stream(&self) -> LgnLayerType {
+        self.v1_stream
+    }
+
+    /// Returns the midget ganglion cells
+    pub fn midget_layer(&self) -> &GanglionLayer {
+        &self.ganglion_layer
+    }
+
+    /// Encodes the current midget ganglion output as spike trains
+    ///
+    /// Call after processing an image; the trains can be delivered to a
+    /// `NeuralNetwork` with `run_spike_trains`. Use
+    /// `encoder.encode_layer(pathway.parasol_layer())` for the parasol cells.
+    ///
+    /// # Arguments
+    /// * `encoder` - Coding scheme and time window
+    pub fn ganglion_spike_trains(&self, encoder: &mut SpikeEncoder) -> Vec<SpikeTrain> {
+        encoder.encode_layer(&self.ganglion_layer)
+    }
+
+    /// Returns a copy with a differently configured V1
+    ///
+    /// # Arguments
+    /// * `v1_cortex` - V1 covering the same visual field, or the same
+    ///   cortical map in a foveated pathway (e.g. with more
+    ///   orientation channels, see `V1Cortex::with_orientation_channels`)
+    ///
+    /// # Panics
+    /// Panics if the cortex does not cover the visual field, or the
+    /// cortical map of a foveated pathway
+    pub fn with_v1_cortex(mut self, v1_cortex: V1Cortex) -> Self {
+        let expected = self.cortical_grid.map_or((self.width, self.height), |grid| grid.dimensions());
+        assert_eq!(v1_cortex.dimensions(), expected, "V1 does not match the pathway's cortical field");
+        self.v1_cortex = v1_cortex;
+        self.v1_cortex.set_frame_interval_ms(self.frame_interval_ms());
+        self
+    }
+
+    /// Returns a copy in which V1 adaptation carries over between static images
+    ///
+    /// By default V1 neither fatigues on nor applies adaptation to the
+    /// images of `process_grayscale_image` (and the other static-image
+    /// methods), so the cortex itself answers the same input the same
+    /// way every time. With adaptation enabled, V1 neurons fatigue over
+    /// successive images as they always do over the frames of
+    /// `process_frame`, e.g. for adapt-then-test experiments.
+    pub fn with_v1_adaptation(mut self, enabled: bool) -> Self {
+        self.v1_adaptation = enabled;
+        self
+    }
+
+    /// Returns whether V1 adaptation carries over between static images
+    pub fn v1_adaptation(&self) -> bool {
+        self.v1_adaptation
+    }
+
+    /// Returns the V1 cortex (orientation columns and their adaptation state)
+    pub fn v1_cortex(&self) -> &V1Cortex {
+        &self.v1_cortex
+    }
+
+    /// Returns the parasol ganglion cells
+    pub fn parasol_layer(&self) -> &GanglionLayer {
+        &self.parasol_layer
+    }
+
+    /// Returns a copy with a differently configured LGN stage
+    ///
+    /// # Arguments
+    /// * `lgn` - LGN covering the same visual field (e.g. with attention
+    ///   or a burst-mode resting potential already set)
+    pub fn with_lgn(mut self, lgn: LateralGeniculateNucleus) -> Self {
+        self.lgn = lgn;
+        self.lgn.set_frame_interval_ms(self.frame_interval_ms());
+        self
+    }
+
+    /// Returns the lateral geniculate nucleus
+    pub fn lgn(&self) -> &LateralGeniculateNucleus {
+        &self.lgn
+    }
+
+    /// Returns the lateral geniculate nucleus for modification
+    ///
+    /// Use this to set attention or cortical feedback between frames.
+    pub fn lgn_mut(&mut self) -> &mut LateralGeniculateNucleus {
+        &mut self.lgn
+    }
+
+    /// Returns the colour-opponent ganglion cells
+    pub fn color_opponent_layer(&self) -> &GanglionLayer {
+        &self.opponent_layer
+    }
+
+    /// Returns the cone mosaic
+    pub fn mosaic(&self) -> &ConeMosaic {
+        &self.mosaic
+    }
+
+    /// Returns the number of cones sampling the visual field
+    pub fn cone_count(&self) -> usize {
+        self.cones.len()
+    }
+
+    /// Returns the foveation of a foveated pathway
+    pub fn foveation(&self) -> Option<&Foveation> {
+        self.foveation.as_ref()
+    }
+
+    /// Places the eye's optics in front of the retina
+    ///
+    /// By default the retina receives a perfectly focused image. With
+    /// optics, each photoreceptor class samples the image blurred by
+    /// diffraction, chromatic defocus and intraocular scatter.
+    ///
+    /// # Arguments
+    /// * `optics` - Optics of the eye
+    ///
+    /// # Returns
+    /// The pathway imaging through the given optics
+    pub fn with_optics(mut self, optics: EyeOptics) -> Self {
+        self.optics = Some(optics);
+        self
+    }
+
+    /// Replaces the eye's optics (None = perfect focus)
+    pub fn set_optics(&mut self, optics: Option<EyeOptics>) {
+        self.optics = optics;
+    }
+
+    /// Returns the eye's optics, if modelled
+    pub fn optics(&self) -> Option<&EyeOptics> {
+        self.optics.as_ref()
+    }
+
+    /// Returns mutable access to the eye's optics, e.g. to change the pupil
+    pub fn optics_mut(&mut self) -> Option<&mut EyeOptics> {
+        self.optics.as_mut()
+    }
+
+    /// Adds melanopsin ipRGCs that control the pupil of the given optics
+    ///
+    /// On every `process_frame`, ipRGCs integrate the melanopsin-weighted
+    /// light and the pupil follows their slow, sustained drive after the
+    /// reflex latency. The pupil size feeds back through the optics,
+    /// closing the loop.
+    ///
+    /// The reflex only runs in `process_frame` (and the methods built on
+    /// it, such as `process_sequence`). `process_grayscale_image` and the
+    /// other static-image methods leave ipRGCs and pupil as they are.
+    ///
+    /// # Arguments
+    /// * `optics` - Optics whose pupil the reflex controls; they replace
+    ///   any optics set before, with the pupil fully dilated
+    ///
+    /// # Returns
+    /// The pathway with a pupillary light reflex, dark adapted (ipRGCs at
+    /// rest, pupil fully dilated)
+    pub fn with_pupil_reflex(mut self, mut optics: EyeOptics) -> Self {
+        let pupil = Pupil::dark_adapted();
+        optics.set_pupil_diameter(pupil.diameter());
+        self.optics = Some(optics);
+        self.pupil_reflex = Some(PupillaryReflex {
+            iprgcs: MelanopsinLayer::new(self.width, self.height),
+            pupil,
+        });
+        self
+    }
+
+    /// Returns the melanopsin ipRGCs, if the pupil reflex is modelled
+    pub fn melanopsin_layer(&self) -> Option<&MelanopsinLayer> {
+        self.pupil_reflex.as_ref().map(|reflex| &reflex.iprgcs)
+    }
+
+    /// Returns the reflex-controlled pupil, if modelled
+    pub fn pupil(&self) -> Option<&Pupil> {
+        self.pupil_reflex.as_ref().map(|reflex| &reflex.pupil)
+    }
+
+    /// Returns the dimensions of the visual field
+    pub fn dimensions(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+}
+
+/// Input that can drive the photoreceptor mosaic
+trait RetinalInput {
+    /// Returns the light absorbed by a given cone
+    fn cone_excitation(&self, cone: &Cone) -> f32;
+
+    /// Returns the light absorbed by a rod
+    fn rod_excitation(&self) -> f32;
+
+    /// Returns the light absorbed by melanopsin
+    fn melanopsin_excitation(&self) -> f32;
+}
+
+impl<L: LightSource> RetinalInput for L {
+    fn cone_excitation(&self, cone: &Cone) -> f32 {
+        self.absorbed_intensity(|wavelength| cone.spectral_sensitivity(wavelength))
+    }
+
+    fn rod_excitation(&self) -> f32 {
+        self.absorbed_intensity(rhodopsin_sensitivity)
+    }
+
+    fn melanopsin_excitation(&self) -> f32 {
+        self.absorbed_intensity(melanopsin_sensitivity)
+    }
+}
+
+/// Precomputed photoreceptor excitations for one pixel of a colour image
+struct PhotoreceptorExcitation {
+    linear_rgb: [f32; 3],
+    rod: f32,
+    melanopsin: f32,
+}
+
+impl RetinalInput for PhotoreceptorExcitation {
+    fn cone_excitation(&self, cone: &Cone) -> f32 {
+        let weights = excitation_weights(cone.peak_wavelength());
+        let rgb = self.linear_rgb;
+        (weights[0] * rgb[0] + weights[1] * rgb[1] + weights[2] * rgb[2]).max(0.0)
+    }
+
+    fn rod_excitation(&self) -> f32 {
+        self.rod
+    }
+
+    fn melanopsin_excitation(&self) -> f32 {
+        self.melanopsin
+    }
+}
 
-        for (idx, cone) in self.cones.iter_mut().enumerate() {
-            let y = idx / self.width;
-            let x = idx % self.width;
+/// Melanopsin ipRGCs and the pupil they drive
+#[derive(Debug, Clone)]
+struct PupillaryReflex {
+    iprgcs: MelanopsinLayer,
+    pupil: Pupil,
+}
 
-            if y < light_pattern.len() && x < light_pattern[0].len() {
-                cone.phototransduction(light_pattern[y][x]);
-                // Use response level (0 = dark, 1 = bright) as activation
-                activations[y][x] = cone.response_level();
-            }
-        }
+/// Index of a cone class in per-class arrays
+fn cone_class_index(cone_type: ConeType) -> usize {
+    match cone_type {
+        ConeType::S => 0,
+        ConeType::M => 1,
+        ConeType::L => 2,
+    }
+}
 
-        activations
+/// Bilinear interpolation taps (pixel, weight) for a point of an image
+///
+/// Points past the last row or column take the edge pixel. Taps with zero
+/// weight are left out.
+///
+/// # Returns
+/// None if the point lies outside the image
+fn bilinear_taps((x, y): (f32, f32), width: usize, height: usize) -> Option<Vec<((usize, usize), f32)>> {
+    if x < 0.0 || y < 0.0 || x as usize >= width || y as usize >= height {
+        return None;
     }
+    let (x0, y0) = (x as usize, y as usize);
+    let (x1, y1) = ((x0 + 1).min(width - 1), (y0 + 1).min(height - 1));
+    let (fx, fy) = (x - x0 as f32, y - y0 as f32);
 
-    /// Extract high-level features from V1 responses
-    fn extract_features(&self) -> VisualFeatures {
-        let columns = self.v1_cortex.columns();
-        
-        let mut horizontal_strength = 0.0;
-        let mut vertical_strength = 0.0;
-        let mut diagonal_strength = 0.0;
-        let mut total_activation = 0.0;
-
-        for column in columns {
-            let activation = column.max_activation();
-            total_activation += activation;
-
-            let orientation_deg = column.orientation().degrees();
-            
-            // Note: V1 neurons respond to edges/bars of a given orientation
-            // 0° detects horizontal edges (vertical gradient)
-            // 90° detects vertical edges (horizontal gradient)
-            // We swap the labels to match the STRUCTURE orientation (not gradient direction)
-            
-            if orientation_deg < 22.5 || orientation_deg > 157.5 {
-                // 0° V1 neurons -> detect vertical structures (horizontal transitions)
-                vertical_strength += activation;
-            } else if (67.5..=112.5).contains(&orientation_deg) {
-                // 90° V1 neurons -> detect horizontal structures (vertical transitions)
-                horizontal_strength += activation;
-            } else {
-                diagonal_strength += activation;
-            }
-        }
+    Some(
+        [
+            ((x0, y0), (1.0 - fx) * (1.0 - fy)),
+            ((x1, y0), fx * (1.0 - fy)),
+            ((x0, y1), (1.0 - fx) * fy),
+            ((x1, y1), fx * fy),
+        ]
+        .into_iter()
+        .filter(|&(_, weight)| weight > 0.0)
+        .collect(),
+    )
+}
 
-        // Normalize diagonal strength: we have 2 diagonal orientations (45° and 135°)
-        // vs 1 horizontal (0°) and 1 vertical (90°), so divide by 2 for fair comparison
-        // Additionally, diagonal detectors also respond to H/V edges at angles, so reduce further
-        diagonal_strength /= 2.25;
+/// Blurs an image through the optics and interpolates it at each set of taps
+fn interpolate_blurred(
+    optics: &EyeOptics,
+    image: &[Vec<f32>],
+    wavelength: f32,
+    taps: &[&[((usize, usize), f32)]],
+) -> Vec<f32> {
+    let points: Vec<(usize, usize)> = taps.iter().flat_map(|taps| taps.iter().map(|&(point, _)| point)).collect();
+    let mut blurred = optics.blur_at(image, wavelength, &points).into_iter();
 
-        VisualFeatures {
-            horizontal_strength,
-            vertical_strength,
-            diagonal_strength,
-            total_activation,
-        }
+    taps.iter()
+        .map(|taps| taps.iter().map(|&(_, weight)| weight * blurred.next().unwrap_or(0.0)).sum())
+        .collect()
+}
+
+/// Converts grayscale reflectances to white light (mid-spectrum wavelength)
+fn grayscale_light_pattern(image: &[Vec<f32>], luminance: f32) -> Vec<Vec<LightStimulus>> {
+    image
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|&intensity| LightStimulus::white_light(intensity * luminance))
+                .collect()
+        })
+        .collect()
+}
+
+/// Clips negative values (firing rates cannot fall below zero)
+fn rectify(mut map: Vec<Vec<f32>>) -> Vec<Vec<f32>> {
+    for value in map.iter_mut().flatten() {
+        *value = value.max(0.0);
     }
+    map
+}
 
-    /// Returns the dimensions of the visual field
-    pub fn dimensions(&self) -> (usize, usize) {
-        (self.width, self.height)
+/// Weight of the rod signal in the retinal output for a given mean L+M
+/// cone excitation
+///
+/// 1.0 below the scotopic limit, 0.0 above the photopic limit, and a
+/// log-linear interpolation across the mesopic range.
+fn mesopic_rod_weight(mean_excitation: f32) -> f32 {
+    if mean_excitation <= SCOTOPIC_LIMIT {
+        return 1.0;
+    }
+    if mean_excitation >= PHOTOPIC_LIMIT {
+        return 0.0;
     }
+
+    let position = (mean_excitation / SCOTOPIC_LIMIT).log10() / (PHOTOPIC_LIMIT / SCOTOPIC_LIMIT).log10();
+    1.0 - position
 }
 
 /// Response of the visual system to input
 #[derive(Debug)]
 pub struct VisualResponse {
-    /// Activation levels of cones (0.0 = dark adapted, 1.0 = light adapted)
+    /// Activation levels of the retina (cones plus mixed-in rod signal)
+    /// (0.0 = dark adapted, 1.0 = light adapted)
     pub cone_activations: Vec<Vec<f32>>,
+
+    /// Activation of H1 horizontal cells (pooled L+M cone hyperpolarization)
+    pub horizontal_activations: Vec<Vec<f32>>,
+
+    /// Output of sustained ON bipolar cells (depolarized by light)
+    pub on_bipolar_activations: Vec<Vec<f32>>,
+
+    /// Output of sustained OFF bipolar cells (depolarized by darkness)
+    pub off_bipolar_activations: Vec<Vec<f32>>,
+
+    /// Activation levels of rods (0.0 = dark, 1.0 = saturated)
+    pub rod_activations: Vec<Vec<f32>>,
+
+    /// Weight of the rod signal in the retinal output (1.0 = scotopic, 0.0 = photopic)
+    pub rod_contribution: f32,
     
     /// Edge map from ganglion cells
     pub edge_map: Vec<Vec<f32>>,
+
+    /// Red-green opponent map from midget cells (positive = red, negative = green)
+    pub red_green_map: Vec<Vec<f32>>,
+
+    /// Blue-yellow opponent map from small bistratified cells
+    /// (positive = blue, negative = yellow)
+    pub blue_yellow_map: Vec<Vec<f32>>,
+
+    /// Activity of the parvo-, magno- and koniocellular LGN layers
+    pub lgn: LgnResponse,
+
+    /// Motion signalled by direction-selective ganglion cells since the
+    /// previous frame
+    pub retinal_motion: RetinalMotion,
     
-    /// Dominant orientation at each location (if any)
+    /// Dominant orientation at each location (if any); on the log-polar
+    /// cortical map in a foveated pathway
     pub orientation_map: Vec<Vec<Option<Orientation>>>,
+
+    /// Population-vector orientation and coherence at each location (if
+    /// any); on the log-polar cortical map in a foveated pathway
+    pub orientation_estimates: Vec<Vec<Option<OrientationEstimate>>>,
     
     /// V2 features (corners and contours)
     pub v2_features: crate::v2_cortex::V2Response,
@@ -214,6 +1370,17 @@ pub struct VisualResponse {
     
     /// High-level extracted features
     pub features: VisualFeatures,
+
+    /// Edge map in log-polar V1 coordinates (foveated pathways only)
+    /// Columns: distance from the foveal representation; rows: polar angle
+    pub cortical_map: Option<Vec<Vec<f32>>>,
+
+    /// Pupil diameter set by the light reflex for the next frame (mm),
+    /// when the reflex is modelled
+    pub pupil_diameter: Option<f32>,
+
+    /// Mean firing rate of melanopsin ipRGCs (Hz), when the reflex is modelled
+    pub melanopsin_firing_rate: Option<f32>,
 }
 
 /// High-level visual features extracted from V1
@@ -230,6 +1397,9 @@ pub struct VisualFeatures {
     
     /// Total cortical activation
     pub total_activation: f32,
+
+    /// Summed activation of each V1 orientation channel
+    pub orientation_histogram: Vec<(Orientation, f32)>,
 }
 
 impl VisualFeatures {
@@ -255,6 +1425,46 @@ impl VisualFeatures {
     pub fn edge_strength(&self) -> f32 {
         self.horizontal_strength + self.vertical_strength + self.diagonal_strength
     }
+
+    /// Returns the scene orientation decoded from the orientation histogram
+    pub fn orientation_estimate(&self) -> Option<OrientationEstimate> {
+        OrientationEstimate::from_tuning(&self.orientation_histogram)
+    }
+}
+
+/// Retinal motion signalled by ON-OFF direction-selective ganglion cells
+#[derive(Debug, Clone)]
+pub struct RetinalMotion {
+    /// Summed firing rate for each preferred direction,
+    /// indexed by `MotionDirection::index`
+    pub direction_responses: [f32; 4],
+}
+
+impl RetinalMotion {
+    /// Returns the summed firing rate of cells preferring a direction
+    pub fn response(&self, direction: MotionDirection) -> f32 {
+        self.direction_responses[direction.index()]
+    }
+
+    /// Returns the direction with the strongest response, if any cell fired
+    pub fn dominant_direction(&self) -> Option<MotionDirection> {
+        MotionDirection::ALL
+            .into_iter()
+            .max_by(|a, b| self.response(*a).total_cmp(&self.response(*b)))
+            .filter(|&direction| self.response(direction) > 0.0)
+    }
+
+    /// Returns how strongly motion favours one direction over its opposite
+    /// (0.0 = no preference, 1.0 = only one direction responds)
+    pub fn direction_index(&self, direction: MotionDirection) -> f32 {
+        let preferred = self.response(direction);
+        let null = self.response(direction.opposite());
+        if preferred + null > 0.0 {
+            (preferred - null) / (preferred + null)
+        } else {
+            0.0
+        }
+    }
 }
 
 /// Helper functions to create test patterns
@@ -344,12 +1554,71 @@ pub mod test_patterns {
 
         image
     }
+
+    /// Creates a bright vertical bar whose left edge is at column `position`
+    ///
+    /// Call with increasing or decreasing positions to make the frames of
+    /// a bar moving horizontally.
+    pub fn bar_at(width: usize, height: usize, bar_width: usize, position: usize) -> Vec<Vec<f32>> {
+        let mut image = vec![vec![0.0; width]; height];
+        for row in image.iter_mut() {
+            for pixel in row.iter_mut().skip(position).take(bar_width) {
+                *pixel = 1.0;
+            }
+        }
+        image
+    }
+
+    /// Creates a square-wave grating of vertical stripes
+    ///
+    /// Increasing `phase` (in pixels) shifts the stripes to the right.
+    pub fn grating(width: usize, height: usize, period: usize, phase: usize) -> Vec<Vec<f32>> {
+        let period = period.max(2);
+        (0..height)
+            .map(|_| {
+                (0..width)
+                    .map(|x| if (x + period - phase % period) % period < period / 2 { 1.0 } else { 0.0 })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Creates a sinusoidal grating around mid-grey
+    ///
+    /// Increasing `phase` (in pixels) drifts the stripes across their
+    /// orientation, so a sequence of phases makes a drifting grating.
+    ///
+    /// # Arguments
+    /// * `period` - Distance between stripes (pixels)
+    /// * `degrees` - Orientation of the stripes (0 = horizontal, 90 = vertical)
+    /// * `phase` - Offset of the stripes (pixels)
+    /// * `contrast` - Michelson contrast (0.0 = uniform grey, 1.0 = black to white)
+    pub fn sine_grating(width: usize, height: usize, period: f32, degrees: f32, phase: f32, contrast: f32) -> Vec<Vec<f32>> {
+        let angle = degrees.to_radians();
+        let frequency = 2.0 * std::f32::consts::PI / period.max(2.0);
+        let amplitude = 0.5 * contrast.clamp(0.0, 1.0);
+        (0..height)
+            .map(|y| {
+                (0..width)
+                    .map(|x| {
+                        // Distance across the stripes
+                        let across = -(x as f32) * angle.sin() + y as f32 * angle.cos();
+                        0.5 + amplitude * (frequency * (across - phase)).cos()
+                    })
+                    .collect()
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::test_patterns::*;
     use super::*;
+    use crate::color_vision::ColorVisionDeficiency;
+    use crate::eye_movements::FixationalEyeMovements;
+    use crate::mosaic::MosaicPacking;
+    use crate::v1_cortex::V1NeuronType;
 
     #[test]
     fn test_visual_pathway_creation() {
@@ -383,6 +1652,599 @@ mod tests {
                  response.features.vertical_strength);
     }
 
+    #[test]
+    fn test_default_mosaic_is_hexagonal() {
+        let pathway = VisualPathway::new(40, 40);
+        let mosaic = pathway.mosaic();
+        assert_eq!(mosaic.packing(), MosaicPacking::Hexagonal);
+
+        // The reported ratio is that of the cones actually placed
+        for cone_type in [ConeType::L, ConeType::M, ConeType::S] {
+            assert!((pathway.cone_population().fraction(cone_type) - mosaic.fraction(cone_type)).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_cones_sample_their_mosaic_position() {
+        // Even rows sit on pixel centres, odd rows halfway between two pixels
+        assert_eq!(bilinear_taps((3.0, 2.0), 8, 8), Some(vec![((3, 2), 1.0)]));
+        assert_eq!(bilinear_taps((3.5, 1.0), 8, 8), Some(vec![((3, 1), 0.5), ((4, 1), 0.5)]));
+        assert_eq!(bilinear_taps((7.5, 1.0), 8, 8), Some(vec![((7, 1), 0.5), ((7, 1), 0.5)]));
+        assert_eq!(bilinear_taps((8.0, 1.0), 8, 8), None);
+    }
+
+    #[test]
+    fn test_mesopic_rod_weight() {
+        assert_eq!(mesopic_rod_weight(0.01), 1.0);
+        assert_eq!(mesopic_rod_weight(100.0), 0.0);
+
+        let mesopic = mesopic_rod_weight(1.0);
+        assert!(mesopic > 0.0 && mesopic < 1.0);
+    }
+
+    #[test]
+    fn test_luminance_range() {
+        let image = vertical_bar(32, 32);
+
+        // Starlight to daylight: the pathway should see the bar at every level
+        for &luminance in &[0.01, 0.1, 1.0, 10.0, 100.0] {
+            let mut pathway = VisualPathway::new(32, 32);
+            let response = pathway.process_grayscale_image_at_luminance(&image, luminance);
+
+            assert!(
+                response.features.edge_strength() > 0.0,
+                "No edges detected at luminance {}",
+                luminance
+            );
+        }
+
+        // Rods dominate in the dark and are silenced in bright daylight
+        let mut dark = VisualPathway::new(32, 32);
+        let dark_response = dark.process_grayscale_image_at_luminance(&image, 0.01);
+        let mut bright = VisualPathway::new(32, 32);
+        let bright_response = bright.process_grayscale_image_at_luminance(&image, 100.0);
+
+        assert_eq!(dark_response.rod_contribution, 1.0);
+        assert_eq!(bright_response.rod_contribution, 0.0);
+    }
+
+    #[test]
+    fn test_spectral_scene() {
+        use crate::spectrum::SpectralPowerDistribution;
+
+        // Daylight bar on a dark background, described by full spectra
+        let image = vertical_bar(32, 32);
+        let scene: Vec<Vec<SpectralPowerDistribution>> = image
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|&reflectance| SpectralPowerDistribution::d65(reflectance * 100.0))
+                    .collect()
+            })
+            .collect();
+
+        let mut pathway = VisualPathway::new(32, 32);
+        let response = pathway.process_scene(&scene);
+        assert!(response.features.edge_strength() > 0.0);
+    }
+
+    #[test]
+    fn test_color_image() {
+        // Red bar on a green background: no luminance edge needed to see it
+        let mut image = vec![vec![[0.0, 0.6, 0.0]; 32]; 32];
+        for row in image.iter_mut() {
+            for pixel in row.iter_mut().skip(12).take(8) {
+                *pixel = [1.0, 0.0, 0.0];
+            }
+        }
+
+        let mut pathway = VisualPathway::new(32, 32);
+        let response = pathway.process_color_image(&image);
+        assert!(response.features.edge_strength() > 0.0);
+    }
+
+    #[test]
+    fn test_color_image_drives_cone_classes() {
+        let blue = vec![vec![[0.0, 0.0, 1.0]; 20]; 20];
+        let mut pathway = VisualPathway::new(20, 20);
+        let response = pathway.process_color_image(&blue);
+
+        let mean_activation = |cone_type: ConeType| -> f32 {
+            let mosaic = pathway.mosaic();
+            let activations: Vec<f32> = (0..20)
+                .flat_map(|y| (0..20).map(move |x| (x, y)))
+                .filter(|&(x, y)| mosaic.cone_type_at(x, y) == cone_type)
+                .map(|(x, y)| response.cone_activations[y][x])
+                .collect();
+            activations.iter().sum::<f32>() / activations.len() as f32
+        };
+        assert!(mean_activation(ConeType::S) > mean_activation(ConeType::L));
+    }
+
+    #[test]
+    fn test_protanope_misses_red_green_edge() {
+        // Red bar on a green background matched for M-cone excitation
+        let background = vec![vec![[0.0, 0.49, 0.0]; 32]; 32];
+        let mut image = background.clone();
+        for row in image.iter_mut() {
+            for pixel in row.iter_mut().skip(12).take(8) {
+                *pixel = [1.0, 0.0, 0.0];
+            }
+        }
+
+        // Edge signal caused by the bar, over the texture of the mosaic itself
+        let bar_edges = |population: ConePopulation| -> f32 {
+            let edge_sum = |image: &[Vec<[f32; 3]>]| -> f32 {
+                let mut pathway = VisualPathway::with_cone_population(32, 32, population);
+                pathway.process_color_image(image).edge_map.iter().flatten().sum()
+            };
+            edge_sum(&image) - edge_sum(&background)
+        };
+
+        let normal = bar_edges(ConePopulation::normal());
+        let protanope = bar_edges(ConePopulation::from_deficiency(ColorVisionDeficiency::Protanopia));
+        assert!(protanope < normal * 0.5);
+    }
+
+    #[test]
+    fn test_foveated_pathway() {
+        let foveation = Foveation::centered(128, 128);
+        let mut pathway = VisualPathway::foveated(128, 128, foveation);
+        assert!(pathway.cone_count() < 128 * 128 / 4);
+
+        // A vertical bar through fixation is still resolved
+        let response = pathway.process_grayscale_image(&vertical_bar(128, 128));
+        assert!(response.features.vertical_strength > response.features.horizontal_strength);
+
+        // The cortex works on the log-polar map, much smaller than the field
+        let (cortex_width, cortex_height) = (response.orientation_map[0].len(), response.orientation_map.len());
+        assert!(cortex_width * cortex_height < 128 * 128 / 4);
+        assert_eq!(pathway.mosaic().packing(), MosaicPacking::Hexagonal);
+
+        let cortical_map = response.cortical_map.expect("foveated pathway maps edges onto V1");
+        assert_eq!((cortical_map[0].len(), cortical_map.len()), (cortex_width, cortex_height));
+        assert!(cortical_map.iter().flatten().any(|&v| v > 0.0));
+
+        // Orientations are read in the visual field, not on the map
+        let mut pathway = VisualPathway::foveated(128, 128, foveation);
+        let response = pathway.process_grayscale_image(&horizontal_bar(128, 128));
+        assert!(response.features.horizontal_strength > response.features.vertical_strength);
+        assert!(VisualPathway::new(8, 8).process_grayscale_image(&vertical_bar(8, 8)).cortical_map.is_none());
+    }
+
+    #[test]
+    fn test_v1_sees_contrast_polarity() {
+        // Summed activation of the even simple cells: (ON-centre, OFF-centre)
+        let even_cells = |pathway: &VisualPathway| {
+            let neurons = pathway.v1_cortex().columns().iter().flat_map(|column| column.neurons());
+            neurons
+                .filter(|neuron| neuron.neuron_type() == V1NeuronType::Simple)
+                .fold((0.0, 0.0), |(on, off), neuron| match neuron.preferred_phase() {
+                    0.0 => (on + neuron.activation(), off),
+                    180.0 => (on, off + neuron.activation()),
+                    _ => (on, off),
+                })
+        };
+        let inverted = |image: &[Vec<f32>]| -> Vec<Vec<f32>> {
+            image.iter().map(|row| row.iter().map(|v| 1.0 - v).collect()).collect()
+        };
+
+        // A light bar and a dark bar tip the balance in opposite directions
+        // (the bar's two flanks outweigh the bar itself), on the plain and
+        // on the foveated cortex
+        for (size, foveated) in [(32, false), (128, true)] {
+            let balance = |image: &[Vec<f32>]| {
+                let mut pathway = if foveated {
+                    VisualPathway::foveated(size, size, Foveation::centered(size, size))
+                } else {
+                    VisualPathway::new(size, size)
+                };
+                pathway.process_grayscale_image(image);
+                let (on, off) = even_cells(&pathway);
+                on / off
+            };
+            let light = vertical_bar(size, size);
+            let (light_balance, dark_balance) = (balance(&light), balance(&inverted(&light)));
+            assert!(light_balance < 1.0 && dark_balance > 1.0, "{} vs {}", light_balance, dark_balance);
+        }
+    }
+
+    #[test]
+    fn test_simultaneous_contrast() {
+        // The same grey patch looks lighter on a dark surround than on a bright one
+        let patch_on = |surround: f32| -> f32 {
+            let mut image = vec![vec![surround; 32]; 32];
+            for row in image.iter_mut().skip(12).take(8) {
+                for pixel in row.iter_mut().skip(12).take(8) {
+                    *pixel = 0.5;
+                }
+            }
+            let mut pathway = VisualPathway::new(32, 32);
+            pathway.process_grayscale_image(&image);
+            (14..18)
+                .flat_map(|y| (14..18).map(move |x| y * 32 + x))
+                .map(|index| pathway.cones()[index].synaptic_output())
+                .sum()
+        };
+
+        assert!(patch_on(0.05) > patch_on(1.0));
+    }
+
+    #[test]
+    fn test_s_cones_have_blue_yellow_surround() {
+        // Blue patch seen by S-cones, on a black or a yellow surround
+        let s_output_on = |surround: [f32; 3]| -> f32 {
+            let mut image = vec![vec![surround; 32]; 32];
+            for row in image.iter_mut().skip(12).take(8) {
+                for pixel in row.iter_mut().skip(12).take(8) {
+                    *pixel = [0.0, 0.0, 0.6];
+                }
+            }
+            let mut pathway = VisualPathway::new(32, 32);
+            pathway.process_color_image(&image);
+            (13..19)
+                .flat_map(|y| (13..19).map(move |x| y * 32 + x))
+                .map(|index| &pathway.cones()[index])
+                .filter(|cone| cone.cone_type() == ConeType::S)
+                .map(|cone| cone.synaptic_output())
+                .sum()
+        };
+
+        assert!(s_output_on([0.0, 0.0, 0.0]) > s_output_on([1.0, 1.0, 0.0]));
+    }
+
+    #[test]
+    fn test_bipolar_on_off_channels() {
+        let mut pathway = VisualPathway::new(32, 32);
+        let image = vertical_bar(32, 32);
+        let response = pathway.process_grayscale_image(&image);
+
+        // Bright bar in the middle columns, dark background at the edges
+        let (on, off) = (&response.on_bipolar_activations, &response.off_bipolar_activations);
+        assert!(on[16][16] > on[16][2]);
+        assert!(off[16][16] < off[16][2]);
+        assert!(response.edge_map.iter().flatten().sum::<f32>() > 0.0);
+
+        // Transient cells respond to the onset and adapt to a repeated frame
+        let onset: f32 = pathway.bipolar_activations(BipolarType::OnTransient).iter().flatten().sum();
+        pathway.process_grayscale_image(&image);
+        let repeat: f32 = pathway.bipolar_activations(BipolarType::OnTransient).iter().flatten().sum();
+        assert!(onset > 0.0);
+        assert!(repeat < onset * 0.75);
+    }
+
+    #[test]
+    fn test_retinal_direction_selectivity() {
+        let motion_of = |frames: Vec<Vec<Vec<f32>>>| {
+            let mut pathway = VisualPathway::new(32, 32);
+            let mut totals = [0.0; 4];
+            // The first frame is a sudden onset, not motion
+            pathway.process_grayscale_image(&frames[0]);
+            for frame in &frames[1..] {
+                let response = pathway.process_grayscale_image(frame);
+                for (total, rate) in totals.iter_mut().zip(response.retinal_motion.direction_responses) {
+                    *total += rate;
+                }
+            }
+            RetinalMotion { direction_responses: totals }
+        };
+
+        let rightward = motion_of((0..10).map(|i| bar_at(32, 32, 6, 4 + 2 * i)).collect());
+        let leftward = motion_of((0..10).map(|i| bar_at(32, 32, 6, 22 - 2 * i)).collect());
+        assert_eq!(rightward.dominant_direction(), Some(MotionDirection::Rightward));
+        assert_eq!(leftward.dominant_direction(), Some(MotionDirection::Leftward));
+        assert!(rightward.direction_index(MotionDirection::Rightward) > 0.5);
+        assert!(leftward.direction_index(MotionDirection::Leftward) > 0.5);
+
+        let drifting = motion_of((0..10).map(|i| grating(32, 32, 8, 2 * i)).collect());
+        assert_eq!(drifting.dominant_direction(), Some(MotionDirection::Rightward));
+    }
+
+    #[test]
+    fn test_color_opponent_maps() {
+        let mean_opponency = |color: [f32; 3]| {
+            let mut pathway = VisualPathway::new(32, 32);
+            let response = pathway.process_color_image(&vec![vec![color; 32]; 32]);
+            let mean = |map: &Vec<Vec<f32>>| map.iter().flatten().sum::<f32>() / (32.0 * 32.0);
+            (mean(&response.red_green_map), mean(&response.blue_yellow_map))
+        };
+
+        let (red, _) = mean_opponency([0.3, 0.0, 0.0]);
+        let (green, _) = mean_opponency([0.0, 0.3, 0.0]);
+        let (_, blue) = mean_opponency([0.0, 0.0, 0.3]);
+        let (_, yellow) = mean_opponency([0.3, 0.3, 0.0]);
+
+        assert!(red > 0.0 && green < 0.0);
+        assert!(blue > 0.0 && yellow < 0.0);
+    }
+
+    #[test]
+    fn test_parallel_pathways() {
+        let image = vertical_bar(32, 32);
+        let mut pathway = VisualPathway::new(32, 32);
+        let first = pathway.process_grayscale_image(&image);
+        let repeat = pathway.process_grayscale_image(&image);
+        let total = |map: &Vec<Vec<f32>>| map.iter().flatten().sum::<f32>();
+
+        // Parvo (sustained midget) persists, magno (transient parasol) fades
+        let parvo_ratio = total(&repeat.lgn.parvocellular) / total(&first.lgn.parvocellular);
+        let magno_ratio = total(&repeat.lgn.magnocellular) / total(&first.lgn.magnocellular);
+        assert!(parvo_ratio > 0.75);
+        assert!(magno_ratio < 0.75 * parvo_ratio);
+
+        // V1 can read the magnocellular stream instead
+        let mut magno = VisualPathway::new(32, 32).with_v1_stream(LgnLayerType::Magnocellular);
+        let response = magno.process_grayscale_image(&image);
+        assert_eq!(magno.v1_stream(), LgnLayerType::Magnocellular);
+        assert!(response.features.vertical_strength > response.features.horizontal_strength);
+    }
+
+    #[test]
+    fn test_lgn_attention_in_pathway() {
+        let image = cross(32, 32);
+        let total = |map: &Vec<Vec<f32>>, x0: usize| -> f32 {
+            map.iter().flat_map(|row| row[x0..x0 + 16].iter()).sum()
+        };
+
+        let mut pathway = VisualPathway::new(32, 32);
+        let neutral = pathway.process_grayscale_image(&image);
+
+        let mut lgn = LateralGeniculateNucleus::new(32, 32);
+        lgn.attend(8.0, 16.0, 12.0, 3.0);
+        let mut attending = VisualPathway::new(32, 32).with_lgn(lgn);
+        let attended = attending.process_grayscale_image(&image);
+
+        // Relay activity rises on the attended (left) side only
+        let left_gain = total(&attended.lgn.parvocellular, 0) / total(&neutral.lgn.parvocellular, 0);
+        let right_gain = total(&attended.lgn.parvocellular, 16) / total(&neutral.lgn.parvocellular, 16);
+        assert!(left_gain > 1.5);
+        assert!(right_gain < left_gain);
+        assert_eq!(attended.edge_map, neutral.edge_map);
+    }
+
+    #[test]
+    fn test_lgn_relay_mode_reaches_v1() {
+        let image = vertical_bar(32, 32);
+        let grey = vec![vec![0.5; 32]; 32];
+        let v1_total = |pathway: &VisualPathway| -> f32 {
+            pathway.v1_cortex().orientation_tuning().iter().map(|(_, activation)| activation).sum()
+        };
+        // A grey field gives hyperpolarized relay cells time to de-inactivate
+        // their T-channels before the bar appears
+        let respond = |lgn: LateralGeniculateNucleus| {
+            let mut pathway = VisualPathway::new(32, 32).with_lgn(lgn);
+            for _ in 0..5 {
+                pathway.process_grayscale_image(&grey);
+            }
+            let response = pathway.process_grayscale_image(&image);
+            (v1_total(&pathway), response.edge_map)
+        };
+
+        let (awake, awake_retina) = respond(LateralGeniculateNucleus::new(32, 32));
+        let mut drowsy_lgn = LateralGeniculateNucleus::new(32, 32);
+        drowsy_lgn.set_resting_potential(-75.0);
+        let (drowsy, drowsy_retina) = respond(drowsy_lgn);
+
+        // The retina sees the same bar; the burst comes from the LGN
+        assert_eq!(awake_retina, drowsy_retina);
+        assert!(drowsy > 2.0 * awake, "{} vs {}", drowsy, awake);
+    }
+
+    #[test]
+    fn test_ganglion_spikes_drive_network() {
+        use crate::network::NeuralNetwork;
+        use crate::spike_encoding::SpikeCoding;
+
+        let mut pathway = VisualPathway::new(32, 32);
+        pathway.process_grayscale_image(&vertical_bar(32, 32));
+        let mut encoder = SpikeEncoder::new(SpikeCoding::Poisson, 100).with_seed(1);
+        let trains = pathway.ganglion_spike_trains(&mut encoder);
+        assert_eq!(trains.len(), pathway.midget_layer().cells().len());
+
+        // Cells along the bar's edges fire, cells in uniform regions stay silent
+        let active = trains.iter().filter(|t| t.spike_count() > 0).count();
+        assert!(active > 0 && active < trains.len() / 2);
+
+        let mut network = NeuralNetwork::new();
+        let targets: Vec<usize> = trains.iter().map(|_| network.add_neuron()).collect();
+        network.run_spike_trains(&trains, &targets, encoder.window_ms());
+        for (train, &target) in trains.iter().zip(&targets) {
+            let fired = !network.get_neuron(target).spike_history().is_empty();
+            assert_eq!(fired, train.spike_count() > 0);
+        }
+    }
+
+    #[test]
+    fn test_v1_adaptation_is_opt_in_for_static_images() {
+        let image = vertical_bar(32, 32);
+        let v1_total = |pathway: &VisualPathway| -> f32 {
+            pathway.v1_cortex().orientation_tuning().iter().map(|&(_, activation)| activation).sum()
+        };
+        let v1_min_gain = |pathway: &VisualPathway| -> f32 {
+            pathway
+                .v1_cortex()
+                .columns()
+                .iter()
+                .flat_map(|column| column.neurons())
+                .map(|neuron| neuron.adaptation_gain())
+                .fold(1.0, f32::min)
+        };
+
+        // By default V1 meets every static image unadapted
+        let mut pathway = VisualPathway::new(32, 32);
+        assert!(!pathway.v1_adaptation());
+        for _ in 0..6 {
+            pathway.process_grayscale_image(&image);
+        }
+        assert_eq!(v1_min_gain(&pathway), 1.0);
+
+        // With adaptation enabled, V1 fatigues over repeated images
+        let mut adapting = VisualPathway::new(32, 32).with_v1_adaptation(true);
+        adapting.process_grayscale_image(&image);
+        let fresh = v1_total(&adapting);
+        for _ in 0..5 {
+            adapting.process_grayscale_image(&image);
+        }
+        assert!(v1_total(&adapting) < fresh);
+        assert!(v1_min_gain(&adapting) < 0.95);
+
+        // Movie frames always adapt V1
+        pathway.process_frame(&image);
+        assert!(v1_min_gain(&pathway) < 1.0);
+    }
+
+    #[test]
+    fn test_frame_sequence_is_temporally_filtered() {
+        let blank = vec![vec![0.0; 32]; 32];
+        let bar = vertical_bar(32, 32);
+        let energy = |map: &Vec<Vec<f32>>| -> f32 { map.iter().flatten().sum() };
+
+        let mut static_pathway = VisualPathway::new(32, 32);
+        let static_edges = energy(&static_pathway.process_grayscale_image(&bar).edge_map);
+
+        let mut pathway = VisualPathway::new(32, 32).with_frame_interval(10.0);
+        let mut frames = vec![blank.clone(); 2];
+        frames.extend(vec![bar.clone(); 30]);
+        frames.extend(vec![blank.clone(); 40]);
+        let responses = pathway.process_sequence(&frames);
+        let edges: Vec<f32> = responses.iter().map(|r| energy(&r.edge_map)).collect();
+
+        assert_eq!(responses.len(), frames.len());
+        assert!((pathway.elapsed_ms() - 720.0).abs() < 1e-3);
+        // Onset transient, then a sustained response near the static one
+        let onset_peak = edges[2..8].iter().cloned().fold(0.0, f32::max);
+        assert!(onset_peak > edges[31] * 1.1);
+        assert!(edges[31] > static_edges * 0.5 && edges[31] < static_edges * 1.5);
+        // Responses decay once the bar disappears, leaving a faint
+        // afterimage that fades with photoreceptor light adaptation
+        assert!(edges[71] < edges[51] * 0.5);
+        assert!(edges[71] < edges[31] * 0.2);
+
+        pathway.reset_temporal_filters();
+        assert_eq!(pathway.elapsed_ms(), 0.0);
+    }
+
+    #[test]
+    fn test_single_image_is_invariant_to_mean_luminance() {
+        let image = checkerboard(32, 32, 8);
+        let energy = |response: &VisualResponse| -> f32 { response.edge_map.iter().flatten().sum() };
+
+        // A single image at luminances spanning three log units, all in the Weber range
+        let responses: Vec<f32> = [100.0, 1_000.0, 10_000.0, 100_000.0]
+            .iter()
+            .map(|&luminance| {
+                let mut pathway = VisualPathway::new(32, 32);
+                energy(&pathway.process_grayscale_image_at_luminance(&image, luminance))
+            })
+            .collect();
+
+        let max = responses.iter().cloned().fold(f32::MIN, f32::max);
+        let min = responses.iter().cloned().fold(f32::MAX, f32::min);
+        assert!(min > 0.0);
+        assert!(max / min < 1.5);
+    }
+
+    #[test]
+    fn test_fixational_movements_refresh_static_scene() {
+        let scene = checkerboard(64, 64, 4);
+        let magno = |response: &VisualResponse| -> f32 { response.lgn.magnocellular.iter().flatten().sum() };
+        let late_magno = |eye: &mut Eye| -> f32 {
+            let mut pathway = VisualPathway::new(32, 32);
+            let responses = pathway.process_through_eye(&scene, eye, 30);
+            responses[20..].iter().map(|(_, response)| magno(response)).sum::<f32>() / 10.0
+        };
+
+        // A stabilized image fades from the transient (magnocellular) stream...
+        let stabilized = late_magno(&mut Eye::still((32.0, 32.0)));
+        // ...while drift, tremor and microsaccades keep it visible
+        let moving = late_magno(&mut Eye::new((32.0, 32.0), FixationalEyeMovements::new(), 4));
+        assert!(moving > 2.0 * stabilized);
+    }
+
+    #[test]
+    fn test_pupil_reflex_closes_the_loop() {
+        let pupil_after = |reflectance: f32, frames: usize| -> Vec<f32> {
+            let mut pathway = VisualPathway::new(32, 32).with_pupil_reflex(EyeOptics::standard());
+            let frame = vec![vec![reflectance; 32]; 32];
+            (0..frames).map(|_| pathway.process_frame(&frame).pupil_diameter.unwrap()).collect()
+        };
+
+        let bright = pupil_after(1.0, 60);
+        let dim = pupil_after(0.05, 60);
+
+        // The dilated pupil holds still through the reflex latency...
+        assert!(bright[..7].iter().all(|&diameter| diameter == 7.0));
+        // ...then constricts, more for brighter light
+        assert!(bright[59] < 5.0);
+        assert!(dim[59] > bright[59] + 1.0);
+
+        // The constricted pupil admits less light on later frames
+        let mut pathway = VisualPathway::new(32, 32).with_pupil_reflex(EyeOptics::standard());
+        let dark_adapted = pathway.optics().unwrap().retinal_illuminance();
+        for _ in 0..60 {
+            pathway.process_frame(&vec![vec![1.0; 32]; 32]);
+        }
+        assert!(pathway.optics().unwrap().retinal_illuminance() < 0.6 * dark_adapted);
+        assert!(pathway.melanopsin_layer().unwrap().mean_firing_rate() > 0.0);
+        assert!(VisualPathway::new(8, 8).process_frame(&vec![vec![1.0; 8]; 8]).pupil_diameter.is_none());
+    }
+
+    #[test]
+    fn test_optics_attenuate_fine_gratings() {
+        // Amplitude of the grating in the cone image (rows averaged over the mosaic)
+        let amplitude = |response: &VisualResponse, period: f32| -> f32 {
+            let (mut re, mut im) = (0.0, 0.0);
+            for (y, row) in response.cone_activations.iter().enumerate() {
+                let mean = row.iter().sum::<f32>() / row.len() as f32;
+                let angle = 2.0 * std::f32::consts::PI * y as f32 / period;
+                re += mean * angle.cos();
+                im += mean * angle.sin();
+            }
+            re.hypot(im)
+        };
+        let transfer = |period: f32| -> f32 {
+            let image = sine_grating(32, 32, period, 0.0, 0.0, 0.2);
+            let sharp = amplitude(&VisualPathway::new(32, 32).process_grayscale_image(&image), period);
+            let mut pathway = VisualPathway::new(32, 32).with_optics(EyeOptics::standard());
+            amplitude(&pathway.process_grayscale_image(&image), period) / sharp.max(f32::EPSILON)
+        };
+
+        // At 120 pixels per degree: 40 cpd is mostly lost, 7.5 cpd survives
+        let fine = transfer(3.0);
+        let coarse = transfer(16.0);
+        assert!(coarse > 0.7);
+        assert!(fine < 0.4 * coarse);
+
+        // Perfect focus is the default
+        assert!(VisualPathway::new(32, 32).optics().is_none());
+    }
+
+    #[test]
+    fn test_orientation_histogram() {
+        let image = sine_grating(64, 64, 16.0, 120.0, 0.0, 1.0);
+        let mut pathway = VisualPathway::new(64, 64).with_v1_cortex(V1Cortex::with_orientation_channels(64, 64, 8, 5, 12));
+        let response = pathway.process_grayscale_image(&image);
+
+        let histogram = &response.features.orientation_histogram;
+        assert_eq!(histogram.len(), 12);
+        let (peak, _) = histogram.iter().max_by(|a, b| a.1.total_cmp(&b.1)).unwrap();
+        assert_eq!(peak.degrees(), 120.0);
+
+        let estimate = response.features.orientation_estimate().unwrap();
+        assert!((estimate.orientation.degrees() - 120.0).abs() < 10.0);
+        assert!(response.orientation_estimates.iter().flatten().flatten().count() > 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "cortical field")]
+    fn test_v1_cortex_must_cover_the_cortical_field() {
+        // A foveated pathway's V1 covers the cortical map, not the visual field
+        let foveation = Foveation::centered(128, 128);
+        let (width, height) = foveation.cortical_grid(128, 128, 1.0).dimensions();
+        let _ = VisualPathway::foveated(128, 128, foveation).with_v1_cortex(V1Cortex::new(width, height, 8, 5));
+        let _ = VisualPathway::foveated(128, 128, foveation).with_v1_cortex(V1Cortex::new(128, 128, 8, 5));
+    }
+
     #[test]
     fn test_cross_pattern() {
         let mut pathway = VisualPathway::new(32, 32);